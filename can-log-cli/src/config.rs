@@ -1,6 +1,7 @@
 //! Configuration loading and parsing (Phase 7)
 
 use anyhow::{Context, Result};
+use can_log_decoder::CanTpAddressing;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -75,6 +76,8 @@ pub struct CanTpPairConfig {
     pub source: u32,
     pub target: u32,
     pub name: Option<String>,
+    #[serde(default)]
+    pub addressing: CanTpAddressing,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -9,7 +9,7 @@
 //! - Report generation (TXT/HTML)
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 mod config;
@@ -24,16 +24,19 @@ mod report;
 #[command(about = "Decode and analyze CAN log files (BLF, MF4)", long_about = None)]
 #[command(version)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to BLF/MF4 log file to decode
     #[arg(short, long, value_name = "FILE")]
     log: Option<PathBuf>,
 
     /// Path to DBC file(s) (can be repeated)
-    #[arg(long, value_name = "FILE")]
+    #[arg(long, value_name = "FILE", global = true)]
     dbc: Vec<PathBuf>,
 
     /// Path to ARXML file(s) (can be repeated)
-    #[arg(long, value_name = "FILE")]
+    #[arg(long, value_name = "FILE", global = true)]
     arxml: Vec<PathBuf>,
 
     /// Output file for decoded signals (default: stdout)
@@ -57,6 +60,18 @@ struct Args {
     quiet: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check the DBC/ARXML files for conflicting and inconsistent definitions
+    Validate,
+    /// Show file-level metadata of a BLF/MF4 log file
+    Info {
+        /// Path to BLF/MF4 log file
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+}
+
 fn main() -> Result<()> {
     // Parse command line arguments
     let args = Args::parse();
@@ -68,7 +83,11 @@ fn main() -> Result<()> {
     log::info!("Using decoder library v{}", can_log_decoder::VERSION);
 
     // Check if simple decode mode or config mode
-    if args.log.is_some() || !args.dbc.is_empty() || !args.arxml.is_empty() {
+    if let Some(Command::Validate) = &args.command {
+        validate_mode(&args)?;
+    } else if let Some(Command::Info { file }) = &args.command {
+        info_mode(file)?;
+    } else if args.log.is_some() || !args.dbc.is_empty() || !args.arxml.is_empty() {
         // Simple decode mode - just decode and print signals
         simple_decode_mode(&args)?;
     } else if let Some(config_path) = &args.config {
@@ -90,14 +109,124 @@ fn main() -> Result<()> {
 
 /// Simple decode mode - load signals, decode log, print results
 fn simple_decode_mode(args: &Args) -> Result<()> {
-    use can_log_decoder::Decoder;
-    use std::fs::File;
-    use std::io::{self, Write};
-
     println!("═══════════════════════════════════════════════");
     println!("  CAN Log Decoder - Simple Mode");
     println!("═══════════════════════════════════════════════\n");
 
+    let decoder = load_databases(args)?;
+
+    // Show database stats
+    let stats = decoder.database_stats();
+    println!("\n📊 Signal Database:");
+    println!("  Messages: {}", stats.num_messages);
+    println!("  Signals:  {}", stats.num_signals);
+    println!("  Containers: {}", stats.num_containers);
+
+    // Check if we have a log file to decode
+    if let Some(log_path) = &args.log {
+        println!("\n📄 Decoding log file: {:?}", log_path);
+        println!("───────────────────────────────────────────────\n");
+
+        // TODO: Implement actual decoding when BLF parser is complete
+        // For now, just show what would happen
+        println!("⚠️  Log file parsing not yet implemented (Phase 3 stub)");
+        println!("   BLF parser integration coming in next session!");
+        println!("\nWhat WILL work when BLF parser is ready:");
+        println!("  ✓ Parse BLF file");
+        println!("  ✓ Extract CAN frames");
+        println!("  ✓ Decode signals using loaded DBC/ARXML");
+        println!("  ✓ Show physical values with units");
+        println!("  ✓ Handle multiplexed signals");
+
+    } else {
+        println!("\n✓ Signal database loaded successfully!");
+        println!("  Add --log <file.blf> to decode CAN frames");
+    }
+
+    Ok(())
+}
+
+/// Validate mode - load signal files and report database problems
+fn validate_mode(args: &Args) -> Result<()> {
+    if args.dbc.is_empty() && args.arxml.is_empty() {
+        anyhow::bail!("No signal files given (use --dbc and/or --arxml)");
+    }
+
+    let decoder = load_databases(args)?;
+    let report = decoder.validate_database();
+
+    if report.is_ok() {
+        println!("\n✓ No conflicts or inconsistencies found");
+    } else {
+        println!("\n✗ {} problem(s) found:", report.issues.len());
+        for issue in &report.issues {
+            println!("  • {}", issue);
+        }
+    }
+
+    if !report.shared_signal_names.is_empty() {
+        println!("\nSignal names used by several messages (lookups by name bind to the first):");
+        for shared in &report.shared_signal_names {
+            println!("  • {}: {}", shared.name, shared.messages.join(", "));
+        }
+    }
+
+    if !report.is_ok() {
+        anyhow::bail!("{} problem(s) found in the signal database", report.issues.len());
+    }
+    Ok(())
+}
+
+/// Info mode - print file-level metadata of a log file
+fn info_mode(path: &PathBuf) -> Result<()> {
+    use can_log_decoder::formats::LogFileInfo;
+
+    let info = LogFileInfo::from_file(path)?;
+    let time = |t: Option<can_log_decoder::Timestamp>| {
+        t.map_or_else(|| "unknown".to_string(), |t| t.to_rfc3339())
+    };
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    // MF4 frames are only counted, their flags are not known
+    let frame_flag = |b: bool| if info.duration.is_some() { yes_no(b) } else { "unknown" };
+
+    println!("📄 {:?}", path);
+    println!("  Format:     {}", info.format);
+    println!("  Writer:     {}", info.writer);
+    println!("  Compressed: {}", info.compressed.map_or("unknown", yes_no));
+    println!("  Start:      {}", time(info.start_time));
+    println!("  End:        {}", time(info.end_time));
+    println!(
+        "  Duration:   {}",
+        info.duration.map_or_else(|| "unknown".to_string(), |d| format!("{:.3} s", d.as_secs_f64()))
+    );
+    println!("  Frames:     {}", info.frame_count);
+    println!("  CAN-FD:     {}", frame_flag(info.has_fd()));
+    println!("  Extended:   {}", frame_flag(info.has_extended()));
+
+    if !info.channels.is_empty() {
+        println!("\n{:<10} {:>10} {:>10} {:>10} {:>10} {:>10}", "Channel", "Frames", "FD", "Extended", "Remote", "Error");
+        for (channel, stats) in &info.channels {
+            println!(
+                "{:<10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                channel, stats.frames, stats.fd_frames, stats.extended_frames, stats.remote_frames, stats.error_frames
+            );
+        }
+    }
+
+    if !info.object_types.is_empty() {
+        println!("\n{:<10} {:>10}", "Object", "Count");
+        for (object_type, count) in &info.object_types {
+            println!("{:<10} {:>10}", object_type, count);
+        }
+    }
+    Ok(())
+}
+
+/// Load the DBC and ARXML files given on the command line
+fn load_databases(args: &Args) -> Result<can_log_decoder::Decoder> {
+    use can_log_decoder::Decoder;
+    use std::io::{self, Write};
+
     // Create decoder
     let mut decoder = Decoder::new();
 
@@ -129,35 +258,7 @@ fn simple_decode_mode(args: &Args) -> Result<()> {
         }
     }
 
-    // Show database stats
-    let stats = decoder.database_stats();
-    println!("\n📊 Signal Database:");
-    println!("  Messages: {}", stats.num_messages);
-    println!("  Signals:  {}", stats.num_signals);
-    println!("  Containers: {}", stats.num_containers);
-
-    // Check if we have a log file to decode
-    if let Some(log_path) = &args.log {
-        println!("\n📄 Decoding log file: {:?}", log_path);
-        println!("───────────────────────────────────────────────\n");
-
-        // TODO: Implement actual decoding when BLF parser is complete
-        // For now, just show what would happen
-        println!("⚠️  Log file parsing not yet implemented (Phase 3 stub)");
-        println!("   BLF parser integration coming in next session!");
-        println!("\nWhat WILL work when BLF parser is ready:");
-        println!("  ✓ Parse BLF file");
-        println!("  ✓ Extract CAN frames");
-        println!("  ✓ Decode signals using loaded DBC/ARXML");
-        println!("  ✓ Show physical values with units");
-        println!("  ✓ Handle multiplexed signals");
-
-    } else {
-        println!("\n✓ Signal database loaded successfully!");
-        println!("  Add --log <file.blf> to decode CAN frames");
-    }
-
-    Ok(())
+    Ok(decoder)
 }
 
 /// Advanced config mode - full features (future phases)
@@ -167,7 +268,7 @@ fn advanced_config_mode(config_path: &PathBuf, _args: &Args) -> Result<()> {
     println!("═══════════════════════════════════════════════\n");
 
     log::info!("Loading configuration from: {:?}", config_path);
-    let config = config::load_config(config_path)?;
+    let _config = config::load_config(config_path)?;
     log::debug!("Configuration loaded successfully");
 
     println!("✓ Configuration loaded: {:?}", config_path);
@@ -0,0 +1,107 @@
+//! Signal decoding throughput benchmark
+//!
+//! Decodes a synthetic trace (10M frames by default) against a small DBC with
+//! little-endian, big-endian, signed, scaled and multiplexed signals and
+//! reports frames and signals per second.
+//!
+//! Usage:
+//!   cargo bench -p can-log-decoder --bench decode_throughput
+//!   BENCH_FRAMES=1000000 cargo bench -p can-log-decoder --bench decode_throughput
+//!   BENCH_SIGNALS=EngineSpeed,Odometer cargo bench -p can-log-decoder --bench decode_throughput
+
+use can_log_decoder::types::CanFrame;
+use can_log_decoder::{DecodedEvent, Decoder, DecoderConfig};
+use std::io::Write;
+use std::time::Instant;
+
+const DEFAULT_FRAMES: usize = 10_000_000;
+
+const DBC: &str = r#"VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU1
+
+BO_ 256 EngineData: 8 ECU1
+ SG_ EngineSpeed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" ECU1
+ SG_ EngineTemp : 16|8@1- (1,-40) [-40|215] "degC" ECU1
+ SG_ ThrottlePos : 24|10@1+ (0.1,0) [0|100] "%" ECU1
+ SG_ Gear : 34|4@1+ (1,0) [0|15] "" ECU1
+ SG_ EngineRunning : 38|1@1+ (1,0) [0|1] "" ECU1
+ SG_ Torque : 40|16@1- (0.5,0) [-16384|16383.5] "Nm" ECU1
+ SG_ Counter : 60|4@1+ (1,0) [0|15] "" ECU1
+
+BO_ 512 BodyData: 8 ECU1
+ SG_ VehicleSpeed : 7|16@0+ (0.01,0) [0|655.35] "km/h" ECU1
+ SG_ Acceleration : 23|12@0- (0.01,0) [-20.48|20.47] "m/s2" ECU1
+ SG_ DoorState : 27|4@0+ (1,0) [0|15] "" ECU1
+ SG_ Odometer : 39|24@0+ (0.1,0) [0|1677721.5] "km" ECU1
+ SG_ Checksum : 63|8@0+ (1,0) [0|255] "" ECU1
+
+BO_ 768 BatteryData: 8 ECU1
+ SG_ Mode M : 0|8@1+ (1,0) [0|3] "" ECU1
+ SG_ CellVoltage m0 : 8|16@1+ (0.001,0) [0|65.535] "V" ECU1
+ SG_ CellIndex m0 : 24|8@1+ (1,0) [0|255] "" ECU1
+ SG_ PackCurrent m1 : 8|16@1- (0.1,0) [-3276.8|3276.7] "A" ECU1
+ SG_ StateOfCharge m1 : 24|8@1+ (0.5,0) [0|127.5] "%" ECU1
+ SG_ PackTemp : 39|16@0- (0.1,0) [-3276.8|3276.7] "degC" ECU1
+"#;
+
+fn main() {
+    let frame_count = std::env::var("BENCH_FRAMES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_FRAMES);
+
+    let mut dbc_file = tempfile::Builder::new().suffix(".dbc").tempfile().expect("create DBC");
+    dbc_file.write_all(DBC.as_bytes()).expect("write DBC");
+    let mut decoder = Decoder::new();
+    decoder.add_dbc(dbc_file.path()).expect("load DBC");
+
+    // Deterministic payloads so runs are comparable
+    let ids = [0x100, 0x200, 0x300];
+    let frames = (0..frame_count).map(move |i| {
+        let seed = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let mut data = seed.to_le_bytes().to_vec();
+        if ids[i % ids.len()] == 0x300 {
+            data[0] = (i % 2) as u8;
+        }
+        Ok(CanFrame {
+            timestamp_ns: i as u64 * 100_000,
+            measurement_start_ns: 0,
+            channel: 0,
+            can_id: ids[i % ids.len()],
+            data,
+            is_extended: false,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        })
+    });
+
+    // Optional signal allow-list (comma separated)
+    let config = match std::env::var("BENCH_SIGNALS") {
+        Ok(signals) => DecoderConfig::new().with_signal_filter(signals.split(',')),
+        Err(_) => DecoderConfig::new(),
+    };
+
+    let start = Instant::now();
+    let mut messages = 0usize;
+    let mut signals = 0usize;
+    for event in decoder.decode_frames(frames, config) {
+        if let Ok(DecodedEvent::Message { signals: decoded, .. }) = event {
+            messages += 1;
+            signals += decoded.len();
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    println!("Decoded {} frames ({} messages, {} signals) in {:.2} s", frame_count, messages, signals, elapsed);
+    println!(
+        "  {:.2} M frames/s, {:.2} M signals/s",
+        frame_count as f64 / elapsed / 1e6,
+        signals as f64 / elapsed / 1e6
+    );
+}
@@ -6,6 +6,7 @@
 use std::env;
 use std::path::PathBuf;
 
+#[cfg_attr(not(target_os = "windows"), allow(unused_variables, unused_assignments))]
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=../vendor/mdflib");
@@ -145,8 +146,6 @@ fn main() {
     }
 
     // Build our C API wrapper that bridges Rust and mdflib C++
-    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
-
     let mut cxx_build = cc::Build::new();
 
     #[cfg(all(target_os = "windows", target_env = "msvc"))]
@@ -162,7 +161,7 @@ fn main() {
         .cpp(true)
         .file(mdflib_src.join("mdf_c_api.cpp"))
         .include(mdflib_src.join("include"))
-        .include(&dst.join("include"))  // mdflib headers
+        .include(dst.join("include"))  // mdflib headers
         .flag_if_supported("/std:c++17")  // MSVC
         .flag_if_supported("-std=c++17")  // GCC/Clang
         .compile("mdf_c_api");
@@ -7,11 +7,8 @@
 //! - What CAN message types are present
 //! - File structure details
 
-use ablf::BlfFile;
-use std::collections::HashMap;
+use can_log_decoder::formats::LogFileInfo;
 use std::env;
-use std::fs::File;
-use std::io::BufReader;
 use std::path::Path;
 
 fn main() {
@@ -52,19 +49,9 @@ fn main() {
 
     println!("📊 File Size: {:.2} KB ({} bytes)", file_size as f64 / 1024.0, file_size);
 
-    // Open and parse BLF file
-    let file = match File::open(file_path) {
-        Ok(f) => f,
+    let info = match LogFileInfo::from_file(file_path) {
+        Ok(info) => info,
         Err(e) => {
-            println!("❌ ERROR: Cannot open file: {}", e);
-            return;
-        }
-    };
-
-    let reader = BufReader::new(file);
-    let blf = match BlfFile::from_reader(reader) {
-        Ok(b) => b,
-        Err((e, _)) => {
             println!("❌ ERROR: Cannot parse BLF file: {}", e);
             println!("\nThis file may be:");
             println!("  - Corrupted");
@@ -74,50 +61,19 @@ fn main() {
         }
     };
 
-    if !blf.is_valid() {
-        println!("⚠️  WARNING: BLF file validation failed");
-        println!("   Continuing analysis anyway...\n");
-    } else {
-        println!("✅ Valid BLF file format\n");
-    }
-
-    // Analyze objects
-    let mut type_counts: HashMap<u32, usize> = HashMap::new();
-    let mut type_sizes: HashMap<u32, usize> = HashMap::new();
-    let mut total_objects = 0;
-    let mut has_compression = false;
-    let mut has_can_messages = false;
-    let mut has_canfd_100 = false;
-    let mut has_canfd_101 = false;
-
-    println!("🔍 Analyzing objects...\n");
-
-    for obj in blf {
-        *type_counts.entry(obj.object_type).or_insert(0) += 1;
-        *type_sizes.entry(obj.object_type).or_insert(0) += obj.object_size as usize;
-        total_objects += 1;
-
-        // Check for specific types
-        match obj.object_type {
-            10 => has_compression = true,
-            86 => has_can_messages = true,
-            100 => has_canfd_100 = true,
-            101 => has_canfd_101 = true,
-            _ => {}
-        }
-
-        // Show first few objects in detail
-        if total_objects <= 5 {
-            println!("  Object #{}: Type {}, Size {} bytes",
-                total_objects, obj.object_type, obj.object_size);
-        }
+    println!("✅ Valid BLF file format");
+    println!("✍️  Writer: {}", info.writer);
+    if let Some(start) = info.start_time {
+        println!("🕒 Measurement start: {}", start.to_rfc3339());
     }
+    println!("⏱️  Duration: {:.3} s\n", info.duration.unwrap_or_default().as_secs_f64());
 
-    if total_objects > 5 {
-        println!("  ... ({} more objects)\n", total_objects - 5);
-    } else {
-        println!();
-    }
+    let type_counts = &info.object_types;
+    let total_objects: u64 = type_counts.values().sum();
+    let has_compression = info.compressed == Some(true);
+    let has_can_messages = type_counts.contains_key(&86);
+    let has_canfd_100 = type_counts.contains_key(&100);
+    let has_canfd_101 = type_counts.contains_key(&101);
 
     // Sort types by count
     let mut types: Vec<_> = type_counts.iter().collect();
@@ -127,13 +83,12 @@ fn main() {
     println!("                  OBJECT TYPE STATISTICS");
     println!("═══════════════════════════════════════════════════════════════\n");
 
-    println!("{:<15} {:<12} {:<15} {}", "Type", "Count", "Total Size", "Description");
+    println!("{:<15} {:<12} Description", "Type", "Count");
     println!("─────────────────────────────────────────────────────────────");
 
     for (obj_type, count) in types {
-        let total_size = type_sizes.get(obj_type).unwrap_or(&0);
         let type_name = match *obj_type {
-            10 => "LogContainer (⚠️  COMPRESSED)",
+            10 => "LogContainer",
             65 => "AppText",
             73 => "CanErrorFrameExt",
             86 => "CanMessage2 (✅ SUPPORTED)",
@@ -143,12 +98,7 @@ fn main() {
             _ => "Other",
         };
 
-        println!("{:<15} {:<12} {:<15} {}",
-            format!("{}", obj_type),
-            count,
-            format!("{:.1} KB", *total_size as f64 / 1024.0),
-            type_name
-        );
+        println!("{:<15} {:<12} {}", obj_type, count, type_name);
     }
 
     println!("\nTotal Objects: {}", total_objects);
@@ -135,14 +135,17 @@ fn print_decoded_event(event: &DecodedEvent, verbose: bool) {
 
         DecodedEvent::ContainerPdu {
             timestamp,
+            channel,
             container_id,
             container_name,
             container_type,
             contained_pdus,
+            ..
         } => {
             println!(
-                "[{:.6}s] CONTAINER 0x{:03X} {} ({:?}) - {} PDUs",
+                "[{:.6}s] CH{} CONTAINER 0x{:03X} {} ({:?}) - {} PDUs",
                 timestamp_to_secs(timestamp),
+                channel,
                 container_id,
                 container_name,
                 container_type,
@@ -152,10 +155,11 @@ fn print_decoded_event(event: &DecodedEvent, verbose: bool) {
             if verbose {
                 for pdu in contained_pdus {
                     println!(
-                        "    └─ PDU: {} (ID: {}, {} bytes)",
+                        "    └─ PDU: {} (ID: {}, {} bytes){}",
                         pdu.name,
                         pdu.pdu_id,
-                        pdu.data.len()
+                        pdu.data.len(),
+                        if pdu.updated { "" } else { " not updated" }
                     );
                 }
             }
@@ -167,16 +171,14 @@ fn print_decoded_event(event: &DecodedEvent, verbose: bool) {
             can_id,
             data,
             ..
-        } => {
-            if verbose {
-                println!(
-                    "[{:.6}s] CH{} 0x{:03X} RAW [{} bytes]",
-                    timestamp_to_secs(timestamp),
-                    channel,
-                    can_id,
-                    data.len()
-                );
-            }
+        } if verbose => {
+            println!(
+                "[{:.6}s] CH{} 0x{:03X} RAW [{} bytes]",
+                timestamp_to_secs(timestamp),
+                channel,
+                can_id,
+                data.len()
+            );
         }
 
         _ => {} // Skip other event types for now
@@ -1,6 +1,9 @@
 //! Test BLF parser with real files
 
+use ablf::BlfFile;
 use can_log_decoder::formats::blf::BlfParser;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
 
 fn main() {
@@ -21,7 +24,7 @@ fn main() {
         }
 
         match BlfParser::parse(test_path) {
-            Ok(mut iterator) => {
+            Ok(iterator) => {
                 println!("✓ BLF file opened and validated\n");
 
                 let mut can_count = 0;
@@ -30,20 +33,20 @@ fn main() {
                 let mut extended_count = 0;
                 let mut total_objects = 0;
 
-                // Peek at raw objects first
+                // Peek at raw objects first (directly through ablf)
                 println!("Inspecting raw BLF objects...");
-                for obj in &mut iterator.objects {
-                    total_objects += 1;
-                    println!("  Object type={}, size={}", obj.object_type, obj.object_size);
-                    if total_objects >= 10 {
-                        break;
+                let reader = BufReader::new(File::open(test_path).unwrap());
+                if let Ok(blf) = BlfFile::from_reader(reader) {
+                    for obj in blf {
+                        total_objects += 1;
+                        println!("  Object type={}, size={}", obj.object_type, obj.object_size);
+                        if total_objects >= 10 {
+                            break;
+                        }
                     }
                 }
                 println!("(Inspected {} objects)\n", total_objects);
 
-                // Reset iterator by re-parsing
-                iterator = BlfParser::parse(test_path).unwrap();
-
                 for (i, frame_result) in iterator.enumerate() {
                     match frame_result {
                         Ok(frame) => {
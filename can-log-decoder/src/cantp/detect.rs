@@ -0,0 +1,384 @@
+//! CAN-TP (ISO-TP) address pair auto-detection
+//!
+//! Scans a stream of CAN frames for ISO-TP traffic patterns and proposes
+//! source/target pairs with a confidence score. Two kinds of evidence are used:
+//!
+//! - **Handshakes**: First Frame on ID A → Flow Control on ID B → Consecutive Frame
+//!   (SN 1) on ID A, each within the detection window
+//! - **Request/response**: Single/First Frame carrying a diagnostic request SID on
+//!   ID A, answered on ID B with SID + 0x40 (positive) or 0x7F SID (negative)
+//!
+//! The confidence grows with the amount of evidence and is scaled down by the
+//! share of frames on both IDs that do not parse as valid ISO-TP frames.
+
+use crate::config::{CanTpPair, DetectedCanTpPair};
+use crate::types::CanFrame;
+use std::collections::HashMap;
+
+/// Evidence collected for an (unordered) pair of CAN IDs on one channel
+#[derive(Debug, Default)]
+struct PairEvidence {
+    /// Completed FF → FC → CF handshakes
+    handshakes: usize,
+    /// Matched diagnostic request/response exchanges
+    request_responses: usize,
+    /// Orientation votes: positive = lower ID is the requester (source)
+    orientation: i64,
+}
+
+/// A First Frame waiting for Flow Control, or a Flow Control waiting for CF 1
+#[derive(Debug, Clone, Copy)]
+struct PendingHandshake {
+    /// CAN ID that sent the First Frame
+    sender: u32,
+    /// CAN ID that answered with Flow Control (None while waiting)
+    receiver: Option<u32>,
+    /// True if the First Frame payload looked like a request
+    is_request: bool,
+    /// Timestamp of the most recent frame in the handshake
+    timestamp_ns: u64,
+}
+
+/// A diagnostic request waiting for its response
+#[derive(Debug, Clone, Copy)]
+struct PendingRequest {
+    can_id: u32,
+    sid: u8,
+    timestamp_ns: u64,
+}
+
+/// Incremental CAN-TP pair detector
+pub(crate) struct CanTpDetector {
+    /// Maximum gap between related frames
+    window_ns: u64,
+    /// Evidence keyed by (channel, lower ID, higher ID)
+    evidence: HashMap<(u8, u32, u32), PairEvidence>,
+    /// Handshakes in progress, per channel
+    handshakes: HashMap<u8, Vec<PendingHandshake>>,
+    /// Requests waiting for a response, per channel
+    requests: HashMap<u8, Vec<PendingRequest>>,
+    /// (total frames, valid ISO-TP frames) per (channel, CAN ID)
+    frame_counts: HashMap<(u8, u32), (usize, usize)>,
+}
+
+impl CanTpDetector {
+    /// Create a detector that relates frames up to `window_ms` apart
+    pub(crate) fn new(window_ms: u64) -> Self {
+        Self {
+            window_ns: window_ms.saturating_mul(1_000_000),
+            evidence: HashMap::new(),
+            handshakes: HashMap::new(),
+            requests: HashMap::new(),
+            frame_counts: HashMap::new(),
+        }
+    }
+
+    /// Feed one CAN frame into the detector
+    pub(crate) fn process_frame(&mut self, frame: &CanFrame) {
+        if frame.is_error_frame || frame.is_remote_frame {
+            return;
+        }
+
+        let data = &frame.data;
+        let valid = is_valid_isotp_frame(data);
+        let counts = self.frame_counts.entry((frame.channel, frame.can_id)).or_default();
+        counts.0 += 1;
+        if !valid {
+            return;
+        }
+        counts.1 += 1;
+
+        self.expire(frame.channel, frame.timestamp_ns);
+
+        match data[0] >> 4 {
+            0x0 => {
+                if let Some(sid) = single_frame_sid(data) {
+                    self.handle_sid(frame, sid);
+                }
+            }
+            0x1 => {
+                let sid = first_frame_sid(data);
+                if let Some(sid) = sid {
+                    self.handle_sid(frame, sid);
+                }
+                self.handshakes.entry(frame.channel).or_default().push(PendingHandshake {
+                    sender: frame.can_id,
+                    receiver: None,
+                    is_request: sid.is_some_and(is_request_sid),
+                    timestamp_ns: frame.timestamp_ns,
+                });
+            }
+            0x2 => self.handle_consecutive_frame(frame),
+            0x3 => self.handle_flow_control(frame),
+            _ => {}
+        }
+    }
+
+    /// Produce the detected pairs, most confident first
+    pub(crate) fn finish(self) -> Vec<DetectedCanTpPair> {
+        let mut detected: Vec<DetectedCanTpPair> = self
+            .evidence
+            .iter()
+            .filter(|(_, ev)| ev.handshakes + ev.request_responses > 0)
+            .map(|(&(channel, low, high), ev)| {
+                let (source, target) = if ev.orientation >= 0 { (low, high) } else { (high, low) };
+
+                // Each handshake counts double: it is a much stronger signature than a SF exchange
+                let weight = (2 * ev.handshakes + ev.request_responses) as i32;
+                let evidence_score = 1.0 - 0.5f64.powi(weight.min(64));
+
+                let (total, valid) = [low, high]
+                    .iter()
+                    .filter_map(|id| self.frame_counts.get(&(channel, *id)))
+                    .fold((0, 0), |acc, c| (acc.0 + c.0, acc.1 + c.1));
+                let validity = if total == 0 { 0.0 } else { valid as f64 / total as f64 };
+
+                DetectedCanTpPair {
+                    pair: CanTpPair::new(source, target),
+                    channel,
+                    confidence: evidence_score * validity,
+                    handshakes: ev.handshakes,
+                    request_responses: ev.request_responses,
+                }
+            })
+            .collect();
+
+        detected.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then(a.channel.cmp(&b.channel))
+                .then(a.pair.source.cmp(&b.pair.source))
+        });
+        detected
+    }
+
+    /// Match a request/response SID against pending requests on the channel
+    fn handle_sid(&mut self, frame: &CanFrame, sid: u8) {
+        let pending = self.requests.entry(frame.channel).or_default();
+
+        if is_response_sid(sid) {
+            let request_sid = if sid == 0x7F {
+                // Negative response: byte after 0x7F is the rejected request SID
+                negative_response_request_sid(&frame.data)
+            } else {
+                Some(sid - 0x40)
+            };
+
+            let matched = request_sid.and_then(|request_sid| {
+                pending
+                    .iter()
+                    .rposition(|r| r.can_id != frame.can_id && r.sid == request_sid)
+            });
+
+            if let Some(idx) = matched {
+                let request = pending.remove(idx);
+                let ev = self.evidence_for(frame.channel, request.can_id, frame.can_id);
+                ev.request_responses += 1;
+                ev.orientation += orientation_vote(request.can_id, frame.can_id);
+            }
+        } else if is_request_sid(sid) {
+            pending.push(PendingRequest {
+                can_id: frame.can_id,
+                sid,
+                timestamp_ns: frame.timestamp_ns,
+            });
+        }
+    }
+
+    fn handle_flow_control(&mut self, frame: &CanFrame) {
+        let Some(pending) = self.handshakes.get_mut(&frame.channel) else {
+            return;
+        };
+
+        // Most recent First Frame from another ID that is still waiting for FC
+        if let Some(handshake) = pending
+            .iter_mut()
+            .rev()
+            .find(|h| h.receiver.is_none() && h.sender != frame.can_id)
+        {
+            handshake.receiver = Some(frame.can_id);
+            handshake.timestamp_ns = frame.timestamp_ns;
+        }
+    }
+
+    fn handle_consecutive_frame(&mut self, frame: &CanFrame) {
+        if frame.data[0] & 0x0F != 1 {
+            return;
+        }
+        let Some(pending) = self.handshakes.get_mut(&frame.channel) else {
+            return;
+        };
+
+        let Some(idx) = pending
+            .iter()
+            .rposition(|h| h.sender == frame.can_id && h.receiver.is_some())
+        else {
+            return;
+        };
+
+        let handshake = pending.remove(idx);
+        let receiver = handshake.receiver.unwrap_or_default();
+        let vote = if handshake.is_request {
+            orientation_vote(handshake.sender, receiver)
+        } else {
+            orientation_vote(receiver, handshake.sender)
+        };
+
+        let ev = self.evidence_for(frame.channel, handshake.sender, receiver);
+        ev.handshakes += 1;
+        ev.orientation += vote;
+    }
+
+    /// Drop pending handshakes and requests older than the detection window
+    fn expire(&mut self, channel: u8, now_ns: u64) {
+        let window_ns = self.window_ns;
+        if let Some(pending) = self.handshakes.get_mut(&channel) {
+            pending.retain(|h| now_ns.saturating_sub(h.timestamp_ns) <= window_ns);
+        }
+        if let Some(pending) = self.requests.get_mut(&channel) {
+            pending.retain(|r| now_ns.saturating_sub(r.timestamp_ns) <= window_ns);
+        }
+    }
+
+    fn evidence_for(&mut self, channel: u8, a: u32, b: u32) -> &mut PairEvidence {
+        self.evidence.entry((channel, a.min(b), a.max(b))).or_default()
+    }
+}
+
+/// +1 if the requester has the lower ID, -1 otherwise
+fn orientation_vote(requester: u32, responder: u32) -> i64 {
+    if requester < responder {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Check that a frame has a plausible ISO-TP PCI byte
+fn is_valid_isotp_frame(data: &[u8]) -> bool {
+    let Some(&pci) = data.first() else {
+        return false;
+    };
+
+    match pci >> 4 {
+        0x0 => single_frame_sid(data).is_some(),
+        0x1 => {
+            if data.len() < 8 {
+                return false;
+            }
+            let ff_dl = (((pci & 0x0F) as usize) << 8) | data[1] as usize;
+            // Escape sequence (FF_DL = 0) requires a 32-bit length
+            ff_dl >= 8 || (ff_dl == 0 && data.len() >= 6)
+        }
+        0x2 => data.len() >= 2,
+        0x3 => data.len() >= 3 && (pci & 0x0F) <= 2,
+        _ => false,
+    }
+}
+
+/// First payload byte of a Single Frame (classic or CAN-FD escape)
+fn single_frame_sid(data: &[u8]) -> Option<u8> {
+    let (length, start) = match data[0] & 0x0F {
+        0 if data.len() > 8 => (data[1] as usize, 2),
+        0 => return None,
+        len => (len as usize, 1),
+    };
+    if length == 0 || start + length > data.len() {
+        return None;
+    }
+    Some(data[start])
+}
+
+/// First payload byte of a First Frame (classic or escaped length)
+fn first_frame_sid(data: &[u8]) -> Option<u8> {
+    let ff_dl = (((data[0] & 0x0F) as usize) << 8) | *data.get(1)? as usize;
+    let start = if ff_dl == 0 { 6 } else { 2 };
+    data.get(start).copied()
+}
+
+/// Request SID carried by a negative response (0x7F SID NRC)
+fn negative_response_request_sid(data: &[u8]) -> Option<u8> {
+    let pci = data[0];
+    let start = match (pci >> 4, pci & 0x0F) {
+        (0x0, 0) => 2,
+        (0x0, _) => 1,
+        (0x1, _) => 2,
+        _ => return None,
+    };
+    data.get(start + 1).copied()
+}
+
+/// Diagnostic request service IDs (OBD-II modes and UDS services)
+fn is_request_sid(sid: u8) -> bool {
+    matches!(sid, 0x01..=0x0A | 0x10..=0x3E | 0x83..=0x88 | 0xBA..=0xBE)
+}
+
+/// Diagnostic response service IDs (positive responses and 0x7F negative response)
+fn is_response_sid(sid: u8) -> bool {
+    sid == 0x7F || (sid >= 0x40 && is_request_sid(sid - 0x40))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp_ms: u64, can_id: u32, data: &[u8]) -> CanFrame {
+        CanFrame {
+            timestamp_ns: timestamp_ms * 1_000_000,
+            measurement_start_ns: 0,
+            channel: 1,
+            can_id,
+            data: data.to_vec(),
+            is_extended: false,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    #[test]
+    fn test_detect_single_frame_request_response() {
+        let mut detector = CanTpDetector::new(1000);
+        for i in 0..4 {
+            detector.process_frame(&frame(i * 100, 0x7E0, &[0x02, 0x10, 0x03, 0, 0, 0, 0, 0]));
+            detector.process_frame(&frame(i * 100 + 5, 0x7E8, &[0x02, 0x50, 0x03, 0, 0, 0, 0, 0]));
+            // Unrelated periodic traffic
+            detector.process_frame(&frame(i * 100 + 7, 0x123, &[0xFF, 0x12, 0x34]));
+        }
+
+        let detected = detector.finish();
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].pair, CanTpPair::new(0x7E0, 0x7E8));
+        assert_eq!(detected[0].channel, 1);
+        assert_eq!(detected[0].request_responses, 4);
+        assert!(detected[0].confidence > 0.9);
+    }
+
+    #[test]
+    fn test_detect_flow_control_handshake() {
+        let mut detector = CanTpDetector::new(1000);
+        // Request on 0x18DA10F1, long response on 0x18DAF110
+        detector.process_frame(&frame(0, 0x18DA10F1, &[0x03, 0x22, 0xF1, 0x90, 0, 0, 0, 0]));
+        detector.process_frame(&frame(5, 0x18DAF110, &[0x10, 0x14, 0x62, 0xF1, 0x90, 0x57, 0x30, 0x4C]));
+        detector.process_frame(&frame(6, 0x18DA10F1, &[0x30, 0x00, 0x00, 0, 0, 0, 0, 0]));
+        detector.process_frame(&frame(7, 0x18DAF110, &[0x21, 1, 2, 3, 4, 5, 6, 7]));
+
+        let detected = detector.finish();
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].pair, CanTpPair::new(0x18DA10F1, 0x18DAF110));
+        assert_eq!(detected[0].handshakes, 1);
+        assert_eq!(detected[0].request_responses, 1);
+    }
+
+    #[test]
+    fn test_detect_ignores_stale_and_invalid_traffic() {
+        let mut detector = CanTpDetector::new(100);
+        // Response arrives after the window closed
+        detector.process_frame(&frame(0, 0x7E0, &[0x02, 0x10, 0x03, 0, 0, 0, 0, 0]));
+        detector.process_frame(&frame(500, 0x7E8, &[0x02, 0x50, 0x03, 0, 0, 0, 0, 0]));
+        // Non ISO-TP frames
+        detector.process_frame(&frame(600, 0x100, &[0x55, 0x50]));
+
+        assert!(detector.finish().is_empty());
+    }
+}
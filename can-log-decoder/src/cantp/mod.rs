@@ -1,10 +1,787 @@
 //! CAN-TP (ISO-TP) message reconstruction
 //!
-//! Reconstructs multi-frame CAN-TP messages from individual frames.
-//! Implementation scheduled for Phase 5.
-
-// TODO: Implement CAN-TP reconstruction in Phase 5
-// - ISO-TP frame detection (SF, FF, CF)
-// - Flow control handling (CTS, Wait, Overflow)
-// - Auto-detection mode
-// - Explicit pair reconstruction with timeout handling
+//! Reconstructs multi-frame CAN-TP (ISO 15765-2) messages from individual frames
+//! for the explicitly configured address pairs.
+//!
+//! ## Frame Types (Protocol Control Information, high nibble of byte 0)
+//!
+//! - **Single Frame (0x0)**: complete payload in one frame
+//!   - Classic CAN: `0L` with SF_DL = L (1..7)
+//!   - CAN-FD escape: `00 LL` with SF_DL in byte 1 (frames longer than 8 bytes)
+//! - **First Frame (0x1)**: start of a segmented transfer
+//!   - `1L LL` with 12-bit FF_DL (8..4095)
+//!   - Escape: `10 00 LL LL LL LL` with 32-bit FF_DL (> 4095)
+//!   - Other lengths are rejected as invalid frames
+//! - **Consecutive Frame (0x2)**: `2N` with sequence number N (1..15, wraps to 0)
+//! - **Flow Control (0x3)**: `3S BS ST` sent by the receiver
+//!   - S = 0 (CTS), 1 (WAIT), 2 (OVFLW)
+//!
+//! ## Addressing Modes
+//!
+//! - **Normal**: PCI in byte 0, N_SA/N_TA are the CAN IDs of the pair
+//! - **Normal fixed**: 29-bit IDs 0x18DA_TA_SA (physical) / 0x18DB_TA_SA (functional)
+//! - **Extended**: byte 0 carries N_TA, the PCI starts at byte 1
+//! - **Mixed**: byte 0 carries N_AE; 29-bit IDs are 0x18CE_TA_SA / 0x18CD_TA_SA
+//!
+//! Each direction of a pair is reassembled independently, keyed by channel, the
+//! CAN ID the data is sent on and the address byte (extended/mixed addressing).
+//! Flow Control frames arrive on the peer ID.
+//!
+//! Pairs using normal addressing can also be found automatically with the
+//! [`detect`] pre-scan.
+
+pub(crate) mod detect;
+
+use crate::config::{CanTpAddressing, CanTpPair, DecoderConfig};
+use crate::types::{CanFrame, CanTpErrorKind, DecodedEvent, Timestamp};
+use std::collections::HashMap;
+
+/// PCI frame type: Single Frame
+const PCI_SINGLE_FRAME: u8 = 0x0;
+/// PCI frame type: First Frame
+const PCI_FIRST_FRAME: u8 = 0x1;
+/// PCI frame type: Consecutive Frame
+const PCI_CONSECUTIVE_FRAME: u8 = 0x2;
+/// PCI frame type: Flow Control
+const PCI_FLOW_CONTROL: u8 = 0x3;
+
+/// Flow status: Continue To Send
+const FS_CTS: u8 = 0x0;
+/// Flow status: Wait
+const FS_WAIT: u8 = 0x1;
+/// Flow status: Overflow
+const FS_OVFLW: u8 = 0x2;
+
+/// PDU format of functional normal fixed addressing (0x18DB_TA_SA)
+const PF_NORMAL_FIXED_FUNCTIONAL: u8 = 0xDB;
+/// PDU format of physical 29-bit mixed addressing (0x18CE_TA_SA)
+const PF_MIXED_PHYSICAL: u8 = 0xCE;
+/// PDU format of functional 29-bit mixed addressing (0x18CD_TA_SA)
+const PF_MIXED_FUNCTIONAL: u8 = 0xCD;
+
+/// Largest FF_DL without the escape sequence (12 bits)
+const MAX_FF_DL_12BIT: usize = 0xFFF;
+
+/// Transfer key: (channel, sender CAN ID, address byte for extended/mixed addressing)
+type SessionKey = (u8, u32, Option<u8>);
+
+/// How a frame maps onto a configured pair
+#[derive(Debug, Clone, Copy)]
+struct Route {
+    /// Transfer the frame's data belongs to
+    key: SessionKey,
+    /// Transfer a Flow Control frame on this route applies to (the peer's)
+    fc_key: SessionKey,
+    /// N_SA of the frame's sender
+    source_addr: u32,
+    /// N_TA of the frame's receiver
+    target_addr: u32,
+    /// N_AE (mixed addressing)
+    address_extension: Option<u8>,
+    /// Index of the PCI byte (1 when byte 0 is an address byte)
+    pci_offset: usize,
+}
+
+/// An in-progress segmented transfer
+#[derive(Debug)]
+struct RxSession {
+    /// N_SA of the sender
+    source_addr: u32,
+    /// N_TA of the receiver
+    target_addr: u32,
+    /// N_AE (mixed addressing)
+    address_extension: Option<u8>,
+    /// Timestamp of the First Frame
+    start_time: Timestamp,
+    /// Timestamp of the most recent frame belonging to this transfer
+    last_time: Timestamp,
+    /// Same as `last_time`, in nanoseconds (for timeout checks)
+    last_frame_ns: u64,
+    /// Payload length announced in the First Frame
+    expected_len: usize,
+    /// Payload bytes received so far
+    payload: Vec<u8>,
+    /// Next expected consecutive frame sequence number
+    next_sn: u8,
+    /// Flow Control WAIT frames received since the last CTS
+    wait_frames: usize,
+}
+
+/// CAN-TP reassembler for explicitly configured address pairs
+pub struct CanTpReassembler {
+    /// Configured pairs
+    pairs: Vec<CanTpPair>,
+    /// CAN ID → indices of the pairs using it (either direction)
+    by_id: HashMap<u32, Vec<usize>>,
+    /// (PDU format, tester address) of functional 29-bit IDs → pair index
+    functional: HashMap<(u8, u8), usize>,
+    /// In-progress transfers
+    sessions: HashMap<SessionKey, RxSession>,
+    /// Maximum gap between frames of one transfer
+    timeout_ns: u64,
+    /// Maximum number of consecutive Flow Control WAIT frames
+    max_wait_frames: usize,
+}
+
+impl CanTpReassembler {
+    /// Create a reassembler from the decoder configuration
+    ///
+    /// Returns `None` if no CAN-TP pairs are configured.
+    pub fn from_config(config: &DecoderConfig) -> Option<Self> {
+        if config.cantp_pairs.is_empty() {
+            return None;
+        }
+
+        let mut by_id: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut functional = HashMap::new();
+        for (idx, pair) in config.cantp_pairs.iter().enumerate() {
+            by_id.entry(pair.source).or_default().push(idx);
+            by_id.entry(pair.target).or_default().push(idx);
+
+            // Functional requests come from the tester to any target address
+            let functional_pf = match (pair.addressing, pdu_format(pair.source)) {
+                (CanTpAddressing::NormalFixed, Some(_)) => Some(PF_NORMAL_FIXED_FUNCTIONAL),
+                (CanTpAddressing::Mixed { .. }, Some(PF_MIXED_PHYSICAL)) => Some(PF_MIXED_FUNCTIONAL),
+                _ => None,
+            };
+            if let Some(pf) = functional_pf {
+                functional.entry((pf, pair.source as u8)).or_insert(idx);
+            }
+        }
+
+        Some(Self {
+            pairs: config.cantp_pairs.clone(),
+            by_id,
+            functional,
+            sessions: HashMap::new(),
+            timeout_ns: config.cantp_timeout_ms.saturating_mul(1_000_000),
+            max_wait_frames: config.cantp_max_wait_frames,
+        })
+    }
+
+    /// Process a CAN frame and return any completed messages or detected errors
+    pub fn process_frame(&mut self, frame: &CanFrame) -> Vec<DecodedEvent> {
+        let mut events = self.check_timeouts(frame.timestamp_ns);
+
+        if frame.data.is_empty() || frame.is_error_frame || frame.is_remote_frame {
+            return events;
+        }
+        let Some(route) = self.route(frame) else {
+            return events;
+        };
+        let Some(data) = frame.data.get(route.pci_offset..).filter(|d| !d.is_empty()) else {
+            return events;
+        };
+
+        let event = match data[0] >> 4 {
+            PCI_SINGLE_FRAME => self.handle_single_frame(frame, data, &route, &mut events),
+            PCI_FIRST_FRAME => self.handle_first_frame(frame, data, &route, &mut events),
+            PCI_CONSECUTIVE_FRAME => self.handle_consecutive_frame(frame, data, &route),
+            PCI_FLOW_CONTROL => self.handle_flow_control(frame, data, &route),
+            pci => Some(Self::error_event(
+                frame,
+                &route,
+                CanTpErrorKind::InvalidFrame(format!("Unknown PCI type 0x{:X}", pci)),
+            )),
+        };
+
+        events.extend(event);
+        events
+    }
+
+    /// Flush incomplete transfers at the end of the log
+    pub fn finish(&mut self) -> Vec<DecodedEvent> {
+        let keys = self.sorted_session_keys(|_| true);
+        keys.into_iter()
+            .filter_map(|key| self.sessions.remove(&key).map(|session| (key, session)))
+            .map(|((channel, _, _), session)| Self::timeout_event(channel, session))
+            .collect()
+    }
+
+    /// Find the configured pair a frame belongs to
+    fn route(&self, frame: &CanFrame) -> Option<Route> {
+        if let Some(indices) = self.by_id.get(&frame.can_id) {
+            // Extended addressing may put several pairs on one CAN ID
+            if let Some(route) = indices.iter().find_map(|&idx| Self::route_for_pair(&self.pairs[idx], frame)) {
+                return Some(route);
+            }
+        }
+
+        let pf = pdu_format(frame.can_id)?;
+        let &idx = self.functional.get(&(pf, frame.can_id as u8))?;
+        Self::route_for_pair(&self.pairs[idx], frame)
+    }
+
+    /// Resolve addresses and PCI offset of a frame for one pair
+    ///
+    /// Returns `None` if the frame's address byte does not match the pair.
+    fn route_for_pair(pair: &CanTpPair, frame: &CanFrame) -> Option<Route> {
+        let channel = frame.channel;
+        let can_id = frame.can_id;
+        let from_source = can_id != pair.target;
+        let peer = if from_source { pair.target } else { pair.source };
+
+        let route = match pair.addressing {
+            CanTpAddressing::Normal => Route {
+                key: (channel, can_id, None),
+                fc_key: (channel, peer, None),
+                source_addr: can_id,
+                target_addr: peer,
+                address_extension: None,
+                pci_offset: 0,
+            },
+            CanTpAddressing::NormalFixed => Route {
+                key: (channel, can_id, None),
+                fc_key: (channel, peer, None),
+                source_addr: can_id & 0xFF,
+                target_addr: (can_id >> 8) & 0xFF,
+                address_extension: None,
+                pci_offset: 0,
+            },
+            CanTpAddressing::Extended {
+                source_address,
+                target_address,
+            } => {
+                let (sa, expected_ta) = if from_source {
+                    (source_address, target_address)
+                } else {
+                    (target_address, source_address)
+                };
+                let ta = *frame.data.first()?;
+                if ta != expected_ta {
+                    return None;
+                }
+                Route {
+                    key: (channel, can_id, Some(ta)),
+                    // The peer addresses its frames to this sender
+                    fc_key: (channel, peer, Some(sa)),
+                    source_addr: sa as u32,
+                    target_addr: ta as u32,
+                    address_extension: None,
+                    pci_offset: 1,
+                }
+            }
+            CanTpAddressing::Mixed { address_extension } => {
+                if *frame.data.first()? != address_extension {
+                    return None;
+                }
+                let (source_addr, target_addr) = match pdu_format(can_id) {
+                    Some(PF_MIXED_PHYSICAL | PF_MIXED_FUNCTIONAL) => (can_id & 0xFF, (can_id >> 8) & 0xFF),
+                    _ => (can_id, peer),
+                };
+                Route {
+                    key: (channel, can_id, Some(address_extension)),
+                    fc_key: (channel, peer, Some(address_extension)),
+                    source_addr,
+                    target_addr,
+                    address_extension: Some(address_extension),
+                    pci_offset: 1,
+                }
+            }
+        };
+        Some(route)
+    }
+
+    fn handle_single_frame(
+        &mut self,
+        frame: &CanFrame,
+        data: &[u8],
+        route: &Route,
+        events: &mut Vec<DecodedEvent>,
+    ) -> Option<DecodedEvent> {
+        let (length, start) = match data[0] & 0x0F {
+            // CAN-FD escape sequence: SF_DL in the byte after the PCI
+            0 if frame.data.len() > 8 && data.len() > 1 => (data[1] as usize, 2),
+            0 => {
+                return Some(Self::error_event(
+                    frame,
+                    route,
+                    CanTpErrorKind::InvalidFrame("Single frame with SF_DL = 0".to_string()),
+                ));
+            }
+            len => (len as usize, 1),
+        };
+
+        if length == 0 || start + length > data.len() {
+            return Some(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::InvalidFrame(format!(
+                    "Single frame SF_DL {} exceeds frame length {}",
+                    length,
+                    data.len()
+                )),
+            ));
+        }
+
+        self.interrupt_session(frame, route, events);
+
+        Some(DecodedEvent::CanTpMessage {
+            timestamp: frame.timestamp(),
+            channel: frame.channel,
+            source_addr: route.source_addr,
+            target_addr: route.target_addr,
+            address_extension: route.address_extension,
+            payload: data[start..start + length].to_vec(),
+            payload_length: length,
+        })
+    }
+
+    fn handle_first_frame(
+        &mut self,
+        frame: &CanFrame,
+        data: &[u8],
+        route: &Route,
+        events: &mut Vec<DecodedEvent>,
+    ) -> Option<DecodedEvent> {
+        if data.len() < 2 {
+            return Some(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::InvalidFrame("First frame shorter than 2 bytes".to_string()),
+            ));
+        }
+
+        let ff_dl = (((data[0] & 0x0F) as usize) << 8) | data[1] as usize;
+        let (expected_len, start) = if ff_dl == 0 {
+            // Escape sequence: 32-bit FF_DL in the 4 bytes after FF_DL
+            if data.len() < 6 {
+                return Some(Self::error_event(
+                    frame,
+                    route,
+                    CanTpErrorKind::InvalidFrame("Escaped first frame shorter than 6 bytes".to_string()),
+                ));
+            }
+            let len = u32::from_be_bytes([data[2], data[3], data[4], data[5]]) as usize;
+            if len <= MAX_FF_DL_12BIT {
+                return Some(Self::error_event(
+                    frame,
+                    route,
+                    CanTpErrorKind::InvalidFrame(format!("Escaped first frame with FF_DL = {}", len)),
+                ));
+            }
+            (len, 6)
+        } else if ff_dl < 8 {
+            return Some(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::InvalidFrame(format!("First frame with FF_DL = {}", ff_dl)),
+            ));
+        } else {
+            (ff_dl, 2)
+        };
+
+        self.interrupt_session(frame, route, events);
+
+        // Escaped FF_DL can announce up to 4 GiB: grow with the CFs instead
+        let mut payload = Vec::with_capacity(expected_len.min(MAX_FF_DL_12BIT));
+        let take = (data.len() - start).min(expected_len);
+        payload.extend_from_slice(&data[start..start + take]);
+
+        self.sessions.insert(
+            route.key,
+            RxSession {
+                source_addr: route.source_addr,
+                target_addr: route.target_addr,
+                address_extension: route.address_extension,
+                start_time: frame.timestamp(),
+                last_time: frame.timestamp(),
+                last_frame_ns: frame.timestamp_ns,
+                expected_len,
+                payload,
+                next_sn: 1,
+                wait_frames: 0,
+            },
+        );
+
+        None
+    }
+
+    fn handle_consecutive_frame(&mut self, frame: &CanFrame, data: &[u8], route: &Route) -> Option<DecodedEvent> {
+        let sn = data[0] & 0x0F;
+
+        let Some(session) = self.sessions.get_mut(&route.key) else {
+            return Some(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::UnexpectedConsecutiveFrame { sequence_number: sn },
+            ));
+        };
+
+        if sn != session.next_sn {
+            let expected = session.next_sn;
+            self.sessions.remove(&route.key);
+            return Some(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::SequenceError { expected, received: sn },
+            ));
+        }
+
+        let remaining = session.expected_len - session.payload.len();
+        let take = (data.len() - 1).min(remaining);
+        session.payload.extend_from_slice(&data[1..1 + take]);
+        session.next_sn = (sn + 1) & 0x0F;
+        session.last_time = frame.timestamp();
+        session.last_frame_ns = frame.timestamp_ns;
+
+        if session.payload.len() < session.expected_len {
+            return None;
+        }
+
+        let session = self.sessions.remove(&route.key)?;
+        Some(DecodedEvent::CanTpMessage {
+            timestamp: session.start_time,
+            channel: frame.channel,
+            source_addr: session.source_addr,
+            target_addr: session.target_addr,
+            address_extension: session.address_extension,
+            payload_length: session.payload.len(),
+            payload: session.payload,
+        })
+    }
+
+    fn handle_flow_control(&mut self, frame: &CanFrame, data: &[u8], route: &Route) -> Option<DecodedEvent> {
+        // Flow control is sent by the receiver, so it applies to the peer's transfer
+        let key = route.fc_key;
+        let Some(session) = self.sessions.get_mut(&key) else {
+            log::trace!("Flow control on 0x{:X} without active transfer", frame.can_id);
+            return None;
+        };
+
+        session.last_time = frame.timestamp();
+        session.last_frame_ns = frame.timestamp_ns;
+
+        // Errors are reported against the transfer being controlled
+        let transfer = Route {
+            source_addr: session.source_addr,
+            target_addr: session.target_addr,
+            ..*route
+        };
+
+        match data[0] & 0x0F {
+            FS_CTS => {
+                session.wait_frames = 0;
+                None
+            }
+            FS_WAIT => {
+                session.wait_frames += 1;
+                if session.wait_frames <= self.max_wait_frames {
+                    return None;
+                }
+                let wait_frames = session.wait_frames;
+                self.sessions.remove(&key);
+                Some(Self::error_event(
+                    frame,
+                    &transfer,
+                    CanTpErrorKind::WaitLimitExceeded { wait_frames },
+                ))
+            }
+            FS_OVFLW => {
+                self.sessions.remove(&key);
+                Some(Self::error_event(frame, &transfer, CanTpErrorKind::FlowControlOverflow))
+            }
+            fs => Some(Self::error_event(
+                frame,
+                &transfer,
+                CanTpErrorKind::InvalidFrame(format!("Unknown flow status 0x{:X}", fs)),
+            )),
+        }
+    }
+
+    /// Abort an in-progress transfer that is superseded by a new SF/FF
+    fn interrupt_session(&mut self, frame: &CanFrame, route: &Route, events: &mut Vec<DecodedEvent>) {
+        if let Some(session) = self.sessions.remove(&route.key) {
+            events.push(Self::error_event(
+                frame,
+                route,
+                CanTpErrorKind::InterruptedTransfer {
+                    received: session.payload.len(),
+                    expected: session.expected_len,
+                },
+            ));
+        }
+    }
+
+    /// Drop transfers whose last frame is older than the configured timeout
+    fn check_timeouts(&mut self, now_ns: u64) -> Vec<DecodedEvent> {
+        if self.sessions.is_empty() {
+            return Vec::new();
+        }
+
+        let timeout_ns = self.timeout_ns;
+        let expired = self.sorted_session_keys(|s| now_ns.saturating_sub(s.last_frame_ns) > timeout_ns);
+        expired
+            .into_iter()
+            .filter_map(|key| self.sessions.remove(&key).map(|session| (key, session)))
+            .map(|((channel, _, _), session)| Self::timeout_event(channel, session))
+            .collect()
+    }
+
+    /// Session keys matching a predicate, in deterministic order
+    fn sorted_session_keys(&self, predicate: impl Fn(&RxSession) -> bool) -> Vec<SessionKey> {
+        let mut keys: Vec<SessionKey> = self
+            .sessions
+            .iter()
+            .filter(|(_, s)| predicate(s))
+            .map(|(k, _)| *k)
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    fn timeout_event(channel: u8, session: RxSession) -> DecodedEvent {
+        DecodedEvent::CanTpError {
+            timestamp: session.last_time,
+            channel,
+            source_addr: session.source_addr,
+            target_addr: session.target_addr,
+            error: CanTpErrorKind::Timeout {
+                received: session.payload.len(),
+                expected: session.expected_len,
+            },
+        }
+    }
+
+    fn error_event(frame: &CanFrame, route: &Route, error: CanTpErrorKind) -> DecodedEvent {
+        log::debug!(
+            "CAN-TP error on channel {} (0x{:X} → 0x{:X}): {}",
+            frame.channel,
+            route.source_addr,
+            route.target_addr,
+            error
+        );
+        DecodedEvent::CanTpError {
+            timestamp: frame.timestamp(),
+            channel: frame.channel,
+            source_addr: route.source_addr,
+            target_addr: route.target_addr,
+            error,
+        }
+    }
+}
+
+/// PDU format byte (bits 16..24) of a 29-bit CAN ID
+fn pdu_format(can_id: u32) -> Option<u8> {
+    (can_id > 0x7FF).then_some((can_id >> 16) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp_ms: u64, can_id: u32, data: &[u8]) -> CanFrame {
+        CanFrame {
+            timestamp_ns: timestamp_ms * 1_000_000,
+            measurement_start_ns: 0,
+            channel: 0,
+            can_id,
+            data: data.to_vec(),
+            is_extended: false,
+            is_fd: data.len() > 8,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    fn reassembler() -> CanTpReassembler {
+        CanTpReassembler::from_config(&DecoderConfig::new().add_cantp_pair(0x7E0, 0x7E8)).unwrap()
+    }
+
+    fn payload_of(event: &DecodedEvent) -> &[u8] {
+        match event {
+            DecodedEvent::CanTpMessage { payload, .. } => payload,
+            other => panic!("Expected CanTpMessage, got {:?}", other),
+        }
+    }
+
+    fn error_of(event: &DecodedEvent) -> &CanTpErrorKind {
+        match event {
+            DecodedEvent::CanTpError { error, .. } => error,
+            other => panic!("Expected CanTpError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_pairs_no_reassembler() {
+        assert!(CanTpReassembler::from_config(&DecoderConfig::new()).is_none());
+    }
+
+    #[test]
+    fn test_single_frame() {
+        let mut tp = reassembler();
+        let events = tp.process_frame(&frame(0, 0x7E0, &[0x02, 0x10, 0x03, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA]));
+        assert_eq!(events.len(), 1);
+        assert_eq!(payload_of(&events[0]), &[0x10, 0x03]);
+        assert!(matches!(
+            events[0],
+            DecodedEvent::CanTpMessage { source_addr: 0x7E0, target_addr: 0x7E8, payload_length: 2, .. }
+        ));
+
+        // Unrelated IDs are ignored
+        assert!(tp.process_frame(&frame(1, 0x123, &[0x02, 0x10, 0x03])).is_empty());
+    }
+
+    #[test]
+    fn test_multi_frame_with_flow_control() {
+        let mut tp = reassembler();
+        assert!(tp.process_frame(&frame(0, 0x7E8, &[0x10, 0x0A, 0x62, 0xF1, 0x90, 0x57, 0x30, 0x4C])).is_empty());
+        assert!(tp.process_frame(&frame(1, 0x7E0, &[0x30, 0x00, 0x00, 0, 0, 0, 0, 0])).is_empty());
+        let events = tp.process_frame(&frame(2, 0x7E8, &[0x21, 0x30, 0x30, 0x30, 0x30, 0x55, 0x55, 0x55]));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(payload_of(&events[0]), &[0x62, 0xF1, 0x90, 0x57, 0x30, 0x4C, 0x30, 0x30, 0x30, 0x30]);
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0x7E8, target_addr: 0x7E0, .. }));
+    }
+
+    #[test]
+    fn test_sequence_error() {
+        let mut tp = reassembler();
+        tp.process_frame(&frame(0, 0x7E8, &[0x10, 0x14, 1, 2, 3, 4, 5, 6]));
+        let events = tp.process_frame(&frame(1, 0x7E8, &[0x22, 7, 8, 9, 10, 11, 12, 13]));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(error_of(&events[0]), &CanTpErrorKind::SequenceError { expected: 1, received: 2 });
+
+        // Transfer was aborted, further CFs are orphaned
+        let events = tp.process_frame(&frame(2, 0x7E8, &[0x21, 7, 8, 9, 10, 11, 12, 13]));
+        assert!(matches!(error_of(&events[0]), CanTpErrorKind::UnexpectedConsecutiveFrame { sequence_number: 1 }));
+    }
+
+    #[test]
+    fn test_timeout_and_finish() {
+        let mut tp = reassembler();
+        tp.process_frame(&frame(0, 0x7E8, &[0x10, 0x14, 1, 2, 3, 4, 5, 6]));
+        let events = tp.process_frame(&frame(1500, 0x123, &[0x00]));
+        assert_eq!(error_of(&events[0]), &CanTpErrorKind::Timeout { received: 6, expected: 20 });
+
+        tp.process_frame(&frame(2000, 0x7E0, &[0x10, 0x14, 1, 2, 3, 4, 5, 6]));
+        let events = tp.finish();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(error_of(&events[0]), CanTpErrorKind::Timeout { .. }));
+    }
+
+    #[test]
+    fn test_flow_control_overflow_and_wait_limit() {
+        let config = DecoderConfig {
+            cantp_max_wait_frames: 1,
+            ..DecoderConfig::new().add_cantp_pair(0x7E0, 0x7E8)
+        };
+        let mut tp = CanTpReassembler::from_config(&config).unwrap();
+
+        tp.process_frame(&frame(0, 0x7E8, &[0x10, 0x14, 1, 2, 3, 4, 5, 6]));
+        let events = tp.process_frame(&frame(1, 0x7E0, &[0x32, 0, 0]));
+        assert_eq!(error_of(&events[0]), &CanTpErrorKind::FlowControlOverflow);
+
+        tp.process_frame(&frame(2, 0x7E8, &[0x10, 0x14, 1, 2, 3, 4, 5, 6]));
+        assert!(tp.process_frame(&frame(3, 0x7E0, &[0x31, 0, 0])).is_empty());
+        let events = tp.process_frame(&frame(4, 0x7E0, &[0x31, 0, 0]));
+        assert_eq!(error_of(&events[0]), &CanTpErrorKind::WaitLimitExceeded { wait_frames: 2 });
+    }
+
+    #[test]
+    fn test_can_fd_escape_sequences() {
+        let mut tp = reassembler();
+
+        // Single frame with SF_DL in byte 1
+        let mut sf = vec![0x00, 20];
+        sf.extend(1..=20u8);
+        sf.resize(24, 0xCC);
+        let events = tp.process_frame(&frame(0, 0x7E8, &sf));
+        assert_eq!(payload_of(&events[0]), (1..=20u8).collect::<Vec<_>>().as_slice());
+
+        // First frame with 32-bit FF_DL (5000 bytes) followed by 64-byte CFs
+        let mut ff = vec![0x10, 0x00, 0x00, 0x00, 0x13, 0x88];
+        ff.resize(64, 0x11);
+        assert!(tp.process_frame(&frame(1, 0x7E8, &ff)).is_empty());
+
+        let mut received = 58;
+        let mut sn = 1u8;
+        let mut events = Vec::new();
+        while received < 5000 {
+            let mut cf = vec![0x20 | sn];
+            cf.resize(64, 0x22);
+            events = tp.process_frame(&frame(2, 0x7E8, &cf));
+            received += 63;
+            sn = (sn + 1) & 0x0F;
+        }
+        assert_eq!(events.len(), 1);
+        assert_eq!(payload_of(&events[0]).len(), 5000);
+
+        // Escaped FF_DL must exceed 4095, plain FF_DL must be at least 8
+        let invalid: [&[u8]; 3] = [
+            &[0x10, 0x00, 0x00, 0x00, 0x0F, 0xFF],
+            &[0x10, 0x00, 0x00, 0x00, 0x00, 0x00],
+            &[0x10, 0x07, 1, 2, 3, 4, 5, 6],
+        ];
+        for ff in invalid {
+            let events = tp.process_frame(&frame(3, 0x7E8, ff));
+            assert!(matches!(error_of(&events[0]), CanTpErrorKind::InvalidFrame(_)));
+        }
+
+        // Huge announced lengths are not reserved up front
+        assert!(tp.process_frame(&frame(4, 0x7E8, &[0x10, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 1, 2])).is_empty());
+        let events = tp.finish();
+        assert_eq!(error_of(&events[0]), &CanTpErrorKind::Timeout { received: 2, expected: 0xFFFF_FFFF });
+    }
+
+    #[test]
+    fn test_extended_addressing() {
+        let config = DecoderConfig::new()
+            .add_custom_cantp_pair(CanTpPair::extended(0x6F1, 0x640, 0xF1, 0x40))
+            .add_custom_cantp_pair(CanTpPair::extended(0x6F1, 0x612, 0xF1, 0x12));
+        let mut tp = CanTpReassembler::from_config(&config).unwrap();
+
+        let events = tp.process_frame(&frame(0, 0x6F1, &[0x12, 0x02, 0x10, 0x03, 0, 0, 0, 0]));
+        assert_eq!(payload_of(&events[0]), &[0x10, 0x03]);
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0xF1, target_addr: 0x12, .. }));
+
+        // Unknown target address on a shared CAN ID
+        assert!(tp.process_frame(&frame(1, 0x6F1, &[0x99, 0x02, 0x10, 0x03])).is_empty());
+
+        assert!(tp.process_frame(&frame(2, 0x640, &[0xF1, 0x10, 0x08, 1, 2, 3, 4, 5])).is_empty());
+        assert!(tp.process_frame(&frame(3, 0x6F1, &[0x40, 0x30, 0x00, 0x00])).is_empty());
+        let events = tp.process_frame(&frame(4, 0x640, &[0xF1, 0x21, 6, 7, 8, 0, 0, 0]));
+        assert_eq!(payload_of(&events[0]), &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0x40, target_addr: 0xF1, .. }));
+    }
+
+    #[test]
+    fn test_normal_fixed_addressing() {
+        let config = DecoderConfig::new().add_custom_cantp_pair(CanTpPair::normal_fixed(0xF1, 0x10));
+        let mut tp = CanTpReassembler::from_config(&config).unwrap();
+
+        let events = tp.process_frame(&frame(0, 0x18DA10F1, &[0x02, 0x3E, 0x00]));
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0xF1, target_addr: 0x10, .. }));
+
+        // Functional request to the OBD address
+        let events = tp.process_frame(&frame(1, 0x18DB33F1, &[0x02, 0x3E, 0x80]));
+        assert_eq!(payload_of(&events[0]), &[0x3E, 0x80]);
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0xF1, target_addr: 0x33, .. }));
+
+        tp.process_frame(&frame(2, 0x18DAF110, &[0x10, 0x09, 1, 2, 3, 4, 5, 6]));
+        assert!(tp.process_frame(&frame(3, 0x18DA10F1, &[0x30, 0, 0])).is_empty());
+        let events = tp.process_frame(&frame(4, 0x18DAF110, &[0x21, 7, 8, 9]));
+        assert_eq!(payload_of(&events[0]), &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert!(matches!(events[0], DecodedEvent::CanTpMessage { source_addr: 0x10, target_addr: 0xF1, .. }));
+    }
+
+    #[test]
+    fn test_mixed_addressing() {
+        let config = DecoderConfig::new().add_custom_cantp_pair(CanTpPair::mixed(0x18CE10F1, 0x18CEF110, 0x05));
+        let mut tp = CanTpReassembler::from_config(&config).unwrap();
+
+        // Wrong address extension
+        assert!(tp.process_frame(&frame(0, 0x18CEF110, &[0x06, 0x02, 0x50, 0x01])).is_empty());
+
+        tp.process_frame(&frame(1, 0x18CEF110, &[0x05, 0x10, 0x08, 1, 2, 3, 4, 5]));
+        tp.process_frame(&frame(2, 0x18CE10F1, &[0x05, 0x31, 0, 0]));
+        tp.process_frame(&frame(3, 0x18CE10F1, &[0x05, 0x30, 0, 0]));
+        let events = tp.process_frame(&frame(4, 0x18CEF110, &[0x05, 0x21, 6, 7, 8]));
+        assert_eq!(payload_of(&events[0]), &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(matches!(
+            events[0],
+            DecodedEvent::CanTpMessage { source_addr: 0x10, target_addr: 0xF1, address_extension: Some(0x05), .. }
+        ));
+    }
+}
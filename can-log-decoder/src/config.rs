@@ -7,9 +7,9 @@
 use serde::{Deserialize, Serialize};
 
 /// Configuration for the decoder library
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecoderConfig {
-    /// Whether to decode signals (false = only emit raw frames)
+    /// Whether to decode signals (false = only emit raw frames; CAN-TP is still reassembled)
     #[serde(default = "default_true")]
     pub decode_signals: bool,
 
@@ -17,7 +17,7 @@ pub struct DecoderConfig {
     #[serde(default)]
     pub cantp_pairs: Vec<CanTpPair>,
 
-    /// Container PDU IDs to unpack and decode
+    /// Container PDU IDs to unpack and decode (empty = unpack all known containers)
     #[serde(default)]
     pub container_ids: Vec<u32>,
 
@@ -29,6 +29,12 @@ pub struct DecoderConfig {
     #[serde(default)]
     pub message_filter: Option<Vec<u32>>,
 
+    /// Optional: only decode these signals (plus the multiplexers they depend on)
+    ///
+    /// Frames carrying none of them are skipped before signal extraction.
+    #[serde(default)]
+    pub signal_filter: Option<Vec<String>>,
+
     /// Whether to emit raw frames in addition to decoded messages
     #[serde(default)]
     pub emit_raw_frames: bool,
@@ -44,6 +50,42 @@ pub struct DecoderConfig {
     /// Maximum flow control wait frames to handle (default: 10)
     #[serde(default = "default_max_wait_frames")]
     pub cantp_max_wait_frames: usize,
+
+    /// Interpret reassembled CAN-TP payloads as UDS (ISO 14229) services
+    #[serde(default = "default_true")]
+    pub decode_uds: bool,
+
+    /// Decode OBD-II (SAE J1979) responses in CAN-TP payloads into signals
+    #[serde(default = "default_true")]
+    pub decode_obd: bool,
+
+    /// Reassemble J1939 transport protocol (BAM/CMDT/ETP) messages and track address claims
+    ///
+    /// Off by default: proprietary 29-bit IDs on non-J1939 buses can look like
+    /// transport protocol or address claim frames.
+    #[serde(default)]
+    pub decode_j1939: bool,
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        // Keep in sync with the serde defaults above
+        Self {
+            decode_signals: default_true(),
+            cantp_pairs: Vec::new(),
+            container_ids: Vec::new(),
+            channel_filter: None,
+            message_filter: None,
+            signal_filter: None,
+            emit_raw_frames: false,
+            cantp_auto_detect: false,
+            cantp_timeout_ms: default_cantp_timeout(),
+            cantp_max_wait_frames: default_max_wait_frames(),
+            decode_uds: default_true(),
+            decode_obd: default_true(),
+            decode_j1939: false,
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -67,6 +109,38 @@ pub struct CanTpPair {
     pub target: u32,
     /// Optional name for documentation
     pub name: Option<String>,
+    /// ISO-TP addressing mode (default: normal addressing)
+    #[serde(default)]
+    pub addressing: CanTpAddressing,
+}
+
+/// ISO 15765-2 addressing modes
+///
+/// Determines where N_SA/N_TA come from and whether byte 0 of each frame
+/// carries an address byte in front of the PCI.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum CanTpAddressing {
+    /// Normal addressing: the CAN IDs identify sender and receiver
+    #[default]
+    Normal,
+    /// Normal fixed addressing (29-bit): 0x18DA_TA_SA physical, 0x18DB_TA_SA functional
+    NormalFixed,
+    /// Extended addressing: byte 0 carries N_TA
+    Extended {
+        /// N_SA of the requester (carried in byte 0 of responses)
+        source_address: u8,
+        /// N_TA of the responder (carried in byte 0 of requests)
+        target_address: u8,
+    },
+    /// Mixed addressing: byte 0 carries N_AE
+    ///
+    /// 11-bit IDs identify sender/receiver like normal addressing; 29-bit IDs use
+    /// 0x18CE_TA_SA (physical) and 0x18CD_TA_SA (functional).
+    Mixed {
+        /// Address extension carried in byte 0 of every frame
+        address_extension: u8,
+    },
 }
 
 impl CanTpPair {
@@ -76,6 +150,7 @@ impl CanTpPair {
             source,
             target,
             name: None,
+            addressing: CanTpAddressing::Normal,
         }
     }
 
@@ -85,10 +160,72 @@ impl CanTpPair {
             source,
             target,
             name: Some(name.into()),
+            addressing: CanTpAddressing::Normal,
+        }
+    }
+
+    /// Create a normal fixed addressing pair from the 8-bit tester and ECU addresses
+    ///
+    /// Requests use 0x18DA_<ecu>_<tester>, responses 0x18DA_<tester>_<ecu>.
+    pub fn normal_fixed(tester_address: u8, ecu_address: u8) -> Self {
+        Self {
+            source: normal_fixed_id(ecu_address, tester_address),
+            target: normal_fixed_id(tester_address, ecu_address),
+            name: None,
+            addressing: CanTpAddressing::NormalFixed,
         }
     }
+
+    /// Create an extended addressing pair
+    ///
+    /// Requests on `source` carry `target_address` in byte 0, responses on
+    /// `target` carry `source_address`.
+    pub fn extended(source: u32, target: u32, source_address: u8, target_address: u8) -> Self {
+        Self {
+            source,
+            target,
+            name: None,
+            addressing: CanTpAddressing::Extended {
+                source_address,
+                target_address,
+            },
+        }
+    }
+
+    /// Create a mixed addressing pair with the given address extension
+    pub fn mixed(source: u32, target: u32, address_extension: u8) -> Self {
+        Self {
+            source,
+            target,
+            name: None,
+            addressing: CanTpAddressing::Mixed { address_extension },
+        }
+    }
+}
+
+/// Build a physical normal fixed addressing CAN ID (priority 6)
+fn normal_fixed_id(target_address: u8, source_address: u8) -> u32 {
+    0x18DA_0000 | ((target_address as u32) << 8) | source_address as u32
+}
+
+/// A CAN-TP pair found by auto-detection, with supporting evidence
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DetectedCanTpPair {
+    /// Detected pair (source = requester, target = responder)
+    pub pair: CanTpPair,
+    /// CAN channel the traffic was seen on
+    pub channel: u8,
+    /// Confidence score from 0.0 (no evidence) to 1.0 (certain)
+    pub confidence: f64,
+    /// Number of First Frame → Flow Control → Consecutive Frame handshakes seen
+    pub handshakes: usize,
+    /// Number of diagnostic request/response exchanges seen
+    pub request_responses: usize,
 }
 
+/// Minimum confidence for auto-detected pairs to be used during decoding
+pub const DEFAULT_CANTP_DETECT_CONFIDENCE: f64 = 0.5;
+
 impl DecoderConfig {
     /// Create a new decoder configuration with default settings
     pub fn new() -> Self {
@@ -113,6 +250,12 @@ impl DecoderConfig {
         self
     }
 
+    /// Builder method: add a fully specified CAN-TP pair (e.g. non-normal addressing)
+    pub fn add_custom_cantp_pair(mut self, pair: CanTpPair) -> Self {
+        self.cantp_pairs.push(pair);
+        self
+    }
+
     /// Builder method: add a container PDU ID
     pub fn add_container_id(mut self, container_id: u32) -> Self {
         self.container_ids.push(container_id);
@@ -131,6 +274,12 @@ impl DecoderConfig {
         self
     }
 
+    /// Builder method: set signal filter (signal names)
+    pub fn with_signal_filter<S: Into<String>>(mut self, signals: impl IntoIterator<Item = S>) -> Self {
+        self.signal_filter = Some(signals.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Builder method: enable raw frame emission
     pub fn with_raw_frames(mut self, enabled: bool) -> Self {
         self.emit_raw_frames = enabled;
@@ -143,6 +292,51 @@ impl DecoderConfig {
         self
     }
 
+    /// Builder method: enable/disable UDS interpretation of CAN-TP messages
+    pub fn with_uds_decoding(mut self, enabled: bool) -> Self {
+        self.decode_uds = enabled;
+        self
+    }
+
+    /// Builder method: enable/disable OBD-II decoding of CAN-TP messages
+    pub fn with_obd_decoding(mut self, enabled: bool) -> Self {
+        self.decode_obd = enabled;
+        self
+    }
+
+    /// Builder method: enable/disable J1939 transport protocol and address claim decoding
+    pub fn with_j1939_decoding(mut self, enabled: bool) -> Self {
+        self.decode_j1939 = enabled;
+        self
+    }
+
+    /// Builder method: merge auto-detected CAN-TP pairs into the explicit pairs
+    ///
+    /// Pairs below `min_confidence` and pairs already configured (in either
+    /// direction) are skipped, so explicit names and orientation take precedence.
+    pub fn with_detected_cantp_pairs(mut self, detected: &[DetectedCanTpPair], min_confidence: f64) -> Self {
+        self.merge_detected_cantp_pairs(detected, min_confidence);
+        self
+    }
+
+    /// Merge auto-detected CAN-TP pairs into the explicit pairs (hybrid mode)
+    ///
+    /// Returns the number of pairs that were added.
+    pub fn merge_detected_cantp_pairs(&mut self, detected: &[DetectedCanTpPair], min_confidence: f64) -> usize {
+        let mut added = 0;
+        for candidate in detected.iter().filter(|d| d.confidence >= min_confidence) {
+            let (source, target) = (candidate.pair.source, candidate.pair.target);
+            let known = self.cantp_pairs.iter().any(|p| {
+                (p.source == source && p.target == target) || (p.source == target && p.target == source)
+            });
+            if !known {
+                self.cantp_pairs.push(candidate.pair.clone());
+                added += 1;
+            }
+        }
+        added
+    }
+
     /// Check if a channel should be processed
     pub fn should_process_channel(&self, channel: u8) -> bool {
         match &self.channel_filter {
@@ -163,6 +357,13 @@ impl DecoderConfig {
     pub fn should_process_frame(&self, channel: u8, can_id: u32) -> bool {
         self.should_process_channel(channel) && self.should_process_message(can_id)
     }
+
+    /// Check if a container PDU should be unpacked
+    ///
+    /// An empty `container_ids` list means every known container is unpacked.
+    pub fn should_unpack_container(&self, container_id: u32) -> bool {
+        self.container_ids.is_empty() || self.container_ids.contains(&container_id)
+    }
 }
 
 #[cfg(test)]
@@ -177,15 +378,71 @@ mod tests {
             .add_named_cantp_pair(0x7E1, 0x7E9, "TCU_Diagnostics")
             .add_container_id(0x100)
             .with_channel_filter(vec![0, 1])
+            .with_signal_filter(["EngineSpeed", "VehicleSpeed"])
             .with_cantp_auto_detect(true);
 
         assert!(config.decode_signals);
         assert_eq!(config.cantp_pairs.len(), 2);
         assert_eq!(config.container_ids, vec![0x100]);
         assert_eq!(config.channel_filter, Some(vec![0, 1]));
+        assert_eq!(config.signal_filter.as_deref(), Some(&["EngineSpeed".to_string(), "VehicleSpeed".to_string()][..]));
         assert!(config.cantp_auto_detect);
     }
 
+    #[test]
+    fn test_default_matches_serde_defaults() {
+        let config = DecoderConfig::new();
+        assert!(config.decode_signals);
+        assert_eq!(config.cantp_timeout_ms, 1000);
+        assert_eq!(config.cantp_max_wait_frames, 10);
+        assert!(config.decode_uds);
+        assert!(config.decode_obd);
+        assert!(!config.decode_j1939);
+    }
+
+    #[test]
+    fn test_cantp_addressing() {
+        let pair = CanTpPair::normal_fixed(0xF1, 0x10);
+        assert_eq!(pair.source, 0x18DA10F1);
+        assert_eq!(pair.target, 0x18DAF110);
+
+        let config: DecoderConfig = serde_json::from_str(
+            r#"{"cantp_pairs": [
+                {"source": 1777, "target": 1600, "name": null,
+                 "addressing": {"mode": "extended", "source_address": 241, "target_address": 64}},
+                {"source": 2016, "target": 2024, "name": null}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.cantp_pairs[0], CanTpPair::extended(0x6F1, 0x640, 0xF1, 0x40));
+        assert_eq!(config.cantp_pairs[1].addressing, CanTpAddressing::Normal);
+    }
+
+    #[test]
+    fn test_merge_detected_pairs() {
+        let detected = |source, target, confidence| DetectedCanTpPair {
+            pair: CanTpPair::new(source, target),
+            channel: 0,
+            confidence,
+            handshakes: 1,
+            request_responses: 0,
+        };
+        let config = DecoderConfig::new()
+            .add_named_cantp_pair(0x7E0, 0x7E8, "ECU")
+            .with_detected_cantp_pairs(
+                &[
+                    detected(0x7E8, 0x7E0, 0.9), // Already configured (reversed)
+                    detected(0x7E1, 0x7E9, 0.8),
+                    detected(0x600, 0x680, 0.2), // Below threshold
+                ],
+                DEFAULT_CANTP_DETECT_CONFIDENCE,
+            );
+
+        assert_eq!(config.cantp_pairs.len(), 2);
+        assert_eq!(config.cantp_pairs[0].name.as_deref(), Some("ECU"));
+        assert_eq!(config.cantp_pairs[1], CanTpPair::new(0x7E1, 0x7E9));
+    }
+
     #[test]
     fn test_filter_logic() {
         let config = DecoderConfig::new()
@@ -198,6 +455,16 @@ mod tests {
         assert!(!config.should_process_frame(0, 0x789)); // Wrong message
     }
 
+    #[test]
+    fn test_container_selection() {
+        let config = DecoderConfig::new();
+        assert!(config.should_unpack_container(0x100));
+
+        let config = config.add_container_id(0x200);
+        assert!(config.should_unpack_container(0x200));
+        assert!(!config.should_unpack_container(0x100));
+    }
+
     #[test]
     fn test_no_filters() {
         let config = DecoderConfig::new();
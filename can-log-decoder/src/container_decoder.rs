@@ -7,13 +7,21 @@
 //!
 //! - **Static Container**: Fixed layout with PDUs always at the same positions
 //! - **Dynamic Container**: Variable layout with header indicating which PDUs are present
-//!   - SHORT-HEADER: 4 bytes (32 bits) with PDU ID + length for each contained PDU
-//!   - LONG-HEADER: 8 bytes (64 bits) with extended information
+//!   - SHORT-HEADER: 4 bytes with 24-bit PDU header ID + 8-bit length for each contained PDU
+//!   - LONG-HEADER: 8 bytes with 32-bit PDU header ID + 32-bit length
 //! - **Queued Container**: Multiple instances of the same PDU type
+//!
+//! Contained PDUs that are Secured I-PDUs (SecOC) are unwrapped to their
+//! authentic PDU, and contained containers are decoded recursively.
 
 use crate::message_decoder::MessageDecoder;
-use crate::signals::database::{ContainerDefinition, ContainerLayout, SignalDatabase};
-use crate::types::{CanFrame, ContainerType, DecodedEvent, Result, DecoderError};
+use crate::signals::database::{
+    ContainerDefinition, ContainerLayout, MessageDefinition, SecuredPduDefinition, SignalDatabase,
+    MAX_NESTING_DEPTH,
+};
+use crate::types::{
+    CanFrame, ContainedPdu, DecodedEvent, ParentContainer, Result, DecoderError, SecuredPduInfo,
+};
 
 /// Container PDU decoder
 pub struct ContainerDecoder;
@@ -34,13 +42,29 @@ impl ContainerDecoder {
         frame: &CanFrame,
         container_def: &ContainerDefinition,
         signal_db: &SignalDatabase,
+    ) -> Result<Vec<DecodedEvent>> {
+        Self::decode_nested_container(frame, container_def, signal_db, 0)
+    }
+
+    /// Decode a container at the given nesting depth (0 = carried by the frame)
+    fn decode_nested_container(
+        frame: &CanFrame,
+        container_def: &ContainerDefinition,
+        signal_db: &SignalDatabase,
+        depth: usize,
     ) -> Result<Vec<DecodedEvent>> {
         let mut events = Vec::new();
 
         match &container_def.layout {
             ContainerLayout::Static { pdus } => {
                 // Static container: PDUs are always at fixed positions
-                events.extend(Self::decode_static_container(frame, container_def, pdus, signal_db)?);
+                events.extend(Self::decode_static_container(
+                    frame,
+                    container_def,
+                    pdus,
+                    signal_db,
+                    depth,
+                )?);
             }
             ContainerLayout::Dynamic { header_size, pdus } => {
                 // Dynamic container: Header indicates which PDUs are present
@@ -50,6 +74,7 @@ impl ContainerDecoder {
                     *header_size,
                     pdus,
                     signal_db,
+                    depth,
                 )?);
             }
             ContainerLayout::Queued { pdu_id, pdu_size } => {
@@ -67,6 +92,154 @@ impl ContainerDecoder {
         Ok(events)
     }
 
+    /// Decode the signals of a contained PDU in the context of the carrying frame
+    ///
+    /// The message keeps the frame's channel and CAN ID and refers back to
+    /// its container.
+    fn decode_contained_pdu(
+        frame: &CanFrame,
+        container_def: &ContainerDefinition,
+        pdu_id: u32,
+        pdu_data: &[u8],
+        message_def: &MessageDefinition,
+        secured: Option<SecuredPduInfo>,
+    ) -> Option<DecodedEvent> {
+        let mut decoded = MessageDecoder::decode_pdu_data(
+            pdu_data,
+            message_def,
+            frame.timestamp(),
+            frame.channel,
+            frame.can_id,
+        )?;
+        if let DecodedEvent::Message { container, .. } = &mut decoded {
+            *container = Some(ParentContainer {
+                name: container_def.name.clone(),
+                pdu_id,
+                is_fd: frame.is_fd,
+                secured,
+            });
+        }
+        Some(decoded)
+    }
+
+    /// Resolve the payload of a contained PDU by name
+    ///
+    /// Secured I-PDUs are unwrapped to their authentic PDU first; nested
+    /// containers are decoded recursively, anything else as a message.
+    /// Returns the stripped SecOC data and the decoded events.
+    fn decode_contained_payload(
+        frame: &CanFrame,
+        container_def: &ContainerDefinition,
+        pdu_id: u32,
+        name: &str,
+        data: &[u8],
+        signal_db: &SignalDatabase,
+        depth: usize,
+    ) -> (Option<SecuredPduInfo>, Vec<DecodedEvent>) {
+        let (name, data, secured) = match signal_db.get_secured_pdu(name) {
+            Some(secured_def) => match Self::unwrap_secured_pdu(data, secured_def) {
+                Some((payload, info)) => (secured_def.payload_pdu.as_str(), payload, Some(info)),
+                None => {
+                    log::warn!(
+                        "Secured PDU {} too short for its authenticator and freshness value ({} bytes)",
+                        name,
+                        data.len()
+                    );
+                    return (None, Vec::new());
+                }
+            },
+            None => (name, data, None),
+        };
+
+        if let Some(nested_def) = signal_db.get_contained_container(name) {
+            if depth >= MAX_NESTING_DEPTH {
+                log::warn!("Container {} nested deeper than {} levels", name, MAX_NESTING_DEPTH);
+                return (secured, Vec::new());
+            }
+            let nested_frame = CanFrame {
+                data: data.to_vec(),
+                ..frame.clone()
+            };
+            return match Self::decode_nested_container(&nested_frame, nested_def, signal_db, depth + 1) {
+                Ok(events) => (secured, events),
+                Err(e) => {
+                    log::warn!("Failed to decode nested container {}: {}", name, e);
+                    (secured, Vec::new())
+                }
+            };
+        }
+
+        let Some(message_def) = signal_db.get_message_by_name(name) else {
+            log::debug!("No signal definition found for contained PDU: {}", name);
+            return (secured, Vec::new());
+        };
+        let events =
+            Self::decode_contained_pdu(frame, container_def, pdu_id, data, message_def, secured.clone())
+                .into_iter()
+                .collect();
+        (secured, events)
+    }
+
+    /// Split a Secured I-PDU into its authentic PDU and SecOC trailer
+    ///
+    /// Layout: `[header][authentic PDU][freshness value][authenticator]`. The
+    /// optional header holds the authentic PDU length; without one the trailer
+    /// takes the last bytes of the PDU.
+    fn unwrap_secured_pdu<'a>(
+        data: &'a [u8],
+        secured_def: &SecuredPduDefinition,
+    ) -> Option<(&'a [u8], SecuredPduInfo)> {
+        let freshness_bits = secured_def.freshness_length as usize;
+        let auth_bits = secured_def.authenticator_length as usize;
+        let trailer_len = (freshness_bits + auth_bits).div_ceil(8);
+
+        let header_size = secured_def.header_size;
+        let authentic_len = if header_size > 0 {
+            let header = data.get(..header_size)?;
+            header.iter().fold(0usize, |len, &b| (len << 8) | b as usize)
+        } else {
+            data.len().checked_sub(trailer_len)?
+        };
+        let authentic_end = header_size + authentic_len;
+        let authentic = data.get(header_size..authentic_end)?;
+        let trailer = data.get(authentic_end..authentic_end + trailer_len)?;
+
+        let freshness_value = read_bits(trailer, 0, freshness_bits.min(64));
+        let authenticator = (0..auth_bits)
+            .step_by(8)
+            .map(|bit| {
+                let len = (auth_bits - bit).min(8);
+                (read_bits(trailer, freshness_bits + bit, len) << (8 - len)) as u8
+            })
+            .collect();
+
+        Some((
+            authentic,
+            SecuredPduInfo {
+                name: secured_def.name.clone(),
+                freshness_value,
+                authenticator,
+            },
+        ))
+    }
+
+    /// Build the container event for the unpacked PDUs
+    fn container_event(
+        frame: &CanFrame,
+        container_def: &ContainerDefinition,
+        contained_pdus: Vec<ContainedPdu>,
+    ) -> DecodedEvent {
+        DecodedEvent::ContainerPdu {
+            timestamp: frame.timestamp(),
+            channel: frame.channel,
+            container_id: frame.can_id,
+            is_fd: frame.is_fd,
+            container_name: container_def.name.clone(),
+            container_type: container_def.container_type,
+            contained_pdus,
+        }
+    }
+
     /// Decode a Static Container PDU
     ///
     /// Static containers have a fixed layout where PDUs are always at the same byte positions.
@@ -76,6 +249,7 @@ impl ContainerDecoder {
         container_def: &ContainerDefinition,
         pdus: &[crate::signals::database::ContainedPduInfo],
         signal_db: &SignalDatabase,
+        depth: usize,
     ) -> Result<Vec<DecodedEvent>> {
         let mut contained_pdus = Vec::new();
         let mut decoded_events = Vec::new();
@@ -106,46 +280,49 @@ impl ContainerDecoder {
             // Extract PDU data
             let pdu_data = frame.data[pdu_info.position..end_pos].to_vec();
 
-            // Add to contained PDUs list
-            contained_pdus.push(crate::types::ContainedPdu {
-                pdu_id: pdu_info.pdu_id,
-                name: pdu_info.name.clone(),
-                data: pdu_data.clone(),
+            // A cleared update bit means the PDU holds stale data
+            let updated = pdu_info.update_bit.is_none_or(|bit| {
+                frame
+                    .data
+                    .get(bit as usize / 8)
+                    .is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
             });
 
-            // Try to decode signals from this PDU
-            if let Some(message_def) = signal_db.get_message_by_name(&pdu_info.name) {
-                if let Some(decoded_message) = MessageDecoder::decode_pdu_data(
+            let secured = if updated {
+                // Try to decode signals from this PDU
+                let (secured, events) = Self::decode_contained_payload(
+                    frame,
+                    container_def,
+                    pdu_info.pdu_id,
+                    &pdu_info.name,
                     &pdu_data,
-                    message_def,
-                    frame.timestamp(),
-                ) {
-                    log::debug!(
-                        "Decoded {} signals from contained PDU: {}",
-                        match &decoded_message {
-                            DecodedEvent::Message { signals, .. } => signals.len(),
-                            _ => 0,
-                        },
-                        pdu_info.name
-                    );
-                    decoded_events.push(decoded_message);
-                }
-            } else {
+                    signal_db,
+                    depth,
+                );
                 log::debug!(
-                    "No signal definition found for contained PDU: {}",
+                    "Decoded {} events from contained PDU: {}",
+                    events.len(),
                     pdu_info.name
                 );
-            }
+                decoded_events.extend(events);
+                secured
+            } else {
+                log::trace!("Contained PDU {} not updated", pdu_info.name);
+                None
+            };
+
+            // Add to contained PDUs list
+            contained_pdus.push(ContainedPdu {
+                pdu_id: pdu_info.pdu_id,
+                name: pdu_info.name.clone(),
+                data: pdu_data,
+                updated,
+                secured,
+            });
         }
 
         // Create container PDU event
-        let mut events = vec![DecodedEvent::ContainerPdu {
-            timestamp: frame.timestamp(),
-            container_id: container_def.id,
-            container_name: container_def.name.clone(),
-            container_type: container_def.container_type,
-            contained_pdus,
-        }];
+        let mut events = vec![Self::container_event(frame, container_def, contained_pdus)];
 
         // Add all decoded message events from contained PDUs
         events.extend(decoded_events);
@@ -157,17 +334,19 @@ impl ContainerDecoder {
     ///
     /// Dynamic containers use a header to indicate which PDUs are present.
     ///
+    /// The header ID is the HEADER-ID-SHORT-HEADER/HEADER-ID-LONG-HEADER of
+    /// the contained I-PDU.
+    ///
     /// ## SHORT-HEADER Format (4 bytes per PDU):
     /// ```text
-    /// Byte 0-1: PDU ID (16 bits, big-endian)
-    /// Byte 2:   PDU length (8 bits)
-    /// Byte 3:   Reserved/CRC
+    /// Byte 0-2: PDU header ID (24 bits, big-endian)
+    /// Byte 3:   PDU length (8 bits)
     /// ```
     ///
     /// ## LONG-HEADER Format (8 bytes per PDU):
     /// ```text
-    /// Byte 0-3: PDU ID (32 bits, big-endian)
-    /// Byte 4-7: PDU length + metadata
+    /// Byte 0-3: PDU header ID (32 bits, big-endian)
+    /// Byte 4-7: PDU length (32 bits, big-endian)
     /// ```
     fn decode_dynamic_container(
         frame: &CanFrame,
@@ -175,6 +354,7 @@ impl ContainerDecoder {
         header_size: usize,
         pdus: &[crate::signals::database::ContainedPduInfo],
         signal_db: &SignalDatabase,
+        depth: usize,
     ) -> Result<Vec<DecodedEvent>> {
         if frame.data.len() < header_size {
             return Err(DecoderError::InvalidData(format!(
@@ -192,18 +372,20 @@ impl ContainerDecoder {
         while offset + header_size <= frame.data.len() {
             let header_bytes = &frame.data[offset..offset + header_size];
 
-            // Check for end marker (all zeros)
-            if header_bytes.iter().all(|&b| b == 0) {
+            // Check for end marker (all zeros) or padding (unused bit pattern)
+            if header_bytes.iter().all(|&b| b == 0)
+                || header_bytes.iter().all(|&b| b == container_def.unused_bit_pattern)
+            {
                 break;
             }
 
             let (pdu_id, pdu_length) = if header_size == 4 {
-                // SHORT-HEADER: 2 bytes ID + 1 byte length + 1 byte reserved
-                let id = u16::from_be_bytes([header_bytes[0], header_bytes[1]]) as u32;
-                let len = header_bytes[2] as usize;
+                // SHORT-HEADER: 3 bytes ID + 1 byte length
+                let id = u32::from_be_bytes([0, header_bytes[0], header_bytes[1], header_bytes[2]]);
+                let len = header_bytes[3] as usize;
                 (id, len)
             } else if header_size == 8 {
-                // LONG-HEADER: 4 bytes ID + 4 bytes length/metadata
+                // LONG-HEADER: 4 bytes ID + 4 bytes length
                 let id = u32::from_be_bytes([
                     header_bytes[0],
                     header_bytes[1],
@@ -247,37 +429,33 @@ impl ContainerDecoder {
                 .map(|p| p.name.clone())
                 .unwrap_or_else(|| format!("PDU_{}", pdu_id));
 
+            // Try to decode signals from this PDU
+            let (secured, events) = Self::decode_contained_payload(
+                frame,
+                container_def,
+                pdu_id,
+                &pdu_name,
+                &pdu_data,
+                signal_db,
+                depth,
+            );
+            if !events.is_empty() {
+                log::debug!("Decoded signals from dynamic contained PDU: {}", pdu_name);
+            }
+            decoded_events.extend(events);
+
             // Add to contained PDUs list
-            contained_pdus.push(crate::types::ContainedPdu {
+            contained_pdus.push(ContainedPdu {
                 pdu_id,
-                name: pdu_name.clone(),
-                data: pdu_data.clone(),
+                name: pdu_name,
+                data: pdu_data,
+                updated: true,
+                secured,
             });
-
-            // Try to decode signals from this PDU
-            if let Some(message_def) = signal_db.get_message_by_name(&pdu_name) {
-                if let Some(decoded_message) = MessageDecoder::decode_pdu_data(
-                    &pdu_data,
-                    message_def,
-                    frame.timestamp(),
-                ) {
-                    log::debug!(
-                        "Decoded signals from dynamic contained PDU: {}",
-                        pdu_name
-                    );
-                    decoded_events.push(decoded_message);
-                }
-            }
         }
 
         // Create container PDU event
-        let mut events = vec![DecodedEvent::ContainerPdu {
-            timestamp: frame.timestamp(),
-            container_id: container_def.id,
-            container_name: container_def.name.clone(),
-            container_type: container_def.container_type,
-            contained_pdus,
-        }];
+        let mut events = vec![Self::container_event(frame, container_def, contained_pdus)];
 
         // Add all decoded message events from contained PDUs
         events.extend(decoded_events);
@@ -301,36 +479,34 @@ impl ContainerDecoder {
         let mut offset = 0;
         let mut instance = 0;
 
-        // For queued containers, try to find the PDU name by looking it up in the signal database
-        // Since queued containers only store pdu_id, we'll try to resolve the name via message lookup
-        let pdu_name_base: Option<String> = None; // Will be looked up per-instance if needed
-
         // Extract PDUs until we run out of data
         while offset + pdu_size <= frame.data.len() {
             let pdu_data = frame.data[offset..offset + pdu_size].to_vec();
 
-            // Check if PDU is empty (all zeros) - indicates end of queue
-            if pdu_data.iter().all(|&b| b == 0) {
+            // Check if PDU is empty (all zeros) or padding - indicates end of queue
+            if pdu_data.iter().all(|&b| b == 0)
+                || pdu_data.iter().all(|&b| b == container_def.unused_bit_pattern)
+            {
                 break;
             }
 
             let pdu_name = format!("PDU_{}_{}", pdu_id, instance);
 
             // Add to contained PDUs list
-            contained_pdus.push(crate::types::ContainedPdu {
+            contained_pdus.push(ContainedPdu {
                 pdu_id,
                 name: pdu_name.clone(),
                 data: pdu_data.clone(),
+                updated: true,
+                secured: None,
             });
 
             // Try to decode signals from this PDU by looking up the message by CAN ID
             // For queued containers, the pdu_id may map to a CAN message ID
-            if let Some(message_def) = signal_db.get_message(pdu_id) {
-                if let Some(decoded_message) = MessageDecoder::decode_pdu_data(
-                    &pdu_data,
-                    message_def,
-                    frame.timestamp(),
-                ) {
+            if let Some(message_def) = signal_db.get_message_on_channel(frame.channel, pdu_id) {
+                if let Some(decoded_message) =
+                    Self::decode_contained_pdu(frame, container_def, pdu_id, &pdu_data, message_def, None)
+                {
                     log::debug!(
                         "Decoded signals from queued PDU instance {}: ID 0x{:X}",
                         instance,
@@ -345,13 +521,7 @@ impl ContainerDecoder {
         }
 
         // Create container PDU event
-        let mut events = vec![DecodedEvent::ContainerPdu {
-            timestamp: frame.timestamp(),
-            container_id: container_def.id,
-            container_name: container_def.name.clone(),
-            container_type: container_def.container_type,
-            contained_pdus,
-        }];
+        let mut events = vec![Self::container_event(frame, container_def, contained_pdus)];
 
         // Add all decoded message events from contained PDUs
         events.extend(decoded_events);
@@ -360,15 +530,24 @@ impl ContainerDecoder {
     }
 }
 
+/// Read `len` bits (MSB first) starting at `bit_offset`; missing bits read as 0
+fn read_bits(data: &[u8], bit_offset: usize, len: usize) -> u64 {
+    (bit_offset..bit_offset + len).fold(0u64, |value, bit| {
+        let set = data.get(bit / 8).is_some_and(|byte| byte & (0x80 >> (bit % 8)) != 0);
+        (value << 1) | set as u64
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::signals::database::{ContainedPduInfo, SignalDatabase};
-    use crate::types::CanFrame;
+    use crate::types::{CanFrame, ContainerType};
 
     fn create_test_frame(data: Vec<u8>) -> CanFrame {
         CanFrame {
             timestamp_ns: 1000000000,
+            measurement_start_ns: 0,
             channel: 0,
             can_id: 0x100,
             data,
@@ -398,18 +577,21 @@ mod tests {
                 name: "PDU1".to_string(),
                 position: 0,
                 size: 2,
+                update_bit: None,
             },
             ContainedPduInfo {
                 pdu_id: 2,
                 name: "PDU2".to_string(),
                 position: 2,
                 size: 3,
+                update_bit: None,
             },
             ContainedPduInfo {
                 pdu_id: 3,
                 name: "PDU3".to_string(),
                 position: 5,
                 size: 3,
+                update_bit: None,
             },
         ];
 
@@ -420,11 +602,13 @@ mod tests {
             layout: ContainerLayout::Static {
                 pdus: pdus.clone(),
             },
+            unused_bit_pattern: 0,
+            channels: Vec::new(),
             source: "test".to_string(),
         };
 
         let signal_db = create_test_signal_db();
-        let events = ContainerDecoder::decode_static_container(&frame, &container_def, &pdus, &signal_db)
+        let events = ContainerDecoder::decode_static_container(&frame, &container_def, &pdus, &signal_db, 0)
             .expect("Failed to decode static container");
 
         assert_eq!(events.len(), 1);
@@ -441,14 +625,21 @@ mod tests {
     #[test]
     fn test_dynamic_container_short_header() {
         let frame = create_test_frame(vec![
-            // Header 1: PDU ID=0x0001, Length=2
-            0x00, 0x01, 0x02, 0x00, // PDU 1 data
-            0xAA, 0xBB, // Header 2: PDU ID=0x0002, Length=3
-            0x00, 0x02, 0x03, 0x00, // PDU 2 data
-            0xCC, 0xDD, 0xEE, // End marker
-            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x01, 0x02, // Header 1: PDU ID=0x000001, Length=2
+            0xAA, 0xBB, // PDU 1 data
+            0x12, 0x34, 0x56, 0x03, // Header 2: PDU ID=0x123456, Length=3
+            0xCC, 0xDD, 0xEE, // PDU 2 data
+            0x00, 0x00, 0x00, 0x00, // End marker
         ]);
 
+        let pdus = vec![ContainedPduInfo {
+            pdu_id: 0x123456,
+            name: "Pdu2".to_string(),
+            position: 0,
+            size: 3,
+            update_bit: None,
+        }];
+
         let container_def = ContainerDefinition {
             id: 0x100,
             name: "DynamicContainer".to_string(),
@@ -457,12 +648,14 @@ mod tests {
                 header_size: 4,
                 pdus: Vec::new(),
             },
+            unused_bit_pattern: 0,
+            channels: Vec::new(),
             source: "test".to_string(),
         };
 
         let signal_db = create_test_signal_db();
         let events =
-            ContainerDecoder::decode_dynamic_container(&frame, &container_def, 4, &[], &signal_db)
+            ContainerDecoder::decode_dynamic_container(&frame, &container_def, 4, &pdus, &signal_db, 0)
                 .expect("Failed to decode dynamic container");
 
         assert_eq!(events.len(), 1);
@@ -470,7 +663,9 @@ mod tests {
             assert_eq!(contained_pdus.len(), 2);
             assert_eq!(contained_pdus[0].pdu_id, 1);
             assert_eq!(contained_pdus[0].data, vec![0xAA, 0xBB]);
-            assert_eq!(contained_pdus[1].pdu_id, 2);
+            assert_eq!(contained_pdus[0].name, "PDU_1");
+            assert_eq!(contained_pdus[1].pdu_id, 0x123456);
+            assert_eq!(contained_pdus[1].name, "Pdu2");
             assert_eq!(contained_pdus[1].data, vec![0xCC, 0xDD, 0xEE]);
         } else {
             panic!("Expected ContainerPdu event");
@@ -494,6 +689,8 @@ mod tests {
                 pdu_id: 42,
                 pdu_size: 2,
             },
+            unused_bit_pattern: 0,
+            channels: Vec::new(),
             source: "test".to_string(),
         };
 
@@ -511,4 +708,76 @@ mod tests {
             panic!("Expected ContainerPdu event");
         }
     }
+
+    #[test]
+    fn test_update_bits_and_padding() {
+        // Update bits in byte 0: PDU1 updated (bit 0), PDU2 not (bit 1)
+        let frame = create_test_frame(vec![0x01, 0x11, 0x22, 0xFF]);
+        let pdus = vec![
+            ContainedPduInfo {
+                pdu_id: 1,
+                name: "PDU1".to_string(),
+                position: 1,
+                size: 1,
+                update_bit: Some(0),
+            },
+            ContainedPduInfo {
+                pdu_id: 2,
+                name: "PDU2".to_string(),
+                position: 2,
+                size: 1,
+                update_bit: Some(1),
+            },
+        ];
+        let container_def = ContainerDefinition {
+            id: 0x100,
+            name: "StaticContainer".to_string(),
+            container_type: ContainerType::Static,
+            layout: ContainerLayout::Static { pdus: pdus.clone() },
+            unused_bit_pattern: 0xFF,
+            channels: Vec::new(),
+            source: "test".to_string(),
+        };
+
+        let signal_db = create_test_signal_db();
+        let events = ContainerDecoder::decode_static_container(&frame, &container_def, &pdus, &signal_db, 0)
+            .expect("Failed to decode static container");
+        let DecodedEvent::ContainerPdu { contained_pdus, .. } = &events[0] else {
+            panic!("Expected ContainerPdu event");
+        };
+        assert!(contained_pdus[0].updated);
+        assert!(!contained_pdus[1].updated);
+
+        // Dynamic container padded with the unused bit pattern
+        let frame = create_test_frame(vec![0x00, 0x00, 0x01, 0x01, 0xAA, 0xFF, 0xFF, 0xFF, 0xFF]);
+        let events = ContainerDecoder::decode_dynamic_container(&frame, &container_def, 4, &[], &signal_db, 0)
+            .expect("Failed to decode dynamic container");
+        let DecodedEvent::ContainerPdu { contained_pdus, .. } = &events[0] else {
+            panic!("Expected ContainerPdu event");
+        };
+        assert_eq!(contained_pdus.len(), 1);
+        assert_eq!(contained_pdus[0].data, vec![0xAA]);
+    }
+
+    #[test]
+    fn test_unwrap_secured_pdu() {
+        let secured_def = SecuredPduDefinition {
+            name: "SecuredPdu".to_string(),
+            payload_pdu: "Payload".to_string(),
+            header_size: 1,
+            freshness_length: 4,
+            authenticator_length: 12,
+            source: "test".to_string(),
+        };
+
+        // Header: length 2; payload 11 22; FV 0xA, MAC 0xBCD; trailing padding
+        let data = [0x02, 0x11, 0x22, 0xAB, 0xCD, 0x00];
+        let (payload, info) = ContainerDecoder::unwrap_secured_pdu(&data, &secured_def).unwrap();
+        assert_eq!(payload, [0x11, 0x22]);
+        assert_eq!(info.freshness_value, 0xA);
+        assert_eq!(info.authenticator, [0xBC, 0xD0]);
+
+        // Header length pointing past the PDU
+        assert!(ContainerDecoder::unwrap_secured_pdu(&[0x08, 0x11], &secured_def).is_none());
+    }
 }
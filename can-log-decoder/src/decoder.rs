@@ -4,16 +4,26 @@
 //! The Decoder struct is the entry point for loading signal definitions and
 //! decoding log files.
 
-use crate::config::DecoderConfig;
+use crate::cantp::detect::CanTpDetector;
+use crate::cantp::CanTpReassembler;
+use crate::config::{DecoderConfig, DetectedCanTpPair, DEFAULT_CANTP_DETECT_CONFIDENCE};
 use crate::container_decoder::ContainerDecoder;
-use crate::signals::SignalDatabase;
-use crate::types::{CanFrame, DecodedEvent, Result};
+use crate::j1939::J1939Decoder;
+use crate::message_encoder::{EncodeValue, MessageEncoder};
+use crate::signals::{MessageDefinition, SignalDatabase};
+use crate::types::{CanFrame, DecodedEvent, DecoderError, Result};
+use crate::uds::descriptions::UdsDescriptions;
+use crate::uds::UdsInterpreter;
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::path::Path;
 
 /// The main decoder struct - entry point for all decoding operations
 pub struct Decoder {
     /// Internal signal database (loaded from DBC/ARXML files)
     signal_db: SignalDatabase,
+    /// DID/DTC descriptions for UDS decoding
+    uds_descriptions: UdsDescriptions,
 }
 
 impl Decoder {
@@ -21,6 +31,7 @@ impl Decoder {
     pub fn new() -> Self {
         Self {
             signal_db: SignalDatabase::new(),
+            uds_descriptions: UdsDescriptions::new(),
         }
     }
 
@@ -41,13 +52,33 @@ impl Decoder {
     /// decoder.add_dbc(Path::new("powertrain.dbc")).unwrap();
     /// ```
     pub fn add_dbc(&mut self, path: &Path) -> Result<()> {
-        log::info!("Loading DBC file: {:?}", path);
+        self.add_dbc_on_channels(path, &[])
+    }
+
+    /// Load a DBC file whose definitions only apply to frames on `channels`
+    ///
+    /// Definitions bound to a frame's channel take precedence over global
+    /// ones, so buses reusing a CAN ID with different layouts decode
+    /// correctly. An empty `channels` slice loads the file globally.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::Decoder;
+    /// use std::path::Path;
+    ///
+    /// let mut decoder = Decoder::new();
+    /// decoder.add_dbc_on_channels(Path::new("private_can.dbc"), &[2, 3]).unwrap();
+    /// decoder.add_dbc(Path::new("powertrain.dbc")).unwrap();
+    /// ```
+    pub fn add_dbc_on_channels(&mut self, path: &Path, channels: &[u8]) -> Result<()> {
+        log::info!("Loading DBC file: {:?} (channels {:?})", path, channels);
 
         // Parse DBC file
         let messages = crate::signals::dbc::parse_dbc_file(path)?;
 
         // Add all messages to the database
-        for message in messages {
+        for mut message in messages {
+            message.channels = channels.to_vec();
             self.signal_db.add_message(message);
         }
 
@@ -72,25 +103,92 @@ impl Decoder {
     /// decoder.add_arxml(Path::new("system.arxml")).unwrap();
     /// ```
     pub fn add_arxml(&mut self, path: &Path) -> Result<()> {
+        self.add_arxml_on_channels(path, &[])
+    }
+
+    /// Load an ARXML file, binding its CAN clusters to log channels
+    ///
+    /// `clusters` maps CAN-CLUSTER or CAN-PHYSICAL-CHANNEL short names to log
+    /// channels. Frames and containers triggered on a mapped cluster only
+    /// apply to its channels; everything else is loaded globally.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::Decoder;
+    /// use std::path::Path;
+    ///
+    /// let mut decoder = Decoder::new();
+    /// decoder
+    ///     .add_arxml_on_channels(Path::new("system.arxml"), &[("PowertrainCAN", 1), ("ChassisCAN", 2)])
+    ///     .unwrap();
+    /// ```
+    pub fn add_arxml_on_channels(&mut self, path: &Path, clusters: &[(&str, u8)]) -> Result<()> {
         log::info!("Loading ARXML file: {:?}", path);
 
         // Parse ARXML file
-        let (messages, containers) = crate::signals::arxml::parse_arxml_file(path)?;
+        let definitions = crate::signals::arxml::parse_arxml_file(path)?;
+
+        let pdu_clusters = &definitions.pdu_clusters;
+        let channels_of = |pdu_name: &str| {
+            let mut channels: Vec<u8> = pdu_clusters
+                .get(pdu_name)
+                .into_iter()
+                .flatten()
+                .filter_map(|name| clusters.iter().find(|(cluster, _)| cluster == name))
+                .map(|&(_, channel)| channel)
+                .collect();
+            channels.sort_unstable();
+            channels.dedup();
+            channels
+        };
 
         // Add all messages to the database
-        for message in messages {
+        for mut message in definitions.messages {
+            message.channels = channels_of(&message.name);
             self.signal_db.add_message(message);
         }
 
-        // Add all containers to the database
-        for container in containers {
+        // Add all containers and the PDUs they carry to the database
+        for mut container in definitions.containers {
+            container.channels = channels_of(&container.name);
             self.signal_db.add_container(container);
         }
+        for pdu in definitions.contained_pdus {
+            self.signal_db.add_contained_pdu(pdu);
+        }
+        for container in definitions.contained_containers {
+            self.signal_db.add_contained_container(container);
+        }
+        for secured_pdu in definitions.secured_pdus {
+            self.signal_db.add_secured_pdu(secured_pdu);
+        }
 
         log::info!("ARXML file loaded successfully: {:?}", path);
         Ok(())
     }
 
+    /// Load a DID/DTC description file (TOML or JSON) for UDS decoding
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::Decoder;
+    /// use std::path::Path;
+    ///
+    /// let mut decoder = Decoder::new();
+    /// decoder.add_uds_descriptions(Path::new("diagnostics.toml")).unwrap();
+    /// ```
+    pub fn add_uds_descriptions(&mut self, path: &Path) -> Result<()> {
+        log::info!("Loading UDS descriptions: {:?}", path);
+        let descriptions = UdsDescriptions::from_file(path)?;
+        self.uds_descriptions.merge(descriptions);
+        Ok(())
+    }
+
+    /// Get the loaded DID/DTC descriptions
+    pub fn uds_descriptions(&self) -> &UdsDescriptions {
+        &self.uds_descriptions
+    }
+
     /// Decode a log file and return an iterator of decoded events
     ///
     /// This is the main decoding function. It returns an iterator that lazily decodes
@@ -122,10 +220,72 @@ impl Decoder {
     pub fn decode_file(
         &self,
         path: &Path,
-        _config: DecoderConfig,
+        mut config: DecoderConfig,
     ) -> Result<Box<dyn Iterator<Item = Result<DecodedEvent>> + '_>> {
         log::info!("Decoding log file: {:?}", path);
 
+        // Auto-detection mode: pre-scan the file and merge the detected pairs (hybrid mode)
+        if config.cantp_auto_detect {
+            let detected = Self::detect_pairs_with_window(path, config.cantp_timeout_ms)?;
+            let added = config.merge_detected_cantp_pairs(&detected, DEFAULT_CANTP_DETECT_CONFIDENCE);
+            log::info!("CAN-TP auto-detection added {} pair(s)", added);
+        }
+
+        let frame_iter = Self::open_frames(path)?;
+        Ok(Box::new(self.decode_frames(frame_iter, config)))
+    }
+
+    /// Decode a stream of CAN frames that did not come from a log file
+    ///
+    /// Same as [`decode_file`](Self::decode_file) for frames from another
+    /// source (live capture, synthetic traces). CAN-TP auto-detection is not
+    /// applied since it needs a pre-scan of the whole log.
+    pub fn decode_frames<'a, I>(&'a self, frames: I, config: DecoderConfig) -> impl Iterator<Item = Result<DecodedEvent>> + 'a
+    where
+        I: Iterator<Item = Result<CanFrame>> + 'a,
+    {
+        DecodingIterator::new(frames, &self.signal_db, &self.uds_descriptions, config)
+    }
+
+    /// Scan a log file for CAN-TP (ISO-TP) traffic and propose address pairs
+    ///
+    /// Looks for First Frame → Flow Control → Consecutive Frame handshakes and
+    /// diagnostic request/response Single Frames on each channel. Results are
+    /// sorted by confidence (highest first) and can be merged into an explicit
+    /// configuration with [`DecoderConfig::with_detected_cantp_pairs`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::{Decoder, DecoderConfig};
+    /// use std::path::Path;
+    ///
+    /// let decoder = Decoder::new();
+    /// let detected = decoder.detect_cantp_pairs(Path::new("supplier_trace.blf")).unwrap();
+    /// for candidate in &detected {
+    ///     println!("0x{:X} → 0x{:X} ({:.0}%)", candidate.pair.source, candidate.pair.target,
+    ///         candidate.confidence * 100.0);
+    /// }
+    /// let config = DecoderConfig::new().with_detected_cantp_pairs(&detected, 0.8);
+    /// ```
+    pub fn detect_cantp_pairs(&self, path: &Path) -> Result<Vec<DetectedCanTpPair>> {
+        Self::detect_pairs_with_window(path, DecoderConfig::default().cantp_timeout_ms)
+    }
+
+    fn detect_pairs_with_window(path: &Path, window_ms: u64) -> Result<Vec<DetectedCanTpPair>> {
+        log::info!("Scanning for CAN-TP pairs: {:?}", path);
+
+        let mut detector = CanTpDetector::new(window_ms);
+        for frame in Self::open_frames(path)? {
+            detector.process_frame(&frame?);
+        }
+
+        let detected = detector.finish();
+        log::info!("Detected {} CAN-TP pair candidate(s)", detected.len());
+        Ok(detected)
+    }
+
+    /// Open a log file and return an iterator over its raw CAN frames
+    fn open_frames(path: &Path) -> Result<Box<dyn Iterator<Item = Result<CanFrame>>>> {
         // Determine file type from extension
         let extension = path.extension()
             .and_then(|s| s.to_str())
@@ -134,13 +294,11 @@ impl Decoder {
         match extension.as_deref() {
             Some("blf") => {
                 log::debug!("Detected BLF file format");
-                let frame_iter = crate::formats::BlfParser::parse(path)?;
-                Ok(Box::new(DecodingIterator::new(frame_iter, &self.signal_db)))
+                Ok(Box::new(crate::formats::BlfParser::parse(path)?))
             }
             Some("mf4") | Some("mdf") => {
                 log::debug!("Detected MF4 file format");
-                let frame_iter = crate::formats::Mf4Parser::parse(path)?;
-                Ok(Box::new(DecodingIterator::new(frame_iter, &self.signal_db)))
+                Ok(Box::new(crate::formats::Mf4Parser::parse(path)?))
             }
             _ => {
                 Err(crate::types::DecoderError::LogParseError(
@@ -154,6 +312,45 @@ impl Decoder {
     pub fn database_stats(&self) -> DatabaseStats {
         self.signal_db.stats()
     }
+
+    /// Get a loaded message definition by name
+    pub fn message_definition(&self, message_name: &str) -> Option<&MessageDefinition> {
+        self.signal_db.get_message_by_name(message_name)
+    }
+
+    /// Encode signal values into the bytes of a loaded message
+    ///
+    /// Out-of-range values are rejected; use [`MessageEncoder`] with
+    /// [`message_definition`](Self::message_definition) to clamp instead.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::{Decoder, EncodeValue};
+    /// use std::path::Path;
+    ///
+    /// let mut decoder = Decoder::new();
+    /// decoder.add_dbc(Path::new("powertrain.dbc")).unwrap();
+    /// let data = decoder
+    ///     .encode_message("EngineData", [("EngineSpeed", EncodeValue::Physical(2500.0))])
+    ///     .unwrap();
+    /// ```
+    pub fn encode_message<K, V>(&self, message_name: &str, values: impl IntoIterator<Item = (K, V)>) -> Result<Vec<u8>>
+    where
+        K: AsRef<str>,
+        V: Into<EncodeValue>,
+    {
+        let message_def = self
+            .message_definition(message_name)
+            .ok_or_else(|| DecoderError::MessageNameNotFound(message_name.to_string()))?;
+        MessageEncoder::new().encode(message_def, values)
+    }
+
+    /// Check the loaded definitions for conflicts and inconsistencies
+    ///
+    /// See [`SignalDatabase::validate`] for the checks performed.
+    pub fn validate_database(&self) -> ValidationReport {
+        self.signal_db.validate()
+    }
 }
 
 impl Default for Decoder {
@@ -165,80 +362,212 @@ impl Default for Decoder {
 /// Iterator that decodes CAN frames into decoded events
 ///
 /// This iterator wraps a frame iterator and processes each frame:
-/// 1. Check if CAN ID is a container → decode container PDU
-/// 2. Check if CAN ID is a message → decode message signals
-/// 3. Otherwise → emit raw frame event
+/// 1. Apply channel/message filters → skip frame entirely if filtered out
+/// 2. Check if CAN ID is a container → decode container PDU
+/// 3. Check if CAN ID is a message → decode message signals
+/// 4. Otherwise → emit raw frame event
+/// 5. Feed configured CAN-TP pairs into the reassembler → emit CanTpMessage
+/// 6. Interpret CAN-TP payloads as UDS services → emit UdsMessage
+/// 7. Decode OBD-II responses in CAN-TP payloads → emit Message (synthetic names)
+/// 8. Track J1939 address claims and reassemble J1939 transport protocol messages (if enabled)
+///    → emit J1939AddressClaim / J1939TpMessage (+ Message if the PGN is known)
+///
+/// Frames that decode into several events (containers, raw + decoded) are
+/// queued and emitted in order before the next frame is read.
 struct DecodingIterator<'a, I>
 where
     I: Iterator<Item = Result<CanFrame>>,
 {
     frame_iter: I,
-    signal_db: &'a SignalDatabase,
-    pending_events: Vec<DecodedEvent>,
+    /// Signal database, reduced to the selected signals if a signal filter is set
+    signal_db: Cow<'a, SignalDatabase>,
+    config: DecoderConfig,
+    cantp: Option<CanTpReassembler>,
+    uds: Option<UdsInterpreter<'a>>,
+    j1939: Option<J1939Decoder>,
+    pending_events: VecDeque<DecodedEvent>,
+    finished: bool,
 }
 
 impl<'a, I> DecodingIterator<'a, I>
 where
     I: Iterator<Item = Result<CanFrame>>,
 {
-    fn new(frame_iter: I, signal_db: &'a SignalDatabase) -> Self {
+    fn new(
+        frame_iter: I,
+        signal_db: &'a SignalDatabase,
+        uds_descriptions: &'a UdsDescriptions,
+        config: DecoderConfig,
+    ) -> Self {
+        let cantp = CanTpReassembler::from_config(&config);
+        let uds = (cantp.is_some() && config.decode_uds).then(|| UdsInterpreter::with_descriptions(uds_descriptions));
+        let j1939 = config.decode_j1939.then(J1939Decoder::new);
+        let signal_db = match &config.signal_filter {
+            Some(signals) => Cow::Owned(signal_db.select_signals(signals)),
+            None => Cow::Borrowed(signal_db),
+        };
         Self {
             frame_iter,
             signal_db,
-            pending_events: Vec::new(),
+            config,
+            cantp,
+            uds,
+            j1939,
+            pending_events: VecDeque::new(),
+            finished: false,
         }
     }
 
-    /// Process a single CAN frame and generate decoded event(s)
-    fn process_frame(&mut self, frame: CanFrame) -> Result<Option<DecodedEvent>> {
+    /// Process a single CAN frame and queue the resulting decoded event(s)
+    fn process_frame(&mut self, frame: CanFrame) -> Result<()> {
         let can_id = frame.can_id;
 
-        // Check if this is a container PDU
-        if let Some(container_def) = self.signal_db.get_container(can_id) {
-            log::debug!("Decoding container PDU: {} (ID: 0x{:X})", container_def.name, can_id);
+        // Apply filters before any decoding work
+        if !self.config.should_process_frame(frame.channel, can_id) {
+            log::trace!("Filtered out frame: channel {} ID 0x{:X}", frame.channel, can_id);
+            return Ok(());
+        }
 
-            // Decode container - this returns a Vec of events
-            let container_events = ContainerDecoder::decode_container(&frame, container_def, self.signal_db)?;
+        // CAN-TP reassembly runs independently of signal decoding
+        let cantp_events = match self.cantp.as_mut() {
+            Some(cantp) => cantp.process_frame(&frame),
+            None => Vec::new(),
+        };
 
-            // Split: first event to return, rest go to pending queue
-            let mut events_iter = container_events.into_iter();
-            let first_event = events_iter.next();
+        let j1939_event = self.j1939.as_mut().and_then(|j1939| j1939.process_frame(&frame));
 
-            // Store remaining events for later emission
-            self.pending_events.extend(events_iter);
+        self.decode_frame(frame)?;
+        self.queue_cantp_events(cantp_events);
+        if let Some(event) = j1939_event {
+            self.queue_j1939_event(event);
+        }
+        Ok(())
+    }
+
+    /// Queue a J1939 event, followed by the decoded message for transport payloads
+    fn queue_j1939_event(&mut self, event: DecodedEvent) {
+        let decoded = match &event {
+            DecodedEvent::J1939TpMessage {
+                timestamp,
+                channel,
+                pgn,
+                source_address,
+                destination_address,
+                payload,
+            } if self.config.decode_signals => self
+                .signal_db
+                .get_message_by_pgn_on_channel(*channel, *pgn)
+                .and_then(|message_def| {
+                    let can_id = crate::j1939::build_id(
+                        crate::j1939::priority(message_def.id),
+                        *pgn,
+                        *destination_address,
+                        *source_address,
+                    );
+                    let mut decoded = crate::message_decoder::MessageDecoder::decode_pdu_data(
+                        payload,
+                        message_def,
+                        *timestamp,
+                        *channel,
+                        can_id,
+                    )?;
+                    if let DecodedEvent::Message { source_address: sa, .. } = &mut decoded {
+                        *sa = Some(*source_address);
+                    }
+                    Some(decoded)
+                }),
+            _ => None,
+        };
+        self.pending_events.push_back(event);
+        self.pending_events.extend(decoded);
+    }
+
+    /// Queue CAN-TP events, each message followed by its UDS/OBD-II interpretation
+    fn queue_cantp_events(&mut self, events: Vec<DecodedEvent>) {
+        for event in events {
+            let uds_event = self.uds.as_mut().and_then(|uds| uds.process_event(&event));
+            let obd_event = if self.config.decode_obd {
+                crate::obd::process_event(&event)
+            } else {
+                None
+            };
+            self.pending_events.push_back(event);
+            self.pending_events.extend(uds_event);
+            self.pending_events.extend(obd_event);
+        }
+    }
+
+    /// Decode a frame that passed the filters and queue its event(s)
+    fn decode_frame(&mut self, frame: CanFrame) -> Result<()> {
+        let can_id = frame.can_id;
 
-            // Return the first event
-            Ok(first_event)
+        // Signal decoding disabled - only raw frames are emitted
+        if !self.config.decode_signals {
+            self.pending_events.push_back(Self::raw_frame_event(frame));
+            return Ok(());
         }
-        // Check if this is a regular message
-        else if let Some(message_def) = self.signal_db.get_message(can_id) {
+
+        // Check if this is a container PDU that should be unpacked
+        if let Some(container_def) = self
+            .signal_db
+            .get_container_on_channel(frame.channel, can_id)
+            .filter(|_| self.config.should_unpack_container(can_id))
+        {
+            log::debug!("Decoding container PDU: {} (ID: 0x{:X})", container_def.name, can_id);
+
+            // Decode container - this returns a Vec of events
+            let container_events = ContainerDecoder::decode_container(&frame, container_def, &self.signal_db)?;
+
+            if self.config.emit_raw_frames {
+                self.pending_events.push_back(Self::raw_frame_event(frame));
+            }
+            self.pending_events.extend(container_events);
+        }
+        // Check if this is a regular message (the signal filter leaves channel-bound
+        // definitions without signals in place so they still shadow global ones)
+        else if let Some(message_def) = self
+            .signal_db
+            .get_message_on_channel(frame.channel, can_id)
+            .filter(|message_def| !message_def.signals.is_empty() || self.config.signal_filter.is_none())
+        {
             log::debug!("Decoding message: {} (ID 0x{:X})", message_def.name, can_id);
 
             // Decode message signals using MessageDecoder
-            if let Some(decoded_event) = crate::message_decoder::MessageDecoder::decode_message(&frame, message_def) {
-                Ok(Some(decoded_event))
-            } else {
-                // Decoding failed, emit as raw frame
-                log::warn!("Failed to decode message 0x{:X}, emitting as raw frame", can_id);
-                Ok(Some(DecodedEvent::RawFrame {
-                    timestamp: frame.timestamp(),
-                    channel: frame.channel,
-                    can_id: frame.can_id,
-                    data: frame.data,
-                    is_fd: frame.is_fd,
-                }))
+            match crate::message_decoder::MessageDecoder::decode_message(&frame, message_def) {
+                Some(decoded_event) => {
+                    if self.config.emit_raw_frames {
+                        self.pending_events.push_back(Self::raw_frame_event(frame));
+                    }
+                    self.pending_events.push_back(decoded_event);
+                }
+                None => {
+                    // Decoding failed, emit as raw frame
+                    log::warn!("Failed to decode message 0x{:X}, emitting as raw frame", can_id);
+                    self.pending_events.push_back(Self::raw_frame_event(frame));
+                }
             }
         }
-        // Unknown CAN ID - emit as raw frame
+        // Carries none of the filtered signals - skip without decoding
+        else if self.config.signal_filter.is_some() {
+            log::trace!("No filtered signal in ID 0x{:X}, skipping frame", can_id);
+        }
+        // Unknown CAN ID (or container not selected for unpacking) - emit as raw frame
         else {
             log::trace!("Unknown CAN ID: 0x{:X}, emitting as raw frame", can_id);
-            Ok(Some(DecodedEvent::RawFrame {
-                timestamp: frame.timestamp(),
-                channel: frame.channel,
-                can_id: frame.can_id,
-                data: frame.data,
-                is_fd: frame.is_fd,
-            }))
+            self.pending_events.push_back(Self::raw_frame_event(frame));
+        }
+
+        Ok(())
+    }
+
+    /// Build a RawFrame event from a CAN frame
+    fn raw_frame_event(frame: CanFrame) -> DecodedEvent {
+        DecodedEvent::RawFrame {
+            timestamp: frame.timestamp(),
+            channel: frame.channel,
+            can_id: frame.can_id,
+            data: frame.data,
+            is_fd: frame.is_fd,
         }
     }
 }
@@ -250,31 +579,358 @@ where
     type Item = Result<DecodedEvent>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // First, return any pending events from container decoding
-        if let Some(event) = self.pending_events.pop() {
-            return Some(Ok(event));
-        }
+        loop {
+            // First, return any pending events from previously processed frames
+            if let Some(event) = self.pending_events.pop_front() {
+                return Some(Ok(event));
+            }
+
+            if self.finished {
+                return None;
+            }
 
-        // Get next frame from underlying iterator
-        match self.frame_iter.next()? {
-            Ok(frame) => {
-                match self.process_frame(frame) {
-                    Ok(Some(event)) => Some(Ok(event)),
-                    Ok(None) => self.next(), // No event generated, get next frame
-                    Err(e) => Some(Err(e)),
+            // Get next frame from underlying iterator
+            match self.frame_iter.next() {
+                Some(Ok(frame)) => {
+                    // Filtered frames produce no events - keep reading
+                    if let Err(e) = self.process_frame(frame) {
+                        return Some(Err(e));
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    // End of log - report CAN-TP transfers that never completed
+                    self.finished = true;
+                    if let Some(cantp) = self.cantp.as_mut() {
+                        self.pending_events.extend(cantp.finish());
+                    }
                 }
             }
-            Err(e) => Some(Err(e)),
         }
     }
 }
 
 // Re-export DatabaseStats for public API
 pub use crate::signals::DatabaseStats;
+pub use crate::signals::ValidationReport;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::signals::database::{ByteOrder, MessageDefinition, SignalDefinition};
+
+    fn test_frame(channel: u8, can_id: u32, data: Vec<u8>) -> CanFrame {
+        CanFrame {
+            timestamp_ns: 1_000_000_000,
+            measurement_start_ns: 0,
+            channel,
+            can_id,
+            data,
+            is_extended: false,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    fn test_signal_db() -> SignalDatabase {
+        let mut db = SignalDatabase::new();
+        let mut signal = SignalDefinition::test("EngineSpeed", 0, 16, ByteOrder::LittleEndian);
+        signal.max = 8000.0;
+        signal.unit = Some("rpm".to_string());
+        db.add_message(MessageDefinition::test(0x123, "EngineData", 8, vec![signal]));
+        db
+    }
+
+    fn decode_frames(frames: Vec<CanFrame>, db: &SignalDatabase, config: DecoderConfig) -> Vec<DecodedEvent> {
+        let descriptions = UdsDescriptions::new();
+        DecodingIterator::new(frames.into_iter().map(Ok), db, &descriptions, config)
+            .map(|e| e.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_filters_skip_frames() {
+        let db = test_signal_db();
+        let frames = vec![
+            test_frame(0, 0x123, vec![0x10, 0x27]),
+            test_frame(1, 0x123, vec![0x10, 0x27]),
+            test_frame(0, 0x456, vec![0x00]),
+        ];
+        let config = DecoderConfig::new()
+            .with_channel_filter(vec![0])
+            .with_message_filter(vec![0x123]);
+
+        let events = decode_frames(frames, &db, config);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], DecodedEvent::Message { channel: 0, can_id: 0x123, .. }));
+    }
+
+    #[test]
+    fn test_channel_bound_definitions() {
+        let mut db = test_signal_db();
+        let mut private = db.get_message(0x123).unwrap().clone();
+        private.name = "PrivateData".to_string();
+        private.channels = vec![1];
+        db.add_message(private);
+
+        let frames = vec![
+            test_frame(0, 0x123, vec![0x10, 0x27]),
+            test_frame(1, 0x123, vec![0x10, 0x27]),
+        ];
+        let names: Vec<_> = decode_frames(frames, &db, DecoderConfig::new())
+            .into_iter()
+            .filter_map(|event| match event {
+                DecodedEvent::Message { message_name, .. } => message_name,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["EngineData", "PrivateData"]);
+    }
+
+    #[test]
+    fn test_signal_filter() {
+        let mut db = test_signal_db();
+        let mut multiplexed = db.get_message(0x123).unwrap().clone();
+        multiplexed.id = 0x200;
+        multiplexed.name = "Multiplexed".to_string();
+        multiplexed.is_multiplexed = true;
+        multiplexed.multiplexer_signal = Some("Mode".to_string());
+        let template = multiplexed.signals[0].clone();
+        multiplexed.signals = ["Mode", "A", "B"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| SignalDefinition {
+                name: name.to_string(),
+                start_bit: if i == 0 { 0 } else { 8 },
+                length: 8,
+                multiplexer_info: (i > 0).then(|| crate::signals::MultiplexerInfo {
+                    multiplexer_signal: "Mode".to_string(),
+                    multiplexer_values: vec![i as u64 - 1],
+                    multiplexer_ranges: Vec::new(),
+                }),
+                ..template.clone()
+            })
+            .collect();
+        db.add_message(multiplexed);
+
+        let frames = vec![
+            test_frame(0, 0x123, vec![0x10, 0x27]),
+            test_frame(0, 0x200, vec![0x00, 0x05]),
+            test_frame(0, 0x200, vec![0x01, 0x05]),
+            test_frame(0, 0x7FF, vec![0x00]),
+        ];
+        let events = decode_frames(frames, &db, DecoderConfig::new().with_signal_filter(["A"]));
+
+        // Other messages and unknown IDs are skipped; A comes with its multiplexer
+        let decoded: Vec<Vec<String>> = events
+            .iter()
+            .map(|event| match event {
+                DecodedEvent::Message { can_id: 0x200, signals, .. } => {
+                    signals.iter().map(|s| s.name.to_string()).collect()
+                }
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        assert_eq!(decoded, [vec!["Mode", "A"], vec!["Mode"]]);
+    }
+
+    #[test]
+    fn test_signal_filter_keeps_channel_bindings() {
+        use std::io::Write;
+
+        let write_dbc = |message: &str| {
+            let mut file = tempfile::Builder::new().suffix(".dbc").tempfile().unwrap();
+            write!(file, "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: ECU\n\n{}\n", message).unwrap();
+            file
+        };
+        let global = write_dbc("BO_ 291 EngineData: 8 ECU\n SG_ EngineSpeed : 0|16@1+ (1,0) [0|8000] \"rpm\" ECU");
+        let private = write_dbc("BO_ 291 PrivateData: 8 ECU\n SG_ Counter : 0|8@1+ (1,0) [0|255] \"\" ECU");
+
+        let mut decoder = Decoder::new();
+        decoder.add_dbc(global.path()).unwrap();
+        decoder.add_dbc_on_channels(private.path(), &[2]).unwrap();
+
+        let frames = vec![
+            test_frame(1, 0x123, vec![0x10, 0x27]),
+            test_frame(2, 0x123, vec![0x10, 0x27]),
+        ];
+        let config = DecoderConfig::new().with_signal_filter(["EngineSpeed"]);
+        let events: Vec<_> = decoder.decode_frames(frames.into_iter().map(Ok), config).map(|e| e.unwrap()).collect();
+
+        // Channel 2 frames follow the private layout, which carries no selected signal
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], DecodedEvent::Message { channel: 1, .. }));
+    }
+
+    #[test]
+    fn test_signal_decoding_disabled() {
+        let db = test_signal_db();
+        let frames = vec![test_frame(0, 0x123, vec![0x10, 0x27])];
+        let config = DecoderConfig::new().with_signal_decoding(false);
+
+        let events = decode_frames(frames, &db, config);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], DecodedEvent::RawFrame { can_id: 0x123, .. }));
+    }
+
+    #[test]
+    fn test_cantp_reassembly() {
+        let db = test_signal_db();
+        let frames = vec![
+            test_frame(0, 0x7E8, vec![0x10, 0x08, 0x62, 0xF1, 0x90, 0x01, 0x02, 0x03]),
+            test_frame(0, 0x7E0, vec![0x30, 0x00, 0x00]),
+            test_frame(0, 0x7E8, vec![0x21, 0x04, 0x05, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA]),
+            test_frame(0, 0x7E0, vec![0x10, 0x08, 0x2E, 0xF1, 0x90, 0x01, 0x02, 0x03]),
+        ];
+        let config = DecoderConfig::new().add_cantp_pair(0x7E0, 0x7E8);
+
+        let events = decode_frames(frames, &db, config);
+        let cantp: Vec<_> = events
+            .iter()
+            .filter(|e| matches!(e, DecodedEvent::CanTpMessage { .. } | DecodedEvent::CanTpError { .. }))
+            .collect();
+        assert_eq!(cantp.len(), 2);
+        assert!(matches!(
+            cantp[0],
+            DecodedEvent::CanTpMessage { source_addr: 0x7E8, payload_length: 8, .. }
+        ));
+        // Incomplete transfer is reported when the log ends
+        assert!(matches!(cantp[1], DecodedEvent::CanTpError { source_addr: 0x7E0, .. }));
+    }
+
+    #[test]
+    fn test_uds_interpretation() {
+        let db = test_signal_db();
+        let frames = vec![
+            test_frame(0, 0x7E0, vec![0x03, 0x22, 0xF1, 0x90]),
+            test_frame(0, 0x7E8, vec![0x04, 0x62, 0xF1, 0x90, 0x01]),
+        ];
+
+        let config = DecoderConfig::new().add_cantp_pair(0x7E0, 0x7E8);
+        let events = decode_frames(frames.clone(), &db, config);
+        let uds: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                DecodedEvent::UdsMessage { uds, .. } => Some(uds),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(uds.len(), 2);
+        assert_eq!(uds[0].kind, crate::types::UdsMessageKind::Request);
+        assert_eq!(uds[1].data_identifiers, vec![0xF190]);
+        assert_eq!(uds[1].response_time_ms, Some(0.0));
+
+        let config = DecoderConfig::new().add_cantp_pair(0x7E0, 0x7E8).with_uds_decoding(false);
+        let events = decode_frames(frames, &db, config);
+        assert!(!events.iter().any(|e| matches!(e, DecodedEvent::UdsMessage { .. })));
+    }
+
+    #[test]
+    fn test_obd_decoding() {
+        let db = test_signal_db();
+        let frames = vec![
+            test_frame(0, 0x7DF, vec![0x02, 0x01, 0x0D]),
+            test_frame(0, 0x7E8, vec![0x03, 0x41, 0x0D, 0x32]),
+        ];
+        let config = DecoderConfig::new().add_cantp_pair(0x7DF, 0x7E8);
+
+        let events = decode_frames(frames, &db, config);
+        let obd: Vec<_> = events
+            .iter()
+            .filter(|e| matches!(e, DecodedEvent::Message { .. }))
+            .collect();
+        assert_eq!(obd.len(), 1);
+        match obd[0] {
+            DecodedEvent::Message { can_id, message_name, signals, .. } => {
+                assert_eq!(*can_id, 0x7E8);
+                assert_eq!(message_name.as_deref(), Some("OBD2_CurrentData"));
+                assert_eq!(&*signals[0].name, "VehicleSpeed");
+                assert_eq!(signals[0].value, crate::types::SignalValue::Integer(50));
+            }
+            _ => unreachable!(),
+        }
+        // OBD-II services are not reported as UDS
+        assert!(!events.iter().any(|e| matches!(e, DecodedEvent::UdsMessage { .. })));
+    }
+
+    #[test]
+    fn test_raw_frames_alongside_messages() {
+        let db = test_signal_db();
+        let frames = vec![
+            test_frame(0, 0x123, vec![0x10, 0x27]),
+            test_frame(0, 0x456, vec![0x00]),
+        ];
+        let config = DecoderConfig::new().with_raw_frames(true);
+
+        let events = decode_frames(frames, &db, config);
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], DecodedEvent::RawFrame { can_id: 0x123, .. }));
+        match &events[1] {
+            DecodedEvent::Message { signals, .. } => {
+                assert_eq!(signals[0].value, crate::types::SignalValue::Integer(10000));
+            }
+            other => panic!("Expected Message event, got {:?}", other),
+        }
+        // Unknown IDs are only emitted once
+        assert!(matches!(events[2], DecodedEvent::RawFrame { can_id: 0x456, .. }));
+    }
+
+    #[test]
+    fn test_j1939_decoding() {
+        let mut db = SignalDatabase::new();
+        for (id, name, size) in [(0x0CF00400u32, "EEC1", 8usize), (0x18FECA00, "DM1", 10)] {
+            let mut signal = SignalDefinition::test(&format!("{}_Byte8", name), 56, 8, ByteOrder::LittleEndian);
+            signal.max = 255.0;
+            let mut message = MessageDefinition::test(id, name, size, vec![signal]);
+            message.source = "j1939.dbc".to_string();
+            message.is_j1939 = true;
+            db.add_message(message);
+        }
+
+        let mut frames = vec![
+            // EEC1 from a different source address than in the DBC
+            test_frame(0, 0x0CF00417, vec![0, 0, 0, 0, 0, 0, 0, 42]),
+            // DM1 broadcast with BAM from SA 0x17
+            test_frame(0, 0x1CECFF17, vec![32, 10, 0, 2, 0xFF, 0xCA, 0xFE, 0x00]),
+            test_frame(0, 0x1CEBFF17, vec![1, 1, 2, 3, 4, 5, 6, 7]),
+            test_frame(0, 0x1CEBFF17, vec![2, 8, 9, 10, 0xFF, 0xFF, 0xFF, 0xFF]),
+        ];
+        for frame in &mut frames {
+            frame.is_extended = true;
+        }
+
+        let events = decode_frames(frames.clone(), &db, DecoderConfig::new().with_j1939_decoding(true));
+        match &events[0] {
+            DecodedEvent::Message { message_name, source_address, .. } => {
+                assert_eq!(message_name.as_deref(), Some("EEC1"));
+                assert_eq!(*source_address, Some(0x17));
+            }
+            other => panic!("Expected Message event, got {:?}", other),
+        }
+        assert!(matches!(
+            events[events.len() - 2],
+            DecodedEvent::J1939TpMessage { pgn: 0xFECA, source_address: 0x17, .. }
+        ));
+        match &events[events.len() - 1] {
+            DecodedEvent::Message { can_id, message_name, signals, .. } => {
+                assert_eq!(*can_id, 0x18FECA17);
+                assert_eq!(message_name.as_deref(), Some("DM1"));
+                assert_eq!(signals[0].value, crate::types::SignalValue::Integer(8));
+            }
+            other => panic!("Expected Message event, got {:?}", other),
+        }
+
+        // Off by default: transport protocol and address claim IDs on other buses stay plain frames
+        let mut address_claim = test_frame(0, 0x18EEFF17, vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+        address_claim.is_extended = true;
+        frames.push(address_claim);
+        let events = decode_frames(frames, &SignalDatabase::new(), DecoderConfig::new());
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            DecodedEvent::J1939TpMessage { .. } | DecodedEvent::J1939AddressClaim { .. }
+        )));
+    }
 
     #[test]
     fn test_decoder_creation() {
@@ -13,10 +13,18 @@
 //! - Type 115 and others: Unsupported types are silently skipped
 //!
 //! Most BLF files use type 86 for CAN-FD (with FD flag), so type 100 limitation rarely impacts usage.
+//!
+//! ## Timestamps
+//! Object timestamps are relative to the measurement start, in 10 µs or 1 ns
+//! units depending on the object header flags. Frames get absolute times from
+//! the measurement start `SYSTEMTIME` in the file statistics. That is the local
+//! time of the logging PC without a zone; [`BlfParser::parse`] takes it as UTC,
+//! [`BlfParser::parse_with_utc_offset`] applies a known offset.
 
-use crate::types::{CanFrame, DecoderError, Result};
-use ablf::{BlfFile, ObjectTypes};
-use std::collections::HashSet;
+use crate::types::{CanFrame, DecoderError, Result, Timestamp};
+use ablf::{BlfFile, ObjectHeader, ObjectTypes};
+use chrono::{FixedOffset, Offset, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
@@ -29,7 +37,27 @@ impl BlfParser {
     ///
     /// Opens the BLF file and validates its structure. Returns an iterator
     /// that yields CanFrame structs for all supported message types.
+    ///
+    /// The measurement start is taken as UTC; use
+    /// [`parse_with_utc_offset`](Self::parse_with_utc_offset) for files logged
+    /// in another time zone.
     pub fn parse(path: &Path) -> Result<BlfFrameIterator> {
+        Self::parse_with_utc_offset(path, Utc.fix())
+    }
+
+    /// Parse a BLF file whose measurement start was logged at `utc_offset`
+    ///
+    /// # Example
+    /// ```no_run
+    /// use can_log_decoder::formats::BlfParser;
+    /// use chrono::FixedOffset;
+    /// use std::path::Path;
+    ///
+    /// // Logged on a PC running Central European Summer Time
+    /// let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+    /// let frames = BlfParser::parse_with_utc_offset(Path::new("trace.blf"), cest).unwrap();
+    /// ```
+    pub fn parse_with_utc_offset(path: &Path, utc_offset: FixedOffset) -> Result<BlfFrameIterator> {
         log::info!("Parsing BLF file: {:?}", path);
 
         if !path.exists() {
@@ -60,20 +88,109 @@ impl BlfParser {
 
         log::info!("BLF file opened successfully");
 
+        let measurement_start_ns = blf
+            .file_stats
+            .measurement_start_time()
+            .and_then(|start| start.and_local_timezone(utc_offset).single())
+            .and_then(|start| start.timestamp_nanos_opt())
+            .and_then(|ns| u64::try_from(ns).ok());
+        match measurement_start_ns {
+            Some(start) => log::info!("Measurement start: {}", crate::types::timestamp_from_ns(start)),
+            None => log::warn!("BLF file has no measurement start time, timestamps are relative"),
+        }
+
         // Create the iterator from BlfFile
         let object_iter = blf.into_iter();
 
         Ok(BlfFrameIterator {
             objects: object_iter,
+            measurement_start_ns: measurement_start_ns.unwrap_or(0),
             skipped_types: HashSet::new(),
+            object_counts: HashMap::new(),
         })
     }
 }
 
+/// Object header flag: timestamp in 10 µs units (0x2 = 1 ns units)
+const OBJECT_FLAG_TIME_TEN_MICS: u32 = 0x0000_0001;
+
+/// Object type of log containers (unpacked by ablf, counted separately)
+const OBJECT_TYPE_LOG_CONTAINER: u32 = 10;
+
 /// Iterator over CAN frames from a BLF file
 pub struct BlfFrameIterator {
     objects: ablf::ObjectIterator<BufReader<File>>,
+    /// Measurement start in nanoseconds since epoch (0 if the file has none)
+    measurement_start_ns: u64,
     skipped_types: HashSet<u32>,
+    /// Objects read so far per object type
+    object_counts: HashMap<u32, u64>,
+}
+
+impl BlfFrameIterator {
+    /// Measurement start time from the file statistics (`None` if not recorded)
+    ///
+    /// BLF stores the local time of the logging PC without a time zone. It is
+    /// converted with the offset given to the parser, UTC for [`BlfParser::parse`].
+    pub fn measurement_start(&self) -> Option<Timestamp> {
+        (self.measurement_start_ns > 0).then(|| crate::types::timestamp_from_ns(self.measurement_start_ns))
+    }
+
+    /// Number of objects read so far per object type, log containers included
+    pub fn object_type_counts(&self) -> BTreeMap<u32, u64> {
+        let mut counts: BTreeMap<u32, u64> = self.object_counts.iter().map(|(&t, &n)| (t, n)).collect();
+        if self.objects.containers() > 0 {
+            counts.insert(OBJECT_TYPE_LOG_CONTAINER, self.objects.containers());
+        }
+        counts
+    }
+
+    /// Whether any zlib compressed log container was read so far
+    pub fn is_compressed(&self) -> bool {
+        self.objects.compressed_containers() > 0
+    }
+
+    /// Application that wrote the file and the BLF library version, e.g.
+    /// `CANoe 12.0.75 (BLF 4.7.1.0)`
+    pub fn writer(&self) -> String {
+        let stats = self.objects.file_stats();
+        let application = match stats.application_id {
+            1 => "CANalyzer".to_string(),
+            2 => "CANoe".to_string(),
+            3 => "CANstress".to_string(),
+            4 => "CANlog".to_string(),
+            5 => "CANape".to_string(),
+            6 => "CANcaseXL log".to_string(),
+            7 => "Vector Logger Configurator".to_string(),
+            id => format!("Application {}", id),
+        };
+        format!(
+            "{} {}.{}.{} (BLF {}.{}.{}.{})",
+            application,
+            stats.application_major,
+            stats.application_minor,
+            stats.application_build,
+            stats.api_major,
+            stats.api_minor,
+            stats.api_build,
+            stats.api_patch
+        )
+    }
+
+    /// Absolute frame time in nanoseconds from an object header
+    fn frame_time_ns(&self, header: &ObjectHeader) -> u64 {
+        self.measurement_start_ns + object_time_ns(header)
+    }
+}
+
+/// Object time since measurement start in nanoseconds
+fn object_time_ns(header: &ObjectHeader) -> u64 {
+    if header.flags & OBJECT_FLAG_TIME_TEN_MICS != 0 {
+        header.timestamp_ns.saturating_mul(10_000)
+    } else {
+        // 1 ns units, also assumed for headers without a unit flag
+        header.timestamp_ns
+    }
 }
 
 impl Iterator for BlfFrameIterator {
@@ -82,15 +199,19 @@ impl Iterator for BlfFrameIterator {
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             let obj = self.objects.next()?;
+            *self.object_counts.entry(obj.object_type).or_insert(0) += 1;
             match obj.data {
                 ObjectTypes::CanMessage86(msg) => {
+                    const CAN_MSG_EXT: u32 = 0x80000000;
+
                     // Extract CAN 2.0 or CAN-FD message (type 86)
                     return Some(Ok(CanFrame {
-                        timestamp_ns: msg.header.timestamp_ns,
+                        timestamp_ns: self.frame_time_ns(&msg.header),
+                        measurement_start_ns: self.measurement_start_ns,
                         channel: msg.channel as u8,
-                        can_id: msg.id,
+                        can_id: msg.id & 0x1FFFFFFF,
                         data: msg.data.clone(),
-                        is_extended: (msg.flags & 0x02) != 0, // Bit 1: Extended ID
+                        is_extended: (msg.id & CAN_MSG_EXT) != 0,
                         is_fd: (msg.flags & 0x80) != 0,       // Bit 7: CAN-FD frame
                         is_error_frame: false,
                         is_remote_frame: (msg.flags & 0x04) != 0, // Bit 2: Remote frame
@@ -104,7 +225,8 @@ impl Iterator for BlfFrameIterator {
                     let data = msg.data[..data_len].to_vec();
 
                     return Some(Ok(CanFrame {
-                        timestamp_ns: msg.header.timestamp_ns,
+                        timestamp_ns: self.frame_time_ns(&msg.header),
+                        measurement_start_ns: self.measurement_start_ns,
                         channel: msg.channel.saturating_sub(1) as u8,
                         can_id: msg.id & 0x1FFFFFFF,
                         data,
@@ -128,7 +250,8 @@ impl Iterator for BlfFrameIterator {
                     }
 
                     return Some(Ok(CanFrame {
-                        timestamp_ns: msg.header.timestamp_ns,
+                        timestamp_ns: self.frame_time_ns(&msg.header),
+                        measurement_start_ns: self.measurement_start_ns,
                         channel: msg.channel.saturating_sub(1),
                         can_id: msg.id & 0x1FFFFFFF,
                         data,
@@ -141,7 +264,8 @@ impl Iterator for BlfFrameIterator {
                 ObjectTypes::CanErrorExt73(err) => {
                     // Extract CAN error frame (type 73)
                     return Some(Ok(CanFrame {
-                        timestamp_ns: err.header.timestamp_ns,
+                        timestamp_ns: self.frame_time_ns(&err.header),
+                        measurement_start_ns: self.measurement_start_ns,
                         channel: err.channel as u8,
                         can_id: err.id,
                         data: err.data.to_vec(),
@@ -219,6 +343,98 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Minimal uncompressed BLF: file statistics plus CAN messages (type 86)
+    /// given as (object header flags, raw timestamp)
+    fn write_blf(measurement_start: [u16; 8], objects: &[(u32, u64)]) -> tempfile::NamedTempFile {
+        let mut blf = Vec::new();
+        blf.extend_from_slice(b"LOGG");
+        blf.extend_from_slice(&144u32.to_le_bytes()); // statistics size
+        blf.extend_from_slice(&[2, 12, 0, 75, 4, 7, 1, 0]); // CANoe 12.0.75, BLF 4.7.1.0
+        blf.extend_from_slice(&[0; 8 + 8 + 4 + 4]); // sizes, counts
+        measurement_start.iter().for_each(|v| blf.extend_from_slice(&v.to_le_bytes()));
+        blf.resize(144, 0); // last object time, reserved
+
+        for &(flags, timestamp) in objects {
+            blf.extend_from_slice(b"LOBJ");
+            blf.extend_from_slice(&32u16.to_le_bytes()); // header size
+            blf.extend_from_slice(&1u16.to_le_bytes()); // header version
+            blf.extend_from_slice(&56u32.to_le_bytes()); // object size
+            blf.extend_from_slice(&86u32.to_le_bytes()); // CAN_MESSAGE2
+            blf.extend_from_slice(&flags.to_le_bytes());
+            blf.extend_from_slice(&[0; 4]); // client index, version
+            blf.extend_from_slice(&timestamp.to_le_bytes());
+            blf.extend_from_slice(&[1, 0, 0, 8]); // channel, flags, DLC
+            blf.extend_from_slice(&0x123u32.to_le_bytes());
+            blf.extend_from_slice(&[0; 8 + 8]); // data, frame length, bit count, reserved
+        }
+
+        let mut file = tempfile::Builder::new().suffix(".blf").tempfile().unwrap();
+        std::io::Write::write_all(&mut file, &blf).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_absolute_timestamps() {
+        // SYSTEMTIME: year, month, day of week, day, hour, minute, second, milliseconds
+        let file = write_blf(
+            [2024, 3, 5, 15, 10, 30, 0, 500],
+            &[(OBJECT_FLAG_TIME_TEN_MICS, 100), (0x2, 2_000_000)],
+        );
+        let iterator = BlfParser::parse(file.path()).unwrap();
+        let start = iterator.measurement_start().unwrap();
+        assert_eq!(start.to_rfc3339(), "2024-03-15T10:30:00.500+00:00");
+
+        let frames: Vec<CanFrame> = iterator.map(|frame| frame.unwrap()).collect();
+        assert_eq!(frames.len(), 2);
+        // 100 × 10 µs and 2,000,000 × 1 ns after the measurement start
+        assert_eq!(frames[0].relative_timestamp_ns(), 1_000_000);
+        assert_eq!(frames[1].relative_time(), std::time::Duration::from_millis(2));
+        assert_eq!(frames[0].timestamp() - start, chrono::Duration::milliseconds(1));
+        assert_eq!(frames[1].measurement_start_ns, frames[0].measurement_start_ns);
+    }
+
+    #[test]
+    fn test_utc_offset() {
+        let file = write_blf([2024, 3, 5, 15, 10, 30, 0, 500], &[(OBJECT_FLAG_TIME_TEN_MICS, 100)]);
+        let cet = FixedOffset::east_opt(3600).unwrap();
+        let mut iterator = BlfParser::parse_with_utc_offset(file.path(), cet).unwrap();
+        // 10:30 local time is 09:30 UTC
+        assert_eq!(iterator.measurement_start().unwrap().to_rfc3339(), "2024-03-15T09:30:00.500+00:00");
+
+        let frame = iterator.next().unwrap().unwrap();
+        assert_eq!(frame.relative_timestamp_ns(), 1_000_000);
+    }
+
+    #[test]
+    fn test_missing_measurement_start() {
+        let file = write_blf([0; 8], &[(OBJECT_FLAG_TIME_TEN_MICS, 100)]);
+        let mut iterator = BlfParser::parse(file.path()).unwrap();
+        assert!(iterator.measurement_start().is_none());
+
+        let frame = iterator.next().unwrap().unwrap();
+        assert_eq!(frame.timestamp_ns, 1_000_000);
+        assert_eq!(frame.relative_timestamp_ns(), 1_000_000);
+    }
+
+    #[test]
+    fn test_file_info() {
+        let file = write_blf(
+            [2024, 3, 5, 15, 10, 30, 0, 500],
+            &[(OBJECT_FLAG_TIME_TEN_MICS, 100), (OBJECT_FLAG_TIME_TEN_MICS, 250_000)],
+        );
+        let info = crate::formats::LogFileInfo::from_file(file.path()).unwrap();
+        assert_eq!(info.writer, "CANoe 12.0.75 (BLF 4.7.1.0)");
+        assert_eq!(info.compressed, Some(false));
+        assert_eq!(info.start_time.unwrap().to_rfc3339(), "2024-03-15T10:30:00.500+00:00");
+        assert_eq!(info.end_time.unwrap().to_rfc3339(), "2024-03-15T10:30:03+00:00");
+        assert_eq!(info.duration, Some(std::time::Duration::from_millis(2500)));
+        assert_eq!(info.frame_count, 2);
+        // write_blf puts every frame on channel 1
+        assert_eq!(info.channels[&1].frames, 2);
+        assert!(!info.has_fd() && !info.has_extended());
+        assert_eq!(info.object_types, BTreeMap::from([(86, 2)]));
+    }
+
     #[test]
     fn test_parse_real_blf() {
         // Test with the actual example files
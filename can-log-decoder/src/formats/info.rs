@@ -0,0 +1,198 @@
+//! File-level metadata of log files
+//!
+//! [`LogFileInfo`] summarises a BLF or MF4 file without decoding any signals:
+//! measurement start and end, frame counts per channel, CAN-FD and extended ID
+//! presence, object types, compression and the program that wrote the file.
+//! Header fields come from the BLF file statistics or the MF4 header block,
+//! everything else from one pass over the frames.
+//!
+//! MF4 frames are not read: the mdflib wrapper does not extract CAN records
+//! yet, so only the record count of the CAN channel groups is reported.
+
+use super::{BlfParser, Mf4Parser};
+use crate::types::{CanFrame, DecoderError, Result, Timestamp};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Log file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Vector Binary Logging Format
+    Blf,
+    /// ASAM MDF4
+    Mf4,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Blf => write!(f, "BLF"),
+            LogFormat::Mf4 => write!(f, "MF4"),
+        }
+    }
+}
+
+/// Frame counts of one CAN channel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelInfo {
+    /// All frames, error frames included
+    pub frames: u64,
+    /// CAN-FD frames
+    pub fd_frames: u64,
+    /// Frames with a 29-bit identifier
+    pub extended_frames: u64,
+    /// Remote frames
+    pub remote_frames: u64,
+    /// Error frames
+    pub error_frames: u64,
+}
+
+/// Metadata of a log file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFileInfo {
+    /// File format
+    pub format: LogFormat,
+    /// Program that wrote the file and its format version
+    pub writer: String,
+    /// Whether the data is compressed (`None` if the format does not tell)
+    pub compressed: Option<bool>,
+    /// Measurement start from the file header
+    pub start_time: Option<Timestamp>,
+    /// Time of the last frame (`None` without a measurement start)
+    pub end_time: Option<Timestamp>,
+    /// Time from the measurement start to the last frame (`None` if the frames were not read)
+    pub duration: Option<Duration>,
+    /// Number of CAN frames (MF4: records of the CAN channel groups)
+    pub frame_count: u64,
+    /// Frame counts per channel (empty if the frames were not read)
+    pub channels: BTreeMap<u8, ChannelInfo>,
+    /// Number of objects per BLF object type (empty for MF4)
+    pub object_types: BTreeMap<u32, u64>,
+}
+
+impl LogFileInfo {
+    /// Read the metadata of a BLF or MF4 file (format chosen by extension)
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let extension = path.extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_lowercase());
+
+        match extension.as_deref() {
+            Some("blf") => Self::from_blf(path),
+            Some("mf4") | Some("mdf") => Self::from_mf4(path),
+            _ => Err(DecoderError::LogParseError(format!("Unsupported file format: {:?}", extension))),
+        }
+    }
+
+    fn from_blf(path: &Path) -> Result<Self> {
+        let mut frames = BlfParser::parse(path)?;
+        let mut info = Self::new(LogFormat::Blf, frames.writer(), frames.measurement_start());
+        info.duration = Some(Duration::ZERO);
+        for frame in frames.by_ref() {
+            info.record(&frame?);
+        }
+        // Containers are only seen while iterating
+        info.compressed = Some(frames.is_compressed());
+        info.object_types = frames.object_type_counts();
+        Ok(info)
+    }
+
+    fn from_mf4(path: &Path) -> Result<Self> {
+        let frames = Mf4Parser::parse(path)?;
+        let mut info = Self::new(LogFormat::Mf4, frames.writer(), frames.measurement_start());
+        info.frame_count = frames.sample_count();
+        Ok(info)
+    }
+
+    fn new(format: LogFormat, writer: String, start_time: Option<Timestamp>) -> Self {
+        Self {
+            format,
+            writer,
+            compressed: None,
+            start_time,
+            end_time: None,
+            duration: None,
+            frame_count: 0,
+            channels: BTreeMap::new(),
+            object_types: BTreeMap::new(),
+        }
+    }
+
+    fn record(&mut self, frame: &CanFrame) {
+        self.frame_count += 1;
+        let relative = frame.relative_time();
+        if self.duration.is_none_or(|duration| relative >= duration) {
+            self.duration = Some(relative);
+            if self.start_time.is_some() {
+                self.end_time = Some(frame.timestamp());
+            }
+        }
+
+        let channel = self.channels.entry(frame.channel).or_default();
+        channel.frames += 1;
+        channel.fd_frames += frame.is_fd as u64;
+        channel.extended_frames += frame.is_extended as u64;
+        channel.remote_frames += frame.is_remote_frame as u64;
+        channel.error_frames += frame.is_error_frame as u64;
+    }
+
+    /// Whether any channel carries CAN-FD frames
+    pub fn has_fd(&self) -> bool {
+        self.channels.values().any(|channel| channel.fd_frames > 0)
+    }
+
+    /// Whether any channel carries frames with 29-bit identifiers
+    pub fn has_extended(&self) -> bool {
+        self.channels.values().any(|channel| channel.extended_frames > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(channel: u8, timestamp_ns: u64, is_extended: bool, is_fd: bool) -> CanFrame {
+        CanFrame {
+            timestamp_ns,
+            measurement_start_ns: 1_000_000_000,
+            channel,
+            can_id: 0x123,
+            data: vec![0; 8],
+            is_extended,
+            is_fd,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    #[test]
+    fn test_record_frames() {
+        let start = crate::types::timestamp_from_ns(1_000_000_000);
+        let mut info = LogFileInfo::new(LogFormat::Blf, "CANoe 12.0.75 (BLF 4.7.1.0)".to_string(), Some(start));
+        info.record(&frame(0, 1_500_000_000, false, false));
+        info.record(&frame(1, 3_000_000_000, true, true));
+        info.record(&frame(1, 2_000_000_000, false, true));
+
+        assert_eq!(info.frame_count, 3);
+        assert_eq!(info.duration, Some(Duration::from_secs(2)));
+        assert_eq!(info.end_time, Some(crate::types::timestamp_from_ns(3_000_000_000)));
+        assert_eq!(info.channels[&0].frames, 1);
+        assert_eq!(
+            info.channels[&1],
+            ChannelInfo { frames: 2, fd_frames: 2, extended_frames: 1, ..Default::default() }
+        );
+        assert!(info.has_fd() && info.has_extended());
+
+        // Relative timestamps only: no absolute end time
+        let mut info = LogFileInfo::new(LogFormat::Mf4, String::new(), None);
+        info.record(&frame(0, 1_500_000_000, false, false));
+        assert_eq!((info.end_time, info.duration), (None, Some(Duration::from_millis(500))));
+        assert!(!info.has_fd() && !info.has_extended());
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        assert!(LogFileInfo::from_file(Path::new("trace.asc")).is_err());
+    }
+}
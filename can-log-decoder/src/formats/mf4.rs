@@ -6,7 +6,7 @@
 //!
 //! This implementation uses FFI bindings to the mdflib C++ library.
 
-use crate::types::{CanFrame, DecoderError, Result};
+use crate::types::{CanFrame, DecoderError, Result, Timestamp};
 use std::ffi::CString;
 use std::path::Path;
 
@@ -52,6 +52,17 @@ impl Mf4Parser {
 
         log::info!("MF4 file opened successfully");
 
+        // Header information is optional, a failure only loses the metadata
+        let mut file_info = MdfFileInfo {
+            start_time_ns: 0,
+            version: [0; 16],
+            program_id: [0; 16],
+        };
+        let info_error = unsafe { mdf_get_file_info(reader, &mut file_info) };
+        if info_error != MdfError::Ok {
+            log::warn!("Failed to read MF4 header: {} ({:?})", get_last_error(), info_error);
+        }
+
         // Create CAN iterator
         let mut iter_error = MdfError::Ok;
         let iterator = unsafe { mdf_create_can_iterator(reader, &mut iter_error) };
@@ -71,6 +82,9 @@ impl Mf4Parser {
             reader,
             iterator,
             finished: false,
+            measurement_start_ns: file_info.start_time_ns,
+            version: fixed_c_string(&file_info.version),
+            program_id: fixed_c_string(&file_info.program_id),
         })
     }
 }
@@ -80,6 +94,28 @@ pub struct Mf4FrameIterator {
     reader: MdfReaderHandle,
     iterator: MdfIteratorHandle,
     finished: bool,
+    /// Measurement start from the header in nanoseconds since epoch (0 if unknown)
+    measurement_start_ns: u64,
+    version: String,
+    program_id: String,
+}
+
+impl Mf4FrameIterator {
+    /// Measurement start time from the file header (`None` if not recorded)
+    pub fn measurement_start(&self) -> Option<Timestamp> {
+        (self.measurement_start_ns > 0).then(|| crate::types::timestamp_from_ns(self.measurement_start_ns))
+    }
+
+    /// Program that wrote the file and the MDF version, e.g. `CANape (MDF 4.10)`
+    pub fn writer(&self) -> String {
+        let program = if self.program_id.is_empty() { "Unknown program" } else { &self.program_id };
+        format!("{} (MDF {})", program, self.version)
+    }
+
+    /// Number of CAN records announced by the channel group headers
+    pub fn sample_count(&self) -> u64 {
+        unsafe { mdf_iterator_sample_count(self.iterator) }
+    }
 }
 
 impl Iterator for Mf4FrameIterator {
@@ -110,8 +146,10 @@ impl Iterator for Mf4FrameIterator {
                 let mut data = Vec::with_capacity(mdf_frame.data_length as usize);
                 data.extend_from_slice(&mdf_frame.data[..mdf_frame.data_length as usize]);
 
+                // MDF time channels are relative to the header start time
                 Some(Ok(CanFrame {
-                    timestamp_ns: mdf_frame.timestamp_ns,
+                    timestamp_ns: self.measurement_start_ns.saturating_add(mdf_frame.timestamp_ns),
+                    measurement_start_ns: self.measurement_start_ns,
                     channel: mdf_frame.channel,
                     can_id: mdf_frame.can_id,
                     data,
@@ -3,7 +3,7 @@
 //! This module provides safe Rust bindings to the C API wrapper
 //! that interfaces with the mdflib C++ library.
 
-use std::ffi::{CStr, CString};
+use std::ffi::CStr;
 use std::os::raw::{c_char, c_void};
 
 #[repr(C)]
@@ -19,8 +19,16 @@ pub struct MdfCanFrame {
     pub is_remote_frame: u8,
 }
 
+#[repr(C)]
+pub struct MdfFileInfo {
+    pub start_time_ns: u64,
+    pub version: [c_char; 16],
+    pub program_id: [c_char; 16],
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)] // Variants are produced by the C side of the FFI boundary
 pub enum MdfError {
     Ok = 0,
     OpenFailed = 1,
@@ -39,15 +47,23 @@ pub type MdfIteratorHandle = *mut c_void;
 extern "C" {
     pub fn mdf_open(filename: *const c_char, error: *mut MdfError) -> MdfReaderHandle;
     pub fn mdf_close(reader: MdfReaderHandle);
+    pub fn mdf_get_file_info(reader: MdfReaderHandle, info: *mut MdfFileInfo) -> MdfError;
     pub fn mdf_create_can_iterator(
         reader: MdfReaderHandle,
         error: *mut MdfError,
     ) -> MdfIteratorHandle;
     pub fn mdf_iterator_next(iterator: MdfIteratorHandle, frame: *mut MdfCanFrame) -> MdfError;
+    pub fn mdf_iterator_sample_count(iterator: MdfIteratorHandle) -> u64;
     pub fn mdf_iterator_free(iterator: MdfIteratorHandle);
     pub fn mdf_get_error_message() -> *const c_char;
 }
 
+/// Convert a NUL terminated fixed-size C string field
+pub fn fixed_c_string(field: &[c_char]) -> String {
+    let bytes: Vec<u8> = field.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    String::from_utf8_lossy(&bytes).trim().to_string()
+}
+
 /// Safe wrapper around mdf_get_error_message
 pub fn get_last_error() -> String {
     unsafe {
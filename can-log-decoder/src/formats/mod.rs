@@ -7,11 +7,13 @@ use crate::types::{CanFrame, Result};
 use std::path::Path;
 
 pub mod blf;
+pub mod info;
 pub mod mf4;
 mod mf4_ffi;  // FFI bindings for mdflib (private module)
 
 // Re-export parser types
 pub use blf::{BlfParser, BlfFrameIterator};
+pub use info::{ChannelInfo, LogFileInfo, LogFormat};
 pub use mf4::{Mf4Parser, Mf4FrameIterator};
 
 /// Common trait for all log file parsers
@@ -0,0 +1,198 @@
+//! SAE J1939 support
+//!
+//! Helpers for splitting 29-bit identifiers into priority, PGN and addresses,
+//! address claim tracking (PGN 60928) and transport protocol reassembly
+//! (BAM, CMDT and ETP, see [`transport`]).
+
+pub(crate) mod transport;
+
+use crate::types::{CanFrame, DecodedEvent, J1939Name};
+use std::collections::HashMap;
+use transport::J1939TransportReassembler;
+
+/// Request PGN (59904)
+pub const PGN_REQUEST: u32 = 0xEA00;
+/// Address Claimed PGN (60928)
+pub const PGN_ADDRESS_CLAIMED: u32 = 0xEE00;
+/// Transport protocol connection management PGN (60416)
+pub const PGN_TP_CM: u32 = 0xEC00;
+/// Transport protocol data transfer PGN (60160)
+pub const PGN_TP_DT: u32 = 0xEB00;
+/// Extended transport protocol connection management PGN (51200)
+pub const PGN_ETP_CM: u32 = 0xC800;
+/// Extended transport protocol data transfer PGN (50944)
+pub const PGN_ETP_DT: u32 = 0xC700;
+
+/// Global (broadcast) destination address
+pub const GLOBAL_ADDRESS: u8 = 0xFF;
+/// Null address used by "cannot claim address" messages
+pub const NULL_ADDRESS: u8 = 0xFE;
+
+/// PDU format values below 240 are PDU1 (destination specific)
+const PDU2_THRESHOLD: u8 = 240;
+
+/// Extract the parameter group number from a 29-bit CAN ID
+///
+/// For PDU1 messages the PDU specific byte is a destination address and is
+/// not part of the PGN.
+pub fn pgn_from_id(can_id: u32) -> u32 {
+    let pgn = (can_id >> 8) & 0x3FFFF;
+    if pdu_format(can_id) < PDU2_THRESHOLD {
+        pgn & 0x3FF00
+    } else {
+        pgn
+    }
+}
+
+/// Source address (lowest byte of the CAN ID)
+pub fn source_address(can_id: u32) -> u8 {
+    can_id as u8
+}
+
+/// Destination address of a PDU1 message, `None` for PDU2 broadcasts
+pub fn destination_address(can_id: u32) -> Option<u8> {
+    (pdu_format(can_id) < PDU2_THRESHOLD).then_some((can_id >> 8) as u8)
+}
+
+/// Message priority (0 = highest, 7 = lowest)
+pub fn priority(can_id: u32) -> u8 {
+    ((can_id >> 26) & 0x07) as u8
+}
+
+/// Build a 29-bit CAN ID; `destination` is ignored for PDU2 PGNs
+pub fn build_id(priority: u8, pgn: u32, destination: u8, source: u8) -> u32 {
+    let mut id = ((priority as u32 & 0x07) << 26) | ((pgn & 0x3FFFF) << 8) | source as u32;
+    if pdu_format(id) < PDU2_THRESHOLD {
+        id = (id & !0xFF00) | ((destination as u32) << 8);
+    }
+    id
+}
+
+fn pdu_format(can_id: u32) -> u8 {
+    (can_id >> 16) as u8
+}
+
+/// Stateful J1939 processing: transport protocol sessions and address claims
+pub(crate) struct J1939Decoder {
+    transport: J1939TransportReassembler,
+    /// Current NAME per (channel, source address)
+    claims: HashMap<(u8, u8), J1939Name>,
+}
+
+impl J1939Decoder {
+    pub fn new() -> Self {
+        Self {
+            transport: J1939TransportReassembler::new(),
+            claims: HashMap::new(),
+        }
+    }
+
+    /// Feed a frame; returns reassembled transport messages and address claims
+    pub fn process_frame(&mut self, frame: &CanFrame) -> Option<DecodedEvent> {
+        if !frame.is_extended && frame.can_id <= 0x7FF {
+            return None;
+        }
+
+        match pgn_from_id(frame.can_id) {
+            PGN_ADDRESS_CLAIMED => self.process_address_claim(frame),
+            PGN_TP_CM | PGN_TP_DT | PGN_ETP_CM | PGN_ETP_DT => self.transport.process_frame(frame),
+            _ => None,
+        }
+    }
+
+    fn process_address_claim(&mut self, frame: &CanFrame) -> Option<DecodedEvent> {
+        let name = J1939Name::from_bytes(&frame.data)?;
+        let source = source_address(frame.can_id);
+
+        let previous_name = if source == NULL_ADDRESS {
+            None
+        } else {
+            // A NAME owns a single address: drop its claim on any other address
+            self.claims
+                .retain(|&(channel, address), owner| {
+                    channel != frame.channel || address == source || *owner != name
+                });
+            self.claims
+                .insert((frame.channel, source), name)
+                .filter(|previous| *previous != name)
+        };
+
+        log::debug!(
+            "J1939 address claim: channel {} SA 0x{:02X} NAME {}",
+            frame.channel,
+            source,
+            name
+        );
+
+        Some(DecodedEvent::J1939AddressClaim {
+            timestamp: frame.timestamp(),
+            channel: frame.channel,
+            source_address: source,
+            name,
+            previous_name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(can_id: u32, data: Vec<u8>) -> CanFrame {
+        CanFrame {
+            timestamp_ns: 0,
+            measurement_start_ns: 0,
+            channel: 0,
+            can_id,
+            data,
+            is_extended: true,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    #[test]
+    fn test_id_helpers() {
+        // EEC1, PDU2
+        assert_eq!(pgn_from_id(0x0CF00400), 0xF004);
+        assert_eq!(source_address(0x0CF00400), 0x00);
+        assert_eq!(destination_address(0x0CF00400), None);
+        assert_eq!(priority(0x0CF00400), 3);
+
+        // TP.CM from 0xF9 to 0x00, PDU1
+        assert_eq!(pgn_from_id(0x1CEC00F9), PGN_TP_CM);
+        assert_eq!(destination_address(0x1CEC00F9), Some(0x00));
+        assert_eq!(build_id(7, PGN_TP_CM, 0x00, 0xF9), 0x1CEC00F9);
+        assert_eq!(build_id(3, 0xF004, 0x55, 0x00), 0x0CF00400);
+    }
+
+    #[test]
+    fn test_address_claim_tracking() {
+        let mut decoder = J1939Decoder::new();
+        let name_a = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80];
+        let name_b = [0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80];
+
+        let Some(DecodedEvent::J1939AddressClaim { source_address, name, previous_name, .. }) =
+            decoder.process_frame(&frame(0x18EEFF80, name_a.to_vec()))
+        else {
+            panic!("expected address claim");
+        };
+        assert_eq!(source_address, 0x80);
+        assert_eq!(name.identity_number(), 1);
+        assert_eq!(previous_name, None);
+
+        // Another controller takes over 0x80
+        let Some(DecodedEvent::J1939AddressClaim { previous_name, .. }) =
+            decoder.process_frame(&frame(0x18EEFF80, name_b.to_vec()))
+        else {
+            panic!("expected address claim");
+        };
+        assert_eq!(previous_name, Some(J1939Name::from_bytes(&name_a).unwrap()));
+
+        // The first controller moves to 0x81; 0x80 stays with name_b
+        decoder.process_frame(&frame(0x18EEFF81, name_a.to_vec()));
+        assert_eq!(decoder.claims.len(), 2);
+        assert_eq!(decoder.claims[&(0, 0x80)].identity_number(), 2);
+    }
+}
@@ -0,0 +1,341 @@
+//! J1939 transport protocol reassembly (SAE J1939-21 / J1939-22)
+//!
+//! Reassembles multi-packet messages sent with
+//! - BAM: broadcast announce, TP.CM (0xEC00) + TP.DT (0xEB00) to 0xFF
+//! - CMDT: RTS/CTS connection mode on the same PGNs
+//! - ETP: extended transport (> 1785 bytes), ETP.CM (0xC800) + ETP.DT (0xC700)
+//!
+//! Retransmissions requested with CTS are accepted; packets that skip ahead
+//! abort the session. Aborted, timed-out and incomplete sessions are dropped.
+
+use super::{destination_address, pgn_from_id, source_address, GLOBAL_ADDRESS};
+use super::{PGN_ETP_CM, PGN_ETP_DT, PGN_TP_CM, PGN_TP_DT};
+use crate::types::{CanFrame, DecodedEvent, Timestamp};
+use std::collections::HashMap;
+
+// Connection management control bytes
+const TP_CM_RTS: u8 = 16;
+const TP_CM_CTS: u8 = 17;
+const TP_CM_BAM: u8 = 32;
+const ETP_CM_RTS: u8 = 20;
+const ETP_CM_CTS: u8 = 21;
+const ETP_CM_DPO: u8 = 22;
+const CM_ABORT: u8 = 255;
+
+/// Payload bytes per data transfer packet
+const BYTES_PER_PACKET: usize = 7;
+/// Largest TP message; bigger messages use ETP
+const TP_MAX_SIZE: usize = 1785;
+/// Largest ETP message (0xFFFFFF packets)
+const ETP_MAX_SIZE: usize = 117_440_505;
+/// Longest gap between frames of a session (T2/T3 = 1250 ms)
+const SESSION_TIMEOUT_NS: u64 = 1_250_000_000;
+
+/// Session key: (channel, originator address, destination address, is ETP)
+type SessionKey = (u8, u8, u8, bool);
+
+struct Session {
+    /// Timestamp of the RTS/BAM frame
+    timestamp: Timestamp,
+    pgn: u32,
+    size: usize,
+    total_packets: u32,
+    /// Next packet number expected (1-based, absolute for ETP)
+    next_packet: u32,
+    /// ETP data packet offset from the last DPO
+    packet_offset: u32,
+    data: Vec<u8>,
+    last_frame_ns: u64,
+}
+
+/// Reassembles J1939 transport protocol sessions
+pub(crate) struct J1939TransportReassembler {
+    sessions: HashMap<SessionKey, Session>,
+}
+
+impl J1939TransportReassembler {
+    pub fn new() -> Self {
+        Self {
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// Feed a TP/ETP frame; returns the message when its last packet arrives
+    pub fn process_frame(&mut self, frame: &CanFrame) -> Option<DecodedEvent> {
+        let source = source_address(frame.can_id);
+        let destination = destination_address(frame.can_id).unwrap_or(GLOBAL_ADDRESS);
+
+        match pgn_from_id(frame.can_id) {
+            PGN_TP_CM => {
+                self.process_connection_management(frame, source, destination, false);
+                None
+            }
+            PGN_ETP_CM => {
+                self.process_connection_management(frame, source, destination, true);
+                None
+            }
+            PGN_TP_DT => self.process_data_transfer(frame, (frame.channel, source, destination, false)),
+            PGN_ETP_DT => self.process_data_transfer(frame, (frame.channel, source, destination, true)),
+            _ => None,
+        }
+    }
+
+    fn process_connection_management(
+        &mut self,
+        frame: &CanFrame,
+        source: u8,
+        destination: u8,
+        extended: bool,
+    ) {
+        let data = &frame.data;
+        if data.len() < 8 {
+            log::debug!("J1939 TP.CM frame too short (ID 0x{:X})", frame.can_id);
+            return;
+        }
+        let pgn = u32::from_le_bytes([data[5], data[6], data[7], 0]);
+        let key = (frame.channel, source, destination, extended);
+        // CTS/Abort from the receiver refer to the session in the other direction
+        let reverse_key = (frame.channel, destination, source, extended);
+
+        match (data[0], extended) {
+            (TP_CM_RTS, false) | (TP_CM_BAM, false) => {
+                let size = u16::from_le_bytes([data[1], data[2]]) as usize;
+                self.start_session(frame, key, pgn, size, data[3] as u32, TP_MAX_SIZE);
+            }
+            (ETP_CM_RTS, true) => {
+                let size = u32::from_le_bytes([data[1], data[2], data[3], data[4]]) as usize;
+                let packets = size.div_ceil(BYTES_PER_PACKET) as u32;
+                self.start_session(frame, key, pgn, size, packets, ETP_MAX_SIZE);
+            }
+            (ETP_CM_DPO, true) => {
+                if let Some(session) = self.sessions.get_mut(&key) {
+                    session.packet_offset = u32::from_le_bytes([data[2], data[3], data[4], 0]);
+                    session.last_frame_ns = frame.timestamp_ns;
+                }
+            }
+            (TP_CM_CTS, false) | (ETP_CM_CTS, true) => {
+                // Keeps the connection alive while the receiver holds it open
+                if let Some(session) = self.sessions.get_mut(&reverse_key) {
+                    session.last_frame_ns = frame.timestamp_ns;
+                }
+            }
+            (CM_ABORT, _) => {
+                let reason = data[1];
+                if self.sessions.remove(&key).or_else(|| self.sessions.remove(&reverse_key)).is_some() {
+                    log::debug!(
+                        "J1939 transport session for PGN 0x{:X} aborted (reason {})",
+                        pgn,
+                        reason
+                    );
+                }
+            }
+            // End of message acknowledgement and reserved control bytes
+            _ => {}
+        }
+    }
+
+    fn start_session(
+        &mut self,
+        frame: &CanFrame,
+        key: SessionKey,
+        pgn: u32,
+        size: usize,
+        announced_packets: u32,
+        max_size: usize,
+    ) {
+        let total_packets = size.div_ceil(BYTES_PER_PACKET) as u32;
+        if size == 0 || size > max_size || announced_packets < total_packets {
+            log::debug!(
+                "Invalid J1939 transport announcement for PGN 0x{:X}: {} bytes in {} packets",
+                pgn,
+                size,
+                announced_packets
+            );
+            return;
+        }
+
+        let previous = self.sessions.insert(
+            key,
+            Session {
+                timestamp: frame.timestamp(),
+                pgn,
+                size,
+                total_packets,
+                next_packet: 1,
+                packet_offset: 0,
+                data: Vec::with_capacity(size.min(TP_MAX_SIZE)),
+                last_frame_ns: frame.timestamp_ns,
+            },
+        );
+        if let Some(previous) = previous {
+            log::debug!(
+                "J1939 transport session for PGN 0x{:X} interrupted after {} packets",
+                previous.pgn,
+                previous.next_packet - 1
+            );
+        }
+    }
+
+    fn process_data_transfer(&mut self, frame: &CanFrame, key: SessionKey) -> Option<DecodedEvent> {
+        let (&sequence, packet) = frame.data.split_first()?;
+        let session = self.sessions.get_mut(&key)?;
+
+        if frame.timestamp_ns.saturating_sub(session.last_frame_ns) > SESSION_TIMEOUT_NS {
+            log::debug!("J1939 transport session for PGN 0x{:X} timed out", session.pgn);
+            self.sessions.remove(&key);
+            return None;
+        }
+        session.last_frame_ns = frame.timestamp_ns;
+
+        let packet_number = session.packet_offset + sequence as u32;
+        if sequence == 0 || packet_number > session.next_packet {
+            log::debug!(
+                "J1939 transport sequence error for PGN 0x{:X}: expected packet {}, got {}",
+                session.pgn,
+                session.next_packet,
+                packet_number
+            );
+            self.sessions.remove(&key);
+            return None;
+        }
+
+        // Packets below `next_packet` are retransmissions and overwrite earlier data
+        let start = (packet_number as usize - 1) * BYTES_PER_PACKET;
+        let end = (start + BYTES_PER_PACKET).min(session.size);
+        let bytes = &packet[..packet.len().min(end - start)];
+        if session.data.len() < start + bytes.len() {
+            session.data.resize(start + bytes.len(), 0);
+        }
+        session.data[start..start + bytes.len()].copy_from_slice(bytes);
+
+        if packet_number == session.next_packet {
+            session.next_packet += 1;
+        }
+        if session.next_packet <= session.total_packets {
+            return None;
+        }
+
+        let session = self.sessions.remove(&key)?;
+        if session.data.len() < session.size {
+            log::debug!("J1939 transport packets for PGN 0x{:X} too short", session.pgn);
+            return None;
+        }
+
+        let (channel, source_address, destination_address, _) = key;
+        Some(DecodedEvent::J1939TpMessage {
+            timestamp: session.timestamp,
+            channel,
+            pgn: session.pgn,
+            source_address,
+            destination_address,
+            payload: session.data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(timestamp_ns: u64, can_id: u32, data: &[u8]) -> CanFrame {
+        CanFrame {
+            timestamp_ns,
+            measurement_start_ns: 0,
+            channel: 0,
+            can_id,
+            data: data.to_vec(),
+            is_extended: true,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        }
+    }
+
+    fn payload(event: Option<DecodedEvent>) -> (u32, u8, u8, Vec<u8>) {
+        match event {
+            Some(DecodedEvent::J1939TpMessage {
+                pgn,
+                source_address,
+                destination_address,
+                payload,
+                ..
+            }) => (pgn, source_address, destination_address, payload),
+            other => panic!("expected J1939TpMessage, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bam_reassembly() {
+        let mut tp = J1939TransportReassembler::new();
+        // DM1 (PGN 0xFECA), 10 bytes in 2 packets from SA 0x00
+        assert!(tp
+            .process_frame(&frame(0, 0x1CECFF00, &[32, 10, 0, 2, 0xFF, 0xCA, 0xFE, 0x00]))
+            .is_none());
+        assert!(tp
+            .process_frame(&frame(50_000_000, 0x1CEBFF00, &[1, 1, 2, 3, 4, 5, 6, 7]))
+            .is_none());
+        let (pgn, sa, da, data) = payload(
+            tp.process_frame(&frame(100_000_000, 0x1CEBFF00, &[2, 8, 9, 10, 0xFF, 0xFF, 0xFF, 0xFF])),
+        );
+        assert_eq!((pgn, sa, da), (0xFECA, 0x00, GLOBAL_ADDRESS));
+        assert_eq!(data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_cmdt_with_retransmission_and_abort() {
+        let mut tp = J1939TransportReassembler::new();
+        // RTS from 0xF9 to 0x00: 9 bytes, 2 packets, PGN 0xEF00
+        tp.process_frame(&frame(0, 0x1CEC00F9, &[16, 9, 0, 2, 0xFF, 0x00, 0xEF, 0x00]));
+        tp.process_frame(&frame(1_000_000, 0x1CECF900, &[17, 2, 1, 0xFF, 0xFF, 0x00, 0xEF, 0x00]));
+        tp.process_frame(&frame(2_000_000, 0x1CEB00F9, &[1, 0, 0, 0, 0, 0, 0, 0]));
+        // Receiver asks for packet 1 again
+        tp.process_frame(&frame(3_000_000, 0x1CECF900, &[17, 2, 1, 0xFF, 0xFF, 0x00, 0xEF, 0x00]));
+        tp.process_frame(&frame(4_000_000, 0x1CEB00F9, &[1, 1, 2, 3, 4, 5, 6, 7]));
+        let (pgn, sa, da, data) =
+            payload(tp.process_frame(&frame(5_000_000, 0x1CEB00F9, &[2, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF])));
+        assert_eq!((pgn, sa, da), (0xEF00, 0xF9, 0x00));
+        assert_eq!(data, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        // Abort from the receiver discards the session
+        tp.process_frame(&frame(6_000_000, 0x1CEC00F9, &[16, 9, 0, 2, 0xFF, 0x00, 0xEF, 0x00]));
+        tp.process_frame(&frame(7_000_000, 0x1CECF900, &[255, 1, 0xFF, 0xFF, 0xFF, 0x00, 0xEF, 0x00]));
+        tp.process_frame(&frame(8_000_000, 0x1CEB00F9, &[1, 1, 2, 3, 4, 5, 6, 7]));
+        assert!(tp
+            .process_frame(&frame(9_000_000, 0x1CEB00F9, &[2, 8, 9, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_etp_with_data_packet_offset() {
+        let mut tp = J1939TransportReassembler::new();
+        let size = 1800usize;
+        let packets = size.div_ceil(BYTES_PER_PACKET) as u32; // 258
+        let size_bytes = (size as u32).to_le_bytes();
+        tp.process_frame(&frame(
+            0,
+            0x1CC800F9,
+            &[20, size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3], 0x00, 0xDA, 0x00],
+        ));
+
+        let mut result = None;
+        let mut packet = 1u32;
+        // Two DPO windows: 255 packets, then the remaining 3
+        for offset in [0u32, 255] {
+            let count = (packets - offset).min(255);
+            let o = offset.to_le_bytes();
+            tp.process_frame(&frame(0, 0x1CC800F9, &[22, count as u8, o[0], o[1], o[2], 0x00, 0xDA, 0x00]));
+            for sequence in 1..=count {
+                let mut data = vec![sequence as u8];
+                data.extend(std::iter::repeat_n(packet as u8, 7));
+                result = tp.process_frame(&frame(0, 0x1CC700F9, &data));
+                packet += 1;
+            }
+        }
+
+        let (pgn, _, da, data) = payload(result);
+        assert_eq!((pgn, da), (0xDA00, 0x00));
+        assert_eq!(data.len(), size);
+        assert_eq!(data[255 * 7], 0); // packet 256 wraps to 0 as u8
+        assert_eq!(data[size - 1], (packets as u8));
+    }
+}
@@ -9,7 +9,10 @@
 //! - Parses log files and emits a stream of decoded events
 //! - Supports DBC and ARXML signal definitions
 //! - Handles multiplexed signals and AUTOSAR container PDUs
+//! - Encodes signal values back into frame bytes ([`MessageEncoder`])
 //! - Reconstructs CAN-TP (ISO-TP) multi-frame messages
+//! - Interprets CAN-TP payloads as UDS (ISO 14229) services and OBD-II (SAE J1979) responses
+//! - Matches SAE J1939 parameter groups by PGN and reassembles J1939 transport protocol messages
 //!
 //! The library does NOT:
 //! - Track signal value changes (old→new)
@@ -56,17 +59,24 @@ pub mod decoder;
 pub mod types;
 
 // Re-export main types for convenience
-pub use config::{CanTpPair, DecoderConfig};
-pub use decoder::{DatabaseStats, Decoder};
+pub use config::{CanTpAddressing, CanTpPair, DecoderConfig, DetectedCanTpPair};
+pub use decoder::{DatabaseStats, Decoder, ValidationReport};
+pub use message_encoder::{EncodeValue, MessageEncoder};
+pub use uds::descriptions::UdsDescriptions;
 pub use types::{
-    ContainedMessage, ContainedPdu, ContainerType, DecodedEvent, DecodedSignal,
-    DecoderError, Result, SignalValue, Timestamp,
+    CanTpErrorKind, ContainedMessage, ContainedPdu, ContainerType, DecodedEvent, DecodedSignal,
+    DecoderError, J1939Name, ParentContainer, Result, SecuredPduInfo, SignalValue, Timestamp,
+    UdsDtc, UdsMessage, UdsMessageKind,
 };
 
 // Internal modules (not exposed in public API)
 pub mod formats;  // Made public for examples/testing
-mod signals;
+pub mod signals;  // Made public for database inspection and tooling
+pub mod uds;  // Made public for interpreting CAN-TP payloads outside the decoder
+pub mod obd;  // Made public for interpreting CAN-TP payloads outside the decoder
+pub mod j1939;  // Made public for PGN/address helpers
 pub(crate) mod message_decoder;  // Made crate-public for container_decoder
+mod message_encoder;
 mod cantp;
 mod container_decoder;
 
@@ -4,9 +4,13 @@
 //! from the signal database. Handles bit extraction, endianness, multiplexing,
 //! and physical value conversion.
 
-use crate::signals::database::{ByteOrder, MessageDefinition, SignalDefinition, ValueType};
-use crate::types::{CanFrame, DecodedEvent, DecodedSignal, SignalValue, Timestamp};
-use std::collections::HashMap;
+use crate::signals::database::{MessageDefinition, SignalDefinition, ValueType};
+use crate::signals::extraction::BitWindow;
+use crate::types::{CanFrame, DecodedEvent, DecodedSignal, SignalValue};
+use std::sync::Arc;
+
+/// Maximum nesting of multiplexer chains (extended multiplexing)
+pub(crate) const MAX_MULTIPLEXER_DEPTH: usize = 16;
 
 /// Message decoder - extracts signals from CAN frames
 pub struct MessageDecoder;
@@ -22,48 +26,7 @@ impl MessageDecoder {
     /// * `Some(DecodedEvent::Message)` if decoding succeeded
     /// * `None` if no signals could be decoded
     pub fn decode_message(frame: &CanFrame, message_def: &MessageDefinition) -> Option<DecodedEvent> {
-        let mut decoded_signals = Vec::new();
-        let mut multiplexer_value: Option<u64> = None;
-
-        // For multiplexed messages, first extract the multiplexer signal value
-        if message_def.is_multiplexed {
-            if let Some(ref mux_signal_name) = message_def.multiplexer_signal {
-                // Find the multiplexer signal
-                if let Some(mux_signal) = message_def.signals.iter().find(|s| s.name == *mux_signal_name) {
-                    // Extract multiplexer value
-                    if let Some(value) = Self::extract_signal_value(&frame.data, mux_signal) {
-                        multiplexer_value = Some(value as u64);
-                    }
-                }
-            }
-        }
-
-        // Decode all signals (non-multiplexed and applicable multiplexed ones)
-        for signal in &message_def.signals {
-            // Check if signal should be decoded based on multiplexer
-            if let Some(ref mux_info) = signal.multiplexer_info {
-                // This signal is multiplexed - check if it should be active
-                if let Some(current_mux_value) = multiplexer_value {
-                    if !mux_info.multiplexer_values.contains(&current_mux_value) {
-                        // Skip this signal - multiplexer value doesn't match
-                        continue;
-                    }
-                } else {
-                    // No multiplexer value extracted - skip multiplexed signals
-                    continue;
-                }
-            }
-
-            // Extract signal value
-            if let Some(decoded) = Self::decode_signal(&frame.data, signal) {
-                decoded_signals.push(decoded);
-            }
-        }
-
-        // Only emit event if we decoded at least one signal
-        if decoded_signals.is_empty() {
-            return None;
-        }
+        let (decoded_signals, multiplexer_value) = Self::decode_signals(&frame.data, message_def)?;
 
         Some(DecodedEvent::Message {
             timestamp: frame.timestamp(),
@@ -71,9 +34,11 @@ impl MessageDecoder {
             can_id: frame.can_id,
             message_name: Some(message_def.name.clone()),
             sender: message_def.sender.clone(),
+            source_address: message_def.is_j1939.then_some(frame.can_id as u8),
             signals: decoded_signals,
             is_multiplexed: message_def.is_multiplexed,
             multiplexer_value,
+            container: None,
         })
     }
 
@@ -83,6 +48,8 @@ impl MessageDecoder {
     /// * `pdu_data` - Raw PDU data bytes
     /// * `message_def` - Message definition from signal database
     /// * `timestamp` - Timestamp for the decoded signals
+    /// * `channel` - CAN channel the PDU was received on
+    /// * `can_id` - CAN ID of the frame that carried the PDU
     ///
     /// # Returns
     /// * `Some(DecodedEvent::Message)` if decoding succeeded
@@ -91,75 +58,113 @@ impl MessageDecoder {
         pdu_data: &[u8],
         message_def: &MessageDefinition,
         timestamp: crate::types::Timestamp,
+        channel: u8,
+        can_id: u32,
     ) -> Option<DecodedEvent> {
-        let mut decoded_signals = Vec::new();
-        let mut multiplexer_value: Option<u64> = None;
-
-        // For multiplexed messages, first extract the multiplexer signal value
-        if message_def.is_multiplexed {
-            if let Some(ref mux_signal_name) = message_def.multiplexer_signal {
-                // Find the multiplexer signal
-                if let Some(mux_signal) = message_def.signals.iter().find(|s| s.name == *mux_signal_name) {
-                    // Extract multiplexer value
-                    if let Some(value) = Self::extract_signal_value(pdu_data, mux_signal) {
-                        multiplexer_value = Some(value as u64);
-                    }
-                }
-            }
-        }
-
-        // Decode all signals (non-multiplexed and applicable multiplexed ones)
-        for signal in &message_def.signals {
-            // Check if signal should be decoded based on multiplexer
-            if let Some(ref mux_info) = signal.multiplexer_info {
-                // This signal is multiplexed - check if it should be active
-                if let Some(current_mux_value) = multiplexer_value {
-                    if !mux_info.multiplexer_values.contains(&current_mux_value) {
-                        // Skip this signal - multiplexer value doesn't match
-                        continue;
-                    }
-                } else {
-                    // No multiplexer value extracted - skip multiplexed signals
-                    continue;
-                }
-            }
-
-            // Extract signal value
-            if let Some(decoded) = Self::decode_signal(pdu_data, signal) {
-                decoded_signals.push(decoded);
-            }
-        }
-
-        // Only emit event if we decoded at least one signal
-        if decoded_signals.is_empty() {
-            return None;
-        }
+        let (decoded_signals, multiplexer_value) = Self::decode_signals(pdu_data, message_def)?;
 
         Some(DecodedEvent::Message {
             timestamp,
-            channel: 0, // Contained PDUs don't have a specific CAN channel (using 0 as default)
-            can_id: message_def.id,
+            channel,
+            can_id,
             message_name: Some(message_def.name.clone()),
             sender: message_def.sender.clone(),
+            source_address: None,
             signals: decoded_signals,
             is_multiplexed: message_def.is_multiplexed,
             multiplexer_value,
+            container: None,
         })
     }
 
+    /// Decode all active signals of a message
+    ///
+    /// Returns the decoded signals and the value of the top-level multiplexer,
+    /// or `None` if no signal could be decoded.
+    fn decode_signals(data: &[u8], message_def: &MessageDefinition) -> Option<(Vec<DecodedSignal>, Option<u64>)> {
+        // For multiplexed messages, first extract the top-level multiplexer signal value
+        let multiplexer_value = message_def
+            .multiplexer_signal
+            .as_ref()
+            .filter(|_| message_def.is_multiplexed)
+            .and_then(|mux_signal_name| message_def.signals.iter().find(|s| s.name == *mux_signal_name))
+            .and_then(|mux_signal| Self::extract_signal_value(data, mux_signal))
+            .map(|value| value as u64);
+
+        // Decode all signals (non-multiplexed and applicable multiplexed ones)
+        let mut decoded_signals = Vec::with_capacity(message_def.signals.len());
+        decoded_signals.extend(
+            message_def
+                .signals
+                .iter()
+                .filter(|signal| Self::is_signal_active(data, message_def, signal, 0))
+                .filter_map(|signal| Self::decode_signal(data, signal)),
+        );
+
+        // Only emit event if we decoded at least one signal
+        if decoded_signals.is_empty() {
+            return None;
+        }
+        Some((decoded_signals, multiplexer_value))
+    }
+
+    /// Check whether a signal is active for the multiplexer values in `data`
+    ///
+    /// Follows the chain of multiplexers (extended multiplexing): a signal is
+    /// only active if its multiplexer is active and has a matching value.
+    pub(crate) fn is_signal_active(data: &[u8], message_def: &MessageDefinition, signal: &SignalDefinition, depth: usize) -> bool {
+        let Some(ref mux_info) = signal.multiplexer_info else {
+            return true;
+        };
+        // Guard against cyclic multiplexer definitions
+        if depth >= MAX_MULTIPLEXER_DEPTH {
+            return false;
+        }
+
+        let Some(mux_signal) = message_def
+            .signals
+            .iter()
+            .find(|s| s.name == mux_info.multiplexer_signal)
+        else {
+            return false;
+        };
+
+        Self::is_signal_active(data, message_def, mux_signal, depth + 1)
+            && Self::extract_signal_value(data, mux_signal)
+                .is_some_and(|value| mux_info.is_active_for(value as u64))
+    }
+
     /// Decode a single signal from CAN frame data
-    fn decode_signal(data: &[u8], signal: &SignalDefinition) -> Option<DecodedSignal> {
+    pub(crate) fn decode_signal(data: &[u8], signal: &SignalDefinition) -> Option<DecodedSignal> {
         // Extract raw value from CAN frame data
         let raw_value = Self::extract_signal_value(data, signal)?;
 
-        // Apply physical value conversion (factor and offset)
-        let physical_value = signal.offset + signal.factor * (raw_value as f64);
+        // Shared name and unit from the extraction plan (no per-signal allocation)
+        let (name, unit): (Arc<str>, Option<Arc<str>>) = match &signal.extraction {
+            Some(plan) => (plan.name.clone(), plan.unit.clone()),
+            None => (signal.name.as_str().into(), signal.unit.as_deref().map(Into::into)),
+        };
+
+        // IEEE float signals: reinterpret the raw bits, value tables don't apply
+        if let Some(float_value) = Self::float_value(raw_value, signal) {
+            return Some(DecodedSignal {
+                name,
+                value: SignalValue::Float(signal.physical_value(float_value)),
+                unit,
+                value_description: None,
+                raw_value,
+            });
+        }
+
+        // Apply physical value conversion (linear or rational scale for this raw value)
+        let physical_value = signal.physical_value(raw_value as f64);
+        let is_scaled = signal.linear_coefficients(raw_value as f64) != Some((1.0, 0.0));
 
         // Determine value type and create appropriate SignalValue
-        let value = if signal.factor == 1.0 && signal.offset == 0.0 && signal.length == 1 {
+        let value = if !is_scaled && signal.length == 1 {
             // Boolean signal (single bit, no scaling)
             SignalValue::Boolean(raw_value != 0)
-        } else if signal.factor != 1.0 || signal.offset != 0.0 {
+        } else if is_scaled {
             // Scaled signal - use float
             SignalValue::Float(physical_value)
         } else {
@@ -175,9 +180,9 @@ impl MessageDecoder {
             .cloned();
 
         Some(DecodedSignal {
-            name: signal.name.clone(),
+            name,
             value,
-            unit: signal.unit.clone(),
+            unit,
             value_description,
             raw_value,
         })
@@ -185,153 +190,90 @@ impl MessageDecoder {
 
     /// Extract raw signal value from CAN frame data
     ///
-    /// Handles bit extraction with proper endianness support.
-    /// This is the core signal extraction algorithm.
+    /// Uses the signal's precompiled extraction plan, or builds the bit window
+    /// on the fly for signals that were not added through a `SignalDatabase`.
     fn extract_signal_value(data: &[u8], signal: &SignalDefinition) -> Option<i64> {
-        let start_bit = signal.start_bit as usize;
-        let length = signal.length as usize;
+        let window = signal
+            .extraction
+            .as_ref()
+            .map_or_else(|| BitWindow::new(signal), |plan| plan.window);
 
-        // Validate signal fits within data
-        let required_bytes = ((start_bit + length) + 7) / 8;
-        if required_bytes > data.len() {
+        let raw_value = window.extract(data);
+        if raw_value.is_none() {
             log::warn!(
                 "Signal '{}' requires {} bytes but frame only has {} bytes",
                 signal.name,
-                required_bytes,
+                window.required_bytes(),
                 data.len()
             );
-            return None;
-        }
-
-        // Extract raw bits based on byte order
-        let raw_value = match signal.byte_order {
-            ByteOrder::LittleEndian => Self::extract_little_endian(data, start_bit, length),
-            ByteOrder::BigEndian => Self::extract_big_endian(data, start_bit, length),
-        };
-
-        // Apply sign extension if needed
-        let signed_value = match signal.value_type {
-            ValueType::Unsigned => raw_value as i64,
-            ValueType::Signed => Self::sign_extend(raw_value, length),
-        };
-
-        Some(signed_value)
-    }
-
-    /// Extract signal with little-endian (Intel) byte order
-    ///
-    /// Little-endian format:
-    /// - Start bit points to the LSB (least significant bit)
-    /// - Bits are numbered from LSB to MSB within each byte
-    /// - Byte 0 is the first byte in the CAN frame
-    fn extract_little_endian(data: &[u8], start_bit: usize, length: usize) -> u64 {
-        let mut result: u64 = 0;
-
-        for i in 0..length {
-            let bit_pos = start_bit + i;
-            let byte_idx = bit_pos / 8;
-            let bit_in_byte = bit_pos % 8;
-
-            if byte_idx < data.len() {
-                let bit_value = (data[byte_idx] >> bit_in_byte) & 0x01;
-                result |= (bit_value as u64) << i;
-            }
         }
-
-        result
+        raw_value
     }
 
-    /// Extract signal with big-endian (Motorola) byte order
-    ///
-    /// Big-endian format in CAN:
-    /// - Start bit points to the MSB (most significant bit) of the signal
-    /// - Bit numbering: bit 0 = MSB of byte 0, bit 7 = LSB of byte 0
-    /// - Signal grows downward (towards higher bit numbers)
-    fn extract_big_endian(data: &[u8], start_bit: usize, length: usize) -> u64 {
-        let mut result: u64 = 0;
-
-        for i in 0..length {
-            // In big-endian, start_bit is MSB, and we count forward
-            let bit_pos = start_bit + i;
-            let byte_idx = bit_pos / 8;
-            let bit_in_byte = 7 - (bit_pos % 8); // Bit 0 = MSB, bit 7 = LSB
-
-            if byte_idx < data.len() {
-                let bit_value = (data[byte_idx] >> bit_in_byte) & 0x01;
-                result |= (bit_value as u64) << (length - 1 - i);
-            }
+    /// Reinterpret the raw bits of a float signal (`None` for integer signals)
+    fn float_value(raw_value: i64, signal: &SignalDefinition) -> Option<f64> {
+        match signal.value_type {
+            ValueType::Float32 => Some(f32::from_bits(raw_value as u32) as f64),
+            ValueType::Float64 => Some(f64::from_bits(raw_value as u64)),
+            ValueType::Signed | ValueType::Unsigned => None,
         }
-
-        result
     }
 
-    /// Sign-extend a value from N bits to 64 bits
-    ///
-    /// If the value's MSB is 1, fill the upper bits with 1s.
-    /// This converts unsigned representation to proper signed value.
-    fn sign_extend(value: u64, bit_length: usize) -> i64 {
-        if bit_length >= 64 {
-            return value as i64;
-        }
-
-        let sign_bit = 1u64 << (bit_length - 1);
-        if (value & sign_bit) != 0 {
-            // Negative value - sign extend
-            let mask = !0u64 << bit_length;
-            (value | mask) as i64
-        } else {
-            // Positive value
-            value as i64
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use crate::signals::database::{ByteOrder, ConversionScale, SignalConversion};
 
     #[test]
-    fn test_extract_little_endian_simple() {
-        // Signal: 8 bits starting at bit 0 (byte 0)
-        let data = vec![0xAB, 0xCD, 0xEF, 0x12];
-        let value = MessageDecoder::extract_little_endian(&data, 0, 8);
-        assert_eq!(value, 0xAB);
-    }
-
-    #[test]
-    fn test_extract_little_endian_cross_byte() {
-        // Signal: 16 bits starting at bit 0 (bytes 0-1)
-        let data = vec![0xAB, 0xCD, 0xEF, 0x12];
-        let value = MessageDecoder::extract_little_endian(&data, 0, 16);
-        assert_eq!(value, 0xCDAB); // Little-endian byte order
-    }
+    fn test_decode_float_signals() {
+        let signal = |length, byte_order, value_type| {
+            let start_bit = if byte_order == ByteOrder::BigEndian { 7 } else { 0 };
+            let mut signal = SignalDefinition::test("Value", start_bit, length, byte_order);
+            signal.value_type = value_type;
+            signal.factor = 2.0;
+            signal.offset = 1.0;
+            signal
+        };
 
-    #[test]
-    fn test_extract_big_endian_simple() {
-        // Signal: 8 bits starting at bit 7 (byte 0)
-        let data = vec![0xAB, 0xCD, 0xEF, 0x12];
-        let value = MessageDecoder::extract_big_endian(&data, 7, 8);
-        assert_eq!(value, 0xAB);
-    }
+        let data = 1.5f32.to_le_bytes();
+        let decoded =
+            MessageDecoder::decode_signal(&data, &signal(32, ByteOrder::LittleEndian, ValueType::Float32)).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(4.0));
 
-    #[test]
-    fn test_sign_extend_positive() {
-        // 8-bit value 0x7F (127) should remain positive
-        let value = MessageDecoder::sign_extend(0x7F, 8);
-        assert_eq!(value, 127);
-    }
+        let data = (-0.25f32).to_be_bytes();
+        let decoded =
+            MessageDecoder::decode_signal(&data, &signal(32, ByteOrder::BigEndian, ValueType::Float32)).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(0.5));
 
-    #[test]
-    fn test_sign_extend_negative() {
-        // 8-bit value 0xFF (-1 in two's complement) should become -1
-        let value = MessageDecoder::sign_extend(0xFF, 8);
-        assert_eq!(value, -1);
+        let data = 1234.5f64.to_le_bytes();
+        let decoded =
+            MessageDecoder::decode_signal(&data, &signal(64, ByteOrder::LittleEndian, ValueType::Float64)).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(2470.0));
     }
 
     #[test]
-    fn test_sign_extend_negative_16bit() {
-        // 16-bit value 0x8000 (-32768 in two's complement)
-        let value = MessageDecoder::sign_extend(0x8000, 16);
-        assert_eq!(value, -32768);
+    fn test_decode_piecewise_linear_signal() {
+        let mut signal = SignalDefinition::test("Temperature", 0, 8, ByteOrder::LittleEndian);
+        signal.factor = 0.5;
+        signal.max = 150.0;
+        signal.value_table = Some(HashMap::from([(255, "SNA".to_string())]));
+        signal.conversion = Some(SignalConversion::PiecewiseLinear(vec![
+            ConversionScale { lower_limit: 0.0, upper_limit: 100.0, factor: 0.5, offset: 0.0 },
+            ConversionScale { lower_limit: 101.0, upper_limit: 200.0, factor: 1.0, offset: -50.0 },
+        ]));
+
+        let decoded = MessageDecoder::decode_signal(&[80], &signal).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(40.0));
+
+        let decoded = MessageDecoder::decode_signal(&[150], &signal).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(100.0));
+
+        // Outside every scale: fall back to factor/offset, keep the text
+        let decoded = MessageDecoder::decode_signal(&[255], &signal).unwrap();
+        assert_eq!(decoded.value, SignalValue::Float(127.5));
+        assert_eq!(decoded.value_description.as_deref(), Some("SNA"));
     }
 }
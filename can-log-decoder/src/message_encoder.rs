@@ -0,0 +1,388 @@
+//! Message Encoding Engine
+//!
+//! The inverse of the message decoder: packs physical values, value table
+//! labels or raw values into the bytes of a message. Used to build test
+//! frames, write modified logs and round-trip test the decoder.
+
+use crate::message_decoder::{MessageDecoder, MAX_MULTIPLEXER_DEPTH};
+use crate::signals::database::{MessageDefinition, SignalDefinition, ValueType};
+use crate::signals::extraction::{required_bytes, signal_bits};
+use crate::types::{DecoderError, Result};
+use std::collections::HashMap;
+
+/// Signal attribute holding the value of signals that are not given
+const START_VALUE_ATTRIBUTE: &str = "GenSigStartValue";
+
+/// A value to encode into a signal
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodeValue {
+    /// Physical value; the inverse of the signal's conversion is applied
+    Physical(f64),
+    /// Value table label (e.g. "On")
+    Label(String),
+    /// Raw value, written without conversion
+    Raw(i64),
+}
+
+impl From<f64> for EncodeValue {
+    fn from(value: f64) -> Self {
+        EncodeValue::Physical(value)
+    }
+}
+
+impl From<&str> for EncodeValue {
+    fn from(label: &str) -> Self {
+        EncodeValue::Label(label.to_string())
+    }
+}
+
+impl From<String> for EncodeValue {
+    fn from(label: String) -> Self {
+        EncodeValue::Label(label)
+    }
+}
+
+/// Message encoder - packs signal values into frame bytes
+///
+/// Signals that are not given are set to their `GenSigStartValue` attribute
+/// (a raw value), or 0. Multiplexers that are not given take the first value
+/// selecting the given multiplexed signals.
+///
+/// # Example
+/// ```
+/// use can_log_decoder::{EncodeValue, MessageEncoder};
+/// # use can_log_decoder::signals::MessageDefinition;
+/// # fn example(message_def: &MessageDefinition) -> can_log_decoder::Result<()> {
+/// let data = MessageEncoder::new()
+///     .with_clamping(true)
+///     .encode(message_def, [("EngineSpeed", EncodeValue::Physical(2500.0))])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageEncoder {
+    clamp: bool,
+}
+
+impl MessageEncoder {
+    /// Create an encoder that rejects out-of-range values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clamp out-of-range values to the signal's range instead of failing
+    pub fn with_clamping(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Encode signal values into the bytes of a message
+    ///
+    /// Fails for unknown signals, unknown labels, out-of-range values (unless
+    /// clamping) and signals not selected by their multiplexer.
+    pub fn encode<K, V>(
+        &self,
+        message_def: &MessageDefinition,
+        values: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Vec<u8>>
+    where
+        K: AsRef<str>,
+        V: Into<EncodeValue>,
+    {
+        let mut raw_values = HashMap::new();
+        for (name, value) in values {
+            let name = name.as_ref();
+            let signal = message_def
+                .signal(name)
+                .ok_or_else(|| DecoderError::SignalNotFound(format!("{} in {}", name, message_def.name)))?;
+            raw_values.insert(name.to_string(), self.raw_bits(signal, &value.into())?);
+        }
+        let given: Vec<String> = raw_values.keys().cloned().collect();
+        Self::infer_multiplexers(message_def, &mut raw_values);
+
+        let mut data = vec![0u8; message_def.size];
+        let mut pending: Vec<&SignalDefinition> = Vec::new();
+        for signal in &message_def.signals {
+            if signal.multiplexer_info.is_none() {
+                Self::write_signal(&mut data, signal, self.value_or_start(signal, &raw_values)?)?;
+            } else {
+                pending.push(signal);
+            }
+        }
+
+        // Multiplexed signals once their multiplexer chain is written
+        for _ in 0..MAX_MULTIPLEXER_DEPTH {
+            let (active, inactive): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|signal| MessageDecoder::is_signal_active(&data, message_def, signal, 0));
+            pending = inactive;
+            if active.is_empty() {
+                break;
+            }
+            for signal in active {
+                Self::write_signal(&mut data, signal, self.value_or_start(signal, &raw_values)?)?;
+            }
+        }
+
+        if let Some(signal) = pending.iter().find(|signal| given.contains(&signal.name)) {
+            return Err(DecoderError::EncodeError(format!(
+                "Signal {} is not selected by the multiplexer values of {}",
+                signal.name, message_def.name
+            )));
+        }
+        Ok(data)
+    }
+
+    /// Given raw value of a signal, else its start value
+    fn value_or_start(&self, signal: &SignalDefinition, raw_values: &HashMap<String, u64>) -> Result<u64> {
+        if let Some(&raw) = raw_values.get(&signal.name) {
+            return Ok(raw);
+        }
+        let start = signal
+            .attribute(START_VALUE_ATTRIBUTE)
+            .and_then(|value| value.as_f64())
+            .unwrap_or(0.0);
+        // Start values outside the raw range fall back to 0 rather than failing
+        Ok(self.pack_value(signal, start).unwrap_or(0))
+    }
+
+    /// Set multiplexers that were not given to the first value selecting a given signal
+    fn infer_multiplexers(message_def: &MessageDefinition, raw_values: &mut HashMap<String, u64>) {
+        for _ in 0..MAX_MULTIPLEXER_DEPTH {
+            let inferred: Vec<(String, u64)> = message_def
+                .signals
+                .iter()
+                .filter(|signal| raw_values.contains_key(&signal.name))
+                .filter_map(|signal| signal.multiplexer_info.as_ref())
+                .filter(|mux| !raw_values.contains_key(&mux.multiplexer_signal))
+                .filter_map(|mux| {
+                    let value = mux
+                        .multiplexer_values
+                        .first()
+                        .copied()
+                        .or_else(|| mux.multiplexer_ranges.first().map(|&(min, _)| min))?;
+                    Some((mux.multiplexer_signal.clone(), value))
+                })
+                .collect();
+            if inferred.is_empty() {
+                return;
+            }
+            for (multiplexer, value) in inferred {
+                raw_values.entry(multiplexer).or_insert(value);
+            }
+        }
+    }
+
+    /// Convert a value to the raw bits of a signal
+    fn raw_bits(&self, signal: &SignalDefinition, value: &EncodeValue) -> Result<u64> {
+        match value {
+            EncodeValue::Raw(raw) => self.pack_raw(signal, *raw as f64),
+            EncodeValue::Label(label) => {
+                // Ranged labels (ARXML TEXTTABLE) map several raw values to one text
+                let raw = signal
+                    .value_table
+                    .as_ref()
+                    .and_then(|table| table.iter().filter(|(_, text)| *text == label).map(|(&raw, _)| raw).min())
+                    .ok_or_else(|| {
+                        DecoderError::EncodeError(format!("Signal {} has no value \"{}\"", signal.name, label))
+                    })?;
+                self.pack_raw(signal, raw as f64)
+            }
+            EncodeValue::Physical(physical) => {
+                let mut physical = *physical;
+                if signal.min < signal.max && !(signal.min..=signal.max).contains(&physical) {
+                    if !self.clamp {
+                        return Err(DecoderError::EncodeError(format!(
+                            "Value {} of signal {} is outside [{}, {}]",
+                            physical, signal.name, signal.min, signal.max
+                        )));
+                    }
+                    physical = physical.clamp(signal.min, signal.max);
+                }
+                let raw = signal.raw_value(physical).ok_or_else(|| {
+                    DecoderError::EncodeError(format!(
+                        "Value {} of signal {} has no raw representation",
+                        physical, signal.name
+                    ))
+                })?;
+                self.pack_value(signal, raw)
+            }
+        }
+    }
+
+    /// Pack a raw value: IEEE bits for float signals, a rounded integer otherwise
+    fn pack_value(&self, signal: &SignalDefinition, raw: f64) -> Result<u64> {
+        match signal.value_type {
+            ValueType::Float32 => Ok((raw as f32).to_bits() as u64),
+            ValueType::Float64 => Ok(raw.to_bits()),
+            ValueType::Signed | ValueType::Unsigned => self.pack_raw(signal, raw.round()),
+        }
+    }
+
+    /// Range-check an integer raw value and truncate it to the signal length
+    fn pack_raw(&self, signal: &SignalDefinition, raw: f64) -> Result<u64> {
+        let length = signal.length.min(64) as u32;
+        let (min, max) = match signal.value_type {
+            ValueType::Signed => (-(2f64.powi(length as i32 - 1)), 2f64.powi(length as i32 - 1) - 1.0),
+            _ => (0.0, 2f64.powi(length as i32) - 1.0),
+        };
+        let raw = if (min..=max).contains(&raw) {
+            raw
+        } else if self.clamp {
+            raw.clamp(min, max)
+        } else {
+            return Err(DecoderError::EncodeError(format!(
+                "Raw value {} of signal {} does not fit in {} bits",
+                raw, signal.name, length
+            )));
+        };
+
+        let mask = if length >= 64 { u64::MAX } else { (1u64 << length) - 1 };
+        let bits = match signal.value_type {
+            ValueType::Signed => raw as i64 as u64,
+            _ => raw as u64,
+        };
+        Ok(bits & mask)
+    }
+
+    /// Write the raw bits of a signal into the message data
+    fn write_signal(data: &mut [u8], signal: &SignalDefinition, raw: u64) -> Result<()> {
+        let start_bit = signal.start_bit as usize;
+        let length = signal.length as usize;
+        if required_bytes(signal.byte_order, start_bit, length) > data.len() {
+            return Err(DecoderError::InvalidSignalDefinition(format!(
+                "Signal {} does not fit in {} bytes",
+                signal.name,
+                data.len()
+            )));
+        }
+        // Bit i of the raw value goes to the i-th position, least significant first
+        for (i, bit_pos) in signal_bits(signal.byte_order, start_bit, length).into_iter().enumerate() {
+            let bit = ((raw >> i) & 0x01) as u8;
+            data[bit_pos / 8] = (data[bit_pos / 8] & !(1 << (bit_pos % 8))) | (bit << (bit_pos % 8));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::database::{AttributeValue, ByteOrder};
+    use crate::types::{CanFrame, DecodedEvent, SignalValue};
+
+    fn decode(message_def: &MessageDefinition, data: Vec<u8>) -> HashMap<String, SignalValue> {
+        let frame = CanFrame {
+            timestamp_ns: 0,
+            measurement_start_ns: 0,
+            channel: 0,
+            can_id: message_def.id,
+            data,
+            is_extended: false,
+            is_fd: false,
+            is_error_frame: false,
+            is_remote_frame: false,
+        };
+        match MessageDecoder::decode_message(&frame, message_def) {
+            Some(DecodedEvent::Message { signals, .. }) => {
+                signals.into_iter().map(|s| (s.name.to_string(), s.value)).collect()
+            }
+            other => panic!("expected message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_byte_orders_and_types() {
+        let mut speed = SignalDefinition::test("Speed", 0, 16, ByteOrder::LittleEndian);
+        speed.factor = 0.25;
+        speed.max = 16000.0;
+        let mut temp = SignalDefinition::test("Temp", 23, 8, ByteOrder::BigEndian);
+        temp.value_type = ValueType::Signed;
+        temp.offset = -40.0;
+        let mut ratio = SignalDefinition::test("Ratio", 32, 32, ByteOrder::LittleEndian);
+        ratio.value_type = ValueType::Float32;
+        let message_def = MessageDefinition::test(0x100, "Test", 8, vec![speed, temp, ratio]);
+
+        let values = [
+            ("Speed", EncodeValue::Physical(2500.0)),
+            ("Temp", EncodeValue::Physical(-60.0)),
+            ("Ratio", EncodeValue::Physical(0.5)),
+        ];
+        let data = MessageEncoder::new().encode(&message_def, values).unwrap();
+        assert_eq!(&data[..3], [0x10, 0x27, 0xEC]);
+
+        let decoded = decode(&message_def, data);
+        assert_eq!(decoded["Speed"], SignalValue::Float(2500.0));
+        assert_eq!(decoded["Temp"], SignalValue::Float(-60.0));
+        assert_eq!(decoded["Ratio"], SignalValue::Float(0.5));
+    }
+
+    #[test]
+    fn test_labels_start_values_and_multiplexers() {
+        let mut state = SignalDefinition::test("State", 0, 4, ByteOrder::LittleEndian);
+        // Ready covers raw values 3..=6, as expanded from a TEXTTABLE range
+        state.value_table = Some(
+            [(0, "Off")]
+                .into_iter()
+                .chain((3..=6).map(|raw| (raw, "Ready")))
+                .map(|(raw, text)| (raw, text.to_string()))
+                .collect(),
+        );
+        let mut counter = SignalDefinition::test("Counter", 4, 4, ByteOrder::LittleEndian);
+        counter.attributes.insert(START_VALUE_ATTRIBUTE.to_string(), AttributeValue::Float(5.0));
+        let mut gain = SignalDefinition::test("Gain", 24, 32, ByteOrder::LittleEndian);
+        gain.value_type = ValueType::Float32;
+        gain.attributes.insert(START_VALUE_ATTRIBUTE.to_string(), AttributeValue::Float(5.0));
+        let mut message_def = MessageDefinition::test(
+            0x100,
+            "Test",
+            7,
+            vec![
+                state,
+                counter,
+                SignalDefinition::test("Mode", 8, 8, ByteOrder::LittleEndian),
+                SignalDefinition::test("A", 16, 8, ByteOrder::LittleEndian).multiplexed_by("Mode", 1),
+                SignalDefinition::test("B", 16, 8, ByteOrder::LittleEndian).multiplexed_by("Mode", 2),
+                gain,
+            ],
+        );
+        message_def.is_multiplexed = true;
+        message_def.multiplexer_signal = Some("Mode".to_string());
+
+        // Mode is inferred from B, Counter and Gain take their start values (Gain
+        // as IEEE bits), Ready the lowest raw value
+        let encoder = MessageEncoder::new();
+        let data = encoder
+            .encode(&message_def, [("State", EncodeValue::from("Ready")), ("B", EncodeValue::Raw(0x42))])
+            .unwrap();
+        assert_eq!(data, [0x53, 0x02, 0x42, 0x00, 0x00, 0xA0, 0x40]);
+        assert_eq!(decode(&message_def, data)["Gain"], SignalValue::Float(5.0));
+
+        // B is not selected by Mode 1
+        let result = encoder.encode(&message_def, [("Mode", EncodeValue::Raw(1)), ("B", EncodeValue::Raw(1))]);
+        assert!(matches!(result, Err(DecoderError::EncodeError(_))));
+        let result = encoder.encode(&message_def, [("State", "Unknown")]);
+        assert!(matches!(result, Err(DecoderError::EncodeError(_))));
+        let result = encoder.encode(&message_def, [("Missing", 1.0)]);
+        assert!(matches!(result, Err(DecoderError::SignalNotFound(_))));
+    }
+
+    #[test]
+    fn test_range_errors_and_clamping() {
+        let mut level = SignalDefinition::test("Level", 0, 8, ByteOrder::LittleEndian);
+        level.max = 100.0;
+        let mut raw = SignalDefinition::test("Raw", 8, 4, ByteOrder::LittleEndian);
+        raw.value_type = ValueType::Signed;
+        let message_def = MessageDefinition::test(0x100, "Test", 2, vec![level, raw]);
+
+        let strict = MessageEncoder::new();
+        assert!(strict.encode(&message_def, [("Level", 150.0)]).is_err());
+        assert!(strict.encode(&message_def, [("Raw", EncodeValue::Raw(8))]).is_err());
+
+        let clamping = MessageEncoder::new().with_clamping(true);
+        let data = clamping
+            .encode(&message_def, [("Level", EncodeValue::Physical(150.0)), ("Raw", EncodeValue::Raw(-20))])
+            .unwrap();
+        assert_eq!(data, [100, 0x08]);
+    }
+}
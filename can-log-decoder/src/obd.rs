@@ -0,0 +1,349 @@
+//! OBD-II (SAE J1979 / ISO 15765-4) decoding of CAN-TP responses
+//!
+//! Positive responses (service ID + 0x40) of the emission-related services are
+//! turned into `DecodedEvent::Message` events with a synthetic message name, so
+//! they can be used like DBC signals:
+//!
+//! | Mode | Response | Message name          | Content                        |
+//! |------|----------|-----------------------|--------------------------------|
+//! | 01   | 41       | `OBD2_CurrentData`    | PID values                     |
+//! | 02   | 42       | `OBD2_FreezeFrame`    | PID values of a freeze frame   |
+//! | 03   | 43       | `OBD2_StoredDTCs`     | DTC count + DTCs               |
+//! | 07   | 47       | `OBD2_PendingDTCs`    | DTC count + DTCs               |
+//! | 09   | 49       | `OBD2_VehicleInfo`    | VIN, calibration ID, ECU name  |
+//! | 0A   | 4A       | `OBD2_PermanentDTCs`  | DTC count + DTCs               |
+//!
+//! Text values (VIN, DTC codes like "P0123") are carried in
+//! `DecodedSignal::value_description`.
+
+use crate::types::{DecodedEvent, DecodedSignal, SignalValue};
+use crate::uds::POSITIVE_RESPONSE_OFFSET;
+
+/// A mode 01/02 PID with a linear formula: `value = raw * factor + offset`
+struct PidDefinition {
+    pid: u8,
+    name: &'static str,
+    /// Data bytes (A, B, ...) interpreted as one big-endian unsigned value
+    length: usize,
+    factor: f64,
+    offset: f64,
+    unit: Option<&'static str>,
+}
+
+const fn pid(
+    pid: u8,
+    name: &'static str,
+    length: usize,
+    factor: f64,
+    offset: f64,
+    unit: Option<&'static str>,
+) -> PidDefinition {
+    PidDefinition {
+        pid,
+        name,
+        length,
+        factor,
+        offset,
+        unit,
+    }
+}
+
+/// Standard PIDs of modes 01 and 02 (J1979 Appendix B)
+const PIDS: &[PidDefinition] = &[
+    pid(0x03, "FuelSystemStatus", 2, 1.0, 0.0, None),
+    pid(0x04, "CalculatedEngineLoad", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x05, "EngineCoolantTemperature", 1, 1.0, -40.0, Some("°C")),
+    pid(0x06, "ShortTermFuelTrimBank1", 1, 100.0 / 128.0, -100.0, Some("%")),
+    pid(0x07, "LongTermFuelTrimBank1", 1, 100.0 / 128.0, -100.0, Some("%")),
+    pid(0x08, "ShortTermFuelTrimBank2", 1, 100.0 / 128.0, -100.0, Some("%")),
+    pid(0x09, "LongTermFuelTrimBank2", 1, 100.0 / 128.0, -100.0, Some("%")),
+    pid(0x0A, "FuelPressure", 1, 3.0, 0.0, Some("kPa")),
+    pid(0x0B, "IntakeManifoldAbsolutePressure", 1, 1.0, 0.0, Some("kPa")),
+    pid(0x0C, "EngineSpeed", 2, 0.25, 0.0, Some("rpm")),
+    pid(0x0D, "VehicleSpeed", 1, 1.0, 0.0, Some("km/h")),
+    pid(0x0E, "TimingAdvance", 1, 0.5, -64.0, Some("°")),
+    pid(0x0F, "IntakeAirTemperature", 1, 1.0, -40.0, Some("°C")),
+    pid(0x10, "MassAirFlowRate", 2, 0.01, 0.0, Some("g/s")),
+    pid(0x11, "ThrottlePosition", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x1C, "OBDStandard", 1, 1.0, 0.0, None),
+    pid(0x1F, "RunTimeSinceEngineStart", 2, 1.0, 0.0, Some("s")),
+    pid(0x21, "DistanceWithMILOn", 2, 1.0, 0.0, Some("km")),
+    pid(0x22, "FuelRailPressureRelative", 2, 0.079, 0.0, Some("kPa")),
+    pid(0x23, "FuelRailGaugePressure", 2, 10.0, 0.0, Some("kPa")),
+    pid(0x2C, "CommandedEGR", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x2E, "CommandedEvaporativePurge", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x2F, "FuelTankLevel", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x30, "WarmUpsSinceCodesCleared", 1, 1.0, 0.0, None),
+    pid(0x31, "DistanceSinceCodesCleared", 2, 1.0, 0.0, Some("km")),
+    pid(0x33, "BarometricPressure", 1, 1.0, 0.0, Some("kPa")),
+    pid(0x42, "ControlModuleVoltage", 2, 0.001, 0.0, Some("V")),
+    pid(0x43, "AbsoluteLoad", 2, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x45, "RelativeThrottlePosition", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x46, "AmbientAirTemperature", 1, 1.0, -40.0, Some("°C")),
+    pid(0x49, "AcceleratorPedalPositionD", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x4A, "AcceleratorPedalPositionE", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x4C, "CommandedThrottleActuator", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x4D, "TimeRunWithMILOn", 2, 1.0, 0.0, Some("min")),
+    pid(0x4E, "TimeSinceCodesCleared", 2, 1.0, 0.0, Some("min")),
+    pid(0x51, "FuelType", 1, 1.0, 0.0, None),
+    pid(0x52, "EthanolFuelPercentage", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x5A, "RelativeAcceleratorPedalPosition", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x5B, "HybridBatteryRemainingLife", 1, 100.0 / 255.0, 0.0, Some("%")),
+    pid(0x5C, "EngineOilTemperature", 1, 1.0, -40.0, Some("°C")),
+    pid(0x5E, "EngineFuelRate", 2, 0.05, 0.0, Some("L/h")),
+    pid(0xA6, "Odometer", 4, 0.1, 0.0, Some("km")),
+];
+
+/// Decode an OBD-II response carried in a `CanTpMessage` event
+///
+/// Returns a `DecodedEvent::Message` for positive responses of modes
+/// 01/02/03/07/09/0A, `None` for everything else (requests, UDS, ...).
+pub fn process_event(event: &DecodedEvent) -> Option<DecodedEvent> {
+    let DecodedEvent::CanTpMessage {
+        timestamp,
+        channel,
+        source_addr,
+        payload,
+        ..
+    } = event
+    else {
+        return None;
+    };
+
+    let (message_name, signals) = decode_response(payload)?;
+    Some(DecodedEvent::Message {
+        timestamp: *timestamp,
+        channel: *channel,
+        can_id: *source_addr,
+        message_name: Some(message_name.to_string()),
+        sender: None,
+        source_address: None,
+        signals,
+        is_multiplexed: false,
+        multiplexer_value: None,
+        container: None,
+    })
+}
+
+/// Decode an OBD-II positive response payload into a message name and signals
+pub fn decode_response(payload: &[u8]) -> Option<(&'static str, Vec<DecodedSignal>)> {
+    let (&sid, data) = payload.split_first()?;
+    let mode = sid.checked_sub(POSITIVE_RESPONSE_OFFSET)?;
+
+    let (name, signals) = match mode {
+        0x01 => ("OBD2_CurrentData", decode_pids(data, false)),
+        0x02 => ("OBD2_FreezeFrame", decode_pids(data, true)),
+        0x03 => ("OBD2_StoredDTCs", decode_dtcs(data)),
+        0x07 => ("OBD2_PendingDTCs", decode_dtcs(data)),
+        0x09 => ("OBD2_VehicleInfo", decode_vehicle_info(data)),
+        0x0A => ("OBD2_PermanentDTCs", decode_dtcs(data)),
+        _ => return None,
+    };
+
+    (!signals.is_empty()).then_some((name, signals))
+}
+
+/// Decode `PID data [PID data ...]` (mode 02 adds a frame number after each PID)
+fn decode_pids(mut data: &[u8], freeze_frame: bool) -> Vec<DecodedSignal> {
+    let mut signals = Vec::new();
+    let header = if freeze_frame { 2 } else { 1 };
+
+    while data.len() > header {
+        let pid = data[0];
+        let values = &data[header..];
+
+        let consumed = if pid.is_multiple_of(0x20) {
+            // Supported PIDs [pid+1 .. pid+0x20] bitmask
+            let Some(raw) = read_unsigned(values, 4) else {
+                break;
+            };
+            signals.push(integer_signal(
+                format!("PIDsSupported_{:02X}_{:02X}", pid as u16 + 1, pid as u16 + 0x20),
+                raw,
+            ));
+            4
+        } else if pid == 0x01 {
+            // Monitor status since DTCs cleared: MIL (A7) and DTC count (A6..A0)
+            let Some(&a) = values.first() else {
+                break;
+            };
+            if values.len() < 4 {
+                break;
+            }
+            signals.push(DecodedSignal {
+                name: "MIL".into(),
+                value: SignalValue::Boolean(a & 0x80 != 0),
+                unit: None,
+                value_description: None,
+                raw_value: (a >> 7) as i64,
+            });
+            signals.push(integer_signal("DTCCount".to_string(), (a & 0x7F) as u64));
+            4
+        } else if let Some(def) = PIDS.iter().find(|d| d.pid == pid) {
+            let Some(raw) = read_unsigned(values, def.length) else {
+                break;
+            };
+            signals.push(scaled_signal(def, raw));
+            def.length
+        } else {
+            log::trace!("Unknown OBD-II PID 0x{:02X}, stopping", pid);
+            break;
+        };
+
+        data = &values[consumed..];
+    }
+
+    signals
+}
+
+/// Decode `count DTC DTC ...` (2 bytes per DTC)
+fn decode_dtcs(data: &[u8]) -> Vec<DecodedSignal> {
+    let Some((&count, dtcs)) = data.split_first() else {
+        return Vec::new();
+    };
+
+    let mut signals = vec![integer_signal("DTCCount".to_string(), count as u64)];
+    for (index, dtc) in dtcs.chunks_exact(2).take(count as usize).enumerate() {
+        let raw = u16::from_be_bytes([dtc[0], dtc[1]]);
+        signals.push(DecodedSignal {
+            name: format!("DTC_{}", index + 1).into(),
+            value: SignalValue::Integer(raw as i64),
+            unit: None,
+            value_description: Some(format_dtc(raw)),
+            raw_value: raw as i64,
+        });
+    }
+    signals
+}
+
+/// Decode mode 09 info types: `InfoType NumberOfDataItems data`
+fn decode_vehicle_info(data: &[u8]) -> Vec<DecodedSignal> {
+    let Some((&info_type, values)) = data.split_first() else {
+        return Vec::new();
+    };
+
+    let name = match info_type {
+        0x02 => "VIN",
+        0x04 => "CalibrationID",
+        0x0A => "ECUName",
+        pid if pid.is_multiple_of(0x20) => {
+            return read_unsigned(values, 4)
+                .map(|raw| {
+                    vec![integer_signal(
+                        format!("InfoTypesSupported_{:02X}_{:02X}", pid as u16 + 1, pid as u16 + 0x20),
+                        raw,
+                    )]
+                })
+                .unwrap_or_default();
+        }
+        _ => return Vec::new(),
+    };
+
+    // Skip the number of data items
+    let Some((&items, text)) = values.split_first() else {
+        return Vec::new();
+    };
+    vec![DecodedSignal {
+        name: name.into(),
+        value: SignalValue::Integer(items as i64),
+        unit: None,
+        value_description: Some(ascii_text(text)),
+        raw_value: items as i64,
+    }]
+}
+
+/// Format a 2-byte DTC as e.g. "P0123"
+pub fn format_dtc(raw: u16) -> String {
+    let system = ['P', 'C', 'B', 'U'][(raw >> 14) as usize];
+    format!("{}{}{:03X}", system, (raw >> 12) & 0x3, raw & 0x0FFF)
+}
+
+fn read_unsigned(data: &[u8], length: usize) -> Option<u64> {
+    let bytes = data.get(..length)?;
+    Some(bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+}
+
+/// Printable ASCII with padding (NUL, 0xFF, spaces) removed
+fn ascii_text(data: &[u8]) -> String {
+    data.iter()
+        .filter(|b| b.is_ascii_graphic() || **b == b' ')
+        .map(|&b| b as char)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn integer_signal(name: String, raw: u64) -> DecodedSignal {
+    DecodedSignal {
+        name: name.into(),
+        value: SignalValue::Integer(raw as i64),
+        unit: None,
+        value_description: None,
+        raw_value: raw as i64,
+    }
+}
+
+fn scaled_signal(def: &PidDefinition, raw: u64) -> DecodedSignal {
+    let value = if def.factor == 1.0 && def.offset == 0.0 {
+        SignalValue::Integer(raw as i64)
+    } else {
+        SignalValue::Float(raw as f64 * def.factor + def.offset)
+    };
+
+    DecodedSignal {
+        name: def.name.into(),
+        value,
+        unit: def.unit.map(Into::into),
+        value_description: None,
+        raw_value: raw as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value_of<'a>(signals: &'a [DecodedSignal], name: &str) -> &'a SignalValue {
+        &signals.iter().find(|s| &*s.name == name).unwrap().value
+    }
+
+    #[test]
+    fn test_mode_01_multiple_pids() {
+        // RPM 0x1AF8 / 4 = 1726, speed 88 km/h, coolant 0x7B - 40 = 83 °C
+        let (name, signals) = decode_response(&[0x41, 0x0C, 0x1A, 0xF8, 0x0D, 0x58, 0x05, 0x7B]).unwrap();
+        assert_eq!(name, "OBD2_CurrentData");
+        assert_eq!(value_of(&signals, "EngineSpeed"), &SignalValue::Float(1726.0));
+        assert_eq!(value_of(&signals, "VehicleSpeed"), &SignalValue::Integer(88));
+        assert_eq!(value_of(&signals, "EngineCoolantTemperature"), &SignalValue::Float(83.0));
+
+        let (_, signals) = decode_response(&[0x41, 0x01, 0x83, 0x07, 0xE5, 0x00]).unwrap();
+        assert_eq!(value_of(&signals, "MIL"), &SignalValue::Boolean(true));
+        assert_eq!(value_of(&signals, "DTCCount"), &SignalValue::Integer(3));
+
+        // Freeze frame carries the frame number after the PID
+        let (name, signals) = decode_response(&[0x42, 0x06, 0x00, 0x80]).unwrap();
+        assert_eq!(name, "OBD2_FreezeFrame");
+        assert_eq!(value_of(&signals, "ShortTermFuelTrimBank1"), &SignalValue::Float(0.0));
+    }
+
+    #[test]
+    fn test_dtcs_and_vin() {
+        let (name, signals) = decode_response(&[0x43, 0x02, 0x01, 0x23, 0xC1, 0x55]).unwrap();
+        assert_eq!(name, "OBD2_StoredDTCs");
+        assert_eq!(signals[1].value_description.as_deref(), Some("P0123"));
+        assert_eq!(signals[2].value_description.as_deref(), Some("U0155"));
+
+        let mut vin = vec![0x49, 0x02, 0x01];
+        vin.extend_from_slice(b"1G1JC5444R7252367");
+        let (name, signals) = decode_response(&vin).unwrap();
+        assert_eq!(name, "OBD2_VehicleInfo");
+        assert_eq!(signals[0].value_description.as_deref(), Some("1G1JC5444R7252367"));
+    }
+
+    #[test]
+    fn test_ignores_requests_and_uds() {
+        assert!(decode_response(&[0x01, 0x0C]).is_none());
+        assert!(decode_response(&[0x62, 0xF1, 0x90, 0x00]).is_none());
+        assert!(decode_response(&[0x7F, 0x01, 0x12]).is_none());
+    }
+}
@@ -4,17 +4,34 @@
 //! Uses the autosar-data crate for robust AUTOSAR 4.x support.
 
 use crate::signals::database::{
-    ByteOrder, ContainedPduInfo, ContainerDefinition, ContainerLayout, MessageDefinition,
-    MultiplexerInfo, SignalDefinition, ValueType,
+    ByteOrder, ContainedPduInfo, ContainerDefinition, ContainerLayout,
+    ConversionScale, MessageDefinition, MultiplexerInfo, RationalScale, SecuredPduDefinition,
+    SignalConversion, SignalDefinition, ValueType,
 };
 use crate::types::{ContainerType, DecoderError, Result};
 use autosar_data::*;
 use std::path::Path;
 
-/// Parse an ARXML file and return message and container definitions
-pub fn parse_arxml_file(
-    path: &Path,
-) -> Result<(Vec<MessageDefinition>, Vec<ContainerDefinition>)> {
+/// Definitions parsed from an ARXML file
+#[derive(Debug, Default)]
+pub struct ArxmlDefinitions {
+    /// PDUs sent in their own CAN frame (`id` is the CAN ID)
+    pub messages: Vec<MessageDefinition>,
+    /// Container I-PDUs
+    pub containers: Vec<ContainerDefinition>,
+    /// I-PDUs only sent inside containers (`id` is the container header ID)
+    pub contained_pdus: Vec<MessageDefinition>,
+    /// Container I-PDUs nested in other containers (`id` is the header ID)
+    pub contained_containers: Vec<ContainerDefinition>,
+    /// Secured I-PDUs (SecOC) and the authentic PDU they carry
+    pub secured_pdus: Vec<SecuredPduDefinition>,
+    /// Short names of the CAN clusters and physical channels each frame PDU
+    /// is triggered on, by PDU name
+    pub pdu_clusters: std::collections::HashMap<String, Vec<String>>,
+}
+
+/// Parse an ARXML file and return message, container, contained and secured PDU definitions
+pub fn parse_arxml_file(path: &Path) -> Result<ArxmlDefinitions> {
     log::info!("Parsing ARXML file with autosar-data: {:?}", path);
 
     if !path.exists() {
@@ -46,22 +63,25 @@ pub fn parse_arxml_file(
     parser.parse()?;
 
     log::info!(
-        "ARXML parsing complete: {} messages, {} containers",
-        parser.messages.len(),
-        parser.containers.len()
+        "ARXML parsing complete: {} messages, {} containers, {} contained PDUs, {} secured PDUs",
+        parser.definitions.messages.len(),
+        parser.definitions.containers.len() + parser.definitions.contained_containers.len(),
+        parser.definitions.contained_pdus.len(),
+        parser.definitions.secured_pdus.len()
     );
 
-    Ok((parser.messages, parser.containers))
+    Ok(parser.definitions)
 }
 
 /// ARXML parser using autosar-data
 struct ArxmlParser {
     model: AutosarModel,
     source: String,
-    messages: Vec<MessageDefinition>,
-    containers: Vec<ContainerDefinition>,
+    definitions: ArxmlDefinitions,
     /// Lookup map: PDU name → CAN ID (built once for performance)
     pdu_to_can_id: std::collections::HashMap<String, u32>,
+    /// Paths of I-PDUs carried by containers or Secured I-PDUs
+    contained_pdu_paths: std::collections::HashSet<String>,
 }
 
 impl ArxmlParser {
@@ -69,9 +89,9 @@ impl ArxmlParser {
         Self {
             model,
             source,
-            messages: Vec::new(),
-            containers: Vec::new(),
+            definitions: ArxmlDefinitions::default(),
             pdu_to_can_id: std::collections::HashMap::new(),
+            contained_pdu_paths: std::collections::HashSet::new(),
         }
     }
 
@@ -79,6 +99,7 @@ impl ArxmlParser {
         // PERFORMANCE FIX: Build PDU-to-CAN-ID lookup map once (O(n) instead of O(n²))
         self.build_pdu_to_can_id_map()?;
         log::info!("Built PDU-to-CAN-ID map with {} entries", self.pdu_to_can_id.len());
+        self.collect_contained_pdu_paths()?;
 
         // Iterate through all elements in the model
         for (_depth, element) in self.model.elements_dfs() {
@@ -86,7 +107,10 @@ impl ArxmlParser {
             match element_name {
                 ElementName::ISignalIPdu => {
                     match self.parse_i_signal_i_pdu(&element) {
-                        Ok(Some(msg)) => self.messages.push(msg),
+                        Ok(Some(msg)) if self.pdu_to_can_id.contains_key(&msg.name) => {
+                            self.definitions.messages.push(msg)
+                        }
+                        Ok(Some(pdu)) => self.definitions.contained_pdus.push(pdu),
                         Ok(None) => {}, // Skipped (no CAN ID, etc)
                         Err(e) => {
                             log::warn!("Failed to parse I-SIGNAL-I-PDU: {} (continuing...)", e);
@@ -95,7 +119,7 @@ impl ArxmlParser {
                 }
                 ElementName::MultiplexedIPdu => {
                     match self.parse_multiplexed_i_pdu(&element) {
-                        Ok(Some(msg)) => self.messages.push(msg),
+                        Ok(Some(msg)) => self.definitions.messages.push(msg),
                         Ok(None) => {},
                         Err(e) => {
                             log::warn!("Failed to parse MULTIPLEXED-I-PDU: {} (continuing...)", e);
@@ -104,13 +128,25 @@ impl ArxmlParser {
                 }
                 ElementName::ContainerIPdu => {
                     match self.parse_container_i_pdu(&element) {
-                        Ok(Some(container)) => self.containers.push(container),
+                        Ok(Some(container)) if self.pdu_to_can_id.contains_key(&container.name) => {
+                            self.definitions.containers.push(container)
+                        }
+                        Ok(Some(container)) => self.definitions.contained_containers.push(container),
                         Ok(None) => {},
                         Err(e) => {
                             log::warn!("Failed to parse CONTAINER-I-PDU: {} (continuing...)", e);
                         }
                     }
                 }
+                ElementName::SecuredIPdu => {
+                    match self.parse_secured_i_pdu(&element) {
+                        Ok(Some(secured_pdu)) => self.definitions.secured_pdus.push(secured_pdu),
+                        Ok(None) => {},
+                        Err(e) => {
+                            log::warn!("Failed to parse SECURED-I-PDU: {} (continuing...)", e);
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -118,14 +154,86 @@ impl ArxmlParser {
         Ok(())
     }
 
+    /// Collect the I-PDUs carried by containers and Secured I-PDUs
+    ///
+    /// CONTAINER-I-PDU → CONTAINED-PDU-TRIGGERING-REF → PDU-TRIGGERING → I-PDU-REF
+    /// SECURED-I-PDU → PAYLOAD-REF → PDU-TRIGGERING → I-PDU-REF
+    fn collect_contained_pdu_paths(&mut self) -> Result<()> {
+        let mut paths = std::collections::HashSet::new();
+        for (_depth, element) in self.model.elements_dfs() {
+            let pdu_triggerings = match element.element_name() {
+                ElementName::ContainerIPdu => self.contained_pdu_triggerings(&element)?,
+                ElementName::SecuredIPdu => self.resolve_reference(&element, "PAYLOAD-REF")?.into_iter().collect(),
+                _ => continue,
+            };
+            for pdu_triggering in pdu_triggerings {
+                if let Some(ipdu_path) = self.get_sub_element_text(&pdu_triggering, "I-PDU-REF")? {
+                    paths.insert(ipdu_path);
+                }
+            }
+        }
+        self.contained_pdu_paths = paths;
+        Ok(())
+    }
+
+    /// Resolve the CONTAINED-PDU-TRIGGERING-REFs of a container
+    fn contained_pdu_triggerings(&self, container: &Element) -> Result<Vec<Element>> {
+        let mut triggerings = Vec::new();
+        if let Some(refs_element) = self.find_sub_element(container, "CONTAINED-PDU-TRIGGERING-REFS")? {
+            for ref_element in self.find_all_sub_elements(&refs_element, "CONTAINED-PDU-TRIGGERING-REF")? {
+                let Some(path) = ref_element.character_data().and_then(|c| c.string_value()) else {
+                    continue;
+                };
+                match self.model.get_element_by_path(&path) {
+                    Some(pdu_triggering) => triggerings.push(pdu_triggering),
+                    None => log::warn!("Contained PDU-TRIGGERING not found: {}", path),
+                }
+            }
+        }
+        Ok(triggerings)
+    }
+
+    /// Header ID of a contained I-PDU (CONTAINED-I-PDU-PROPS)
+    ///
+    /// `long_header` selects HEADER-ID-LONG-HEADER over HEADER-ID-SHORT-HEADER.
+    fn contained_header_id(&self, ipdu: &Element, long_header: bool) -> Result<Option<u32>> {
+        let Some(props) = self.find_sub_element(ipdu, "CONTAINED-I-PDU-PROPS")? else {
+            return Ok(None);
+        };
+        let name = if long_header { "HEADER-ID-LONG-HEADER" } else { "HEADER-ID-SHORT-HEADER" };
+        Ok(self
+            .get_sub_element_text(&props, name)?
+            .and_then(|text| self.parse_can_id(&text)))
+    }
+
+    /// CAN ID of a PDU sent in its own frame, or header ID of a contained PDU
+    ///
+    /// `None` if the PDU is neither mapped to a frame nor carried by a container.
+    fn pdu_identifier(&self, element: &Element, name: &str) -> Result<Option<u32>> {
+        if let Some(&id) = self.pdu_to_can_id.get(name) {
+            return Ok(Some(id));
+        }
+        if !self.contained_pdu_paths.contains(&element.path().unwrap_or_default()) {
+            return Ok(None);
+        }
+        let id = match self.contained_header_id(element, false)? {
+            Some(id) => id,
+            None => self.contained_header_id(element, true)?.unwrap_or(0),
+        };
+        Ok(Some(id))
+    }
+
     /// Build a lookup map of PDU name → CAN ID by scanning all CAN-FRAME-TRIGGERINGs once
     ///
     /// AUTOSAR structure:
     /// CAN-FRAME-TRIGGERING → IDENTIFIER (CAN ID) + FRAME-REF
     /// CAN-FRAME → PDU-TO-FRAME-MAPPING → PDU-REF (PDU name)
     fn build_pdu_to_can_id_map(&mut self) -> Result<()> {
-        // Step 1: Build FRAME-REF → CAN-ID map from CAN-FRAME-TRIGGERINGs
+        // Step 1: Build FRAME-REF → CAN-ID map from CAN-FRAME-TRIGGERINGs,
+        // remembering the cluster/physical channel of each triggering
         let mut frame_to_can_id = std::collections::HashMap::new();
+        let mut frame_to_clusters: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
 
         for (_depth, element) in self.model.elements_dfs() {
             if element.element_name() == ElementName::CanFrameTriggering {
@@ -138,6 +246,10 @@ impl ArxmlParser {
                                 let frame_path = ref_text.string_value().unwrap_or_default();
                                 log::debug!("Found CAN-FRAME-TRIGGERING: CAN-ID={}, FRAME-REF={}", can_id, frame_path);
                                 frame_to_can_id.insert(frame_path.clone(), can_id);
+                                frame_to_clusters
+                                    .entry(frame_path)
+                                    .or_default()
+                                    .extend(self.cluster_names(&element));
                             }
                         }
                     }
@@ -165,11 +277,18 @@ impl ArxmlParser {
                                 if let Some(pdu_ref) = self.find_sub_element(&mapping, "PDU-REF")? {
                                     if let Some(ref_text) = pdu_ref.character_data() {
                                         let pdu_path = ref_text.string_value().unwrap_or_default();
-                                        let pdu_name = pdu_path.split('/').last().unwrap_or("");
+                                        let pdu_name = pdu_path.split('/').next_back().unwrap_or("");
 
                                         if !pdu_name.is_empty() {
                                             log::debug!("Mapping PDU {} to CAN-ID {}", pdu_name, can_id);
                                             self.pdu_to_can_id.insert(pdu_name.to_string(), can_id);
+                                            if let Some(clusters) = frame_to_clusters.get(&frame_path) {
+                                                self.definitions
+                                                    .pdu_clusters
+                                                    .entry(pdu_name.to_string())
+                                                    .or_default()
+                                                    .extend(clusters.iter().cloned());
+                                            }
                                         }
                                     }
                                 }
@@ -185,6 +304,19 @@ impl ArxmlParser {
         Ok(())
     }
 
+    /// Short names of the CAN-PHYSICAL-CHANNEL and CAN-CLUSTER enclosing `element`
+    fn cluster_names(&self, element: &Element) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut current = element.parent().ok().flatten();
+        while let Some(parent) = current {
+            if matches!(parent.element_name(), ElementName::CanPhysicalChannel | ElementName::CanCluster) {
+                names.extend(parent.item_name());
+            }
+            current = parent.parent().ok().flatten();
+        }
+        names
+    }
+
     fn parse_i_signal_i_pdu(&self, element: &Element) -> Result<Option<MessageDefinition>> {
         // Get SHORT-NAME
         let name = self.get_short_name(element)?;
@@ -195,13 +327,11 @@ impl ArxmlParser {
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(8);
 
-        // Get CAN ID from lookup map (O(1) instead of O(n) DFS)
-        let can_id = match self.pdu_to_can_id.get(&name) {
-            Some(&id) => id,
-            None => {
-                log::warn!("No CAN ID found for I-PDU: {}", name);
-                return Ok(None);
-            }
+        // Get CAN ID from lookup map (O(1) instead of O(n) DFS); PDUs only
+        // sent in containers are identified by their header ID instead
+        let Some(can_id) = self.pdu_identifier(element, &name)? else {
+            log::warn!("No CAN ID found for I-PDU: {}", name);
+            return Ok(None);
         };
 
         // Parse signals
@@ -220,6 +350,10 @@ impl ArxmlParser {
             is_multiplexed: false,
             multiplexer_signal: None,
             source: self.source.clone(),
+            is_j1939: false,
+            attributes: std::collections::HashMap::new(),
+            comment: None,
+            channels: Vec::new(),
         }))
     }
 
@@ -266,7 +400,11 @@ impl ArxmlParser {
             max: (1u64 << selector_length) as f64 - 1.0,
             unit: None,
             value_table: None,
+            conversion: None,
             multiplexer_info: None,
+            attributes: std::collections::HashMap::new(),
+            comment: None,
+            extraction: None,
         });
 
         // Parse static part signals
@@ -274,7 +412,7 @@ impl ArxmlParser {
             for i_pdu_ref in self.find_all_sub_elements(&static_part, "I-PDU-REF")? {
                 if let Some(ref_text) = i_pdu_ref.character_data() {
                     let pdu_name = ref_text.string_value().unwrap_or_default();
-                    let pdu_short_name = pdu_name.split('/').last().unwrap_or("");
+                    let pdu_short_name = pdu_name.split('/').next_back().unwrap_or("");
 
                     if let Some(referenced_pdu) = self.find_element_by_short_name(pdu_short_name)? {
                         let mut static_signals = self.parse_signal_mappings(&referenced_pdu)?;
@@ -297,7 +435,7 @@ impl ArxmlParser {
                 for i_pdu_ref in self.find_all_sub_elements(&dynamic_alt, "I-PDU-REF")? {
                     if let Some(ref_text) = i_pdu_ref.character_data() {
                         let pdu_name = ref_text.string_value().unwrap_or_default();
-                        let pdu_short_name = pdu_name.split('/').last().unwrap_or("");
+                        let pdu_short_name = pdu_name.split('/').next_back().unwrap_or("");
 
                         if let Some(referenced_pdu) = self.find_element_by_short_name(pdu_short_name)? {
                             let mut dynamic_signals = self.parse_signal_mappings(&referenced_pdu)?;
@@ -307,6 +445,7 @@ impl ArxmlParser {
                                 signal.multiplexer_info = Some(MultiplexerInfo {
                                     multiplexer_signal: multiplexer_signal_name.clone(),
                                     multiplexer_values: vec![selector_value],
+                                    multiplexer_ranges: Vec::new(),
                                 });
                             }
 
@@ -330,6 +469,10 @@ impl ArxmlParser {
             is_multiplexed: true,
             multiplexer_signal: Some(multiplexer_signal_name),
             source: self.source.clone(),
+            is_j1939: false,
+            attributes: std::collections::HashMap::new(),
+            comment: None,
+            channels: Vec::new(),
         }))
     }
 
@@ -341,12 +484,10 @@ impl ArxmlParser {
             .and_then(|s| s.parse::<usize>().ok())
             .unwrap_or(64);
 
-        let can_id = match self.pdu_to_can_id.get(&name) {
-            Some(&id) => id,
-            None => {
-                log::warn!("No CAN ID found for container I-PDU: {}", name);
-                return Ok(None);
-            }
+        // Containers nested in another container are identified by their header ID
+        let Some(can_id) = self.pdu_identifier(element, &name)? else {
+            log::warn!("No CAN ID found for container I-PDU: {}", name);
+            return Ok(None);
         };
 
         // Get header type
@@ -364,8 +505,13 @@ impl ArxmlParser {
             (ContainerType::Static, 0)
         };
 
+        let unused_bit_pattern = self
+            .get_sub_element_text(element, "UNUSED-BIT-PATTERN")?
+            .and_then(|text| self.parse_can_id(&text))
+            .unwrap_or(0) as u8;
+
         // Parse contained PDU information from CONTAINED-PDU-TRIGGERING-REFS
-        let contained_pdus = self.parse_contained_pdus(element, length)?;
+        let contained_pdus = self.parse_contained_pdus(element, length, header_size == 8)?;
 
         let layout = match container_type {
             ContainerType::Dynamic => ContainerLayout::Dynamic {
@@ -397,6 +543,60 @@ impl ArxmlParser {
             name,
             container_type,
             layout,
+            unused_bit_pattern,
+            channels: Vec::new(),
+            source: self.source.clone(),
+        }))
+    }
+
+    /// Parse a SECURED-I-PDU (SecOC)
+    ///
+    /// SECURED-I-PDU → PAYLOAD-REF → PDU-TRIGGERING → I-PDU-REF is the authentic
+    /// PDU. The transmitted freshness value and authenticator lengths come from
+    /// SECURE-COMMUNICATION-PROPS, or from the referenced FRESHNESS-PROPS and
+    /// AUTHENTICATION-PROPS.
+    fn parse_secured_i_pdu(&self, element: &Element) -> Result<Option<SecuredPduDefinition>> {
+        let name = self.get_short_name(element)?;
+
+        let payload_path = match self.resolve_reference(element, "PAYLOAD-REF")? {
+            Some(pdu_triggering) => self.get_sub_element_text(&pdu_triggering, "I-PDU-REF")?,
+            None => None,
+        };
+        let Some(payload_path) = payload_path else {
+            log::warn!("No payload PDU found for secured I-PDU: {}", name);
+            return Ok(None);
+        };
+        let payload_pdu = payload_path.split('/').next_back().unwrap_or_default().to_string();
+
+        let secure_props = self.find_sub_element(element, "SECURE-COMMUNICATION-PROPS")?;
+        let tx_length = |length_name: &str, props_ref: &str| -> Result<u16> {
+            let mut length = match &secure_props {
+                Some(props) => self.get_sub_element_text(props, length_name)?,
+                None => None,
+            };
+            if length.is_none() {
+                if let Some(props) = self.resolve_reference(element, props_ref)? {
+                    length = self.get_sub_element_text(&props, length_name)?;
+                }
+            }
+            Ok(length.and_then(|s| s.parse::<u16>().ok()).unwrap_or(0))
+        };
+        let freshness_length = tx_length("FRESHNESS-VALUE-TX-LENGTH", "FRESHNESS-PROPS-REF")?;
+        let authenticator_length = tx_length("AUTH-INFO-TX-LENGTH", "AUTHENTICATION-PROPS-REF")?;
+
+        let header_size = match self.get_sub_element_text(element, "USE-SECURED-PDU-HEADER")?.as_deref() {
+            Some("SECURED-PDU-HEADER-08-BIT") => 1,
+            Some("SECURED-PDU-HEADER-16-BIT") => 2,
+            Some("SECURED-PDU-HEADER-32-BIT") => 4,
+            _ => 0,
+        };
+
+        Ok(Some(SecuredPduDefinition {
+            name,
+            payload_pdu,
+            header_size,
+            freshness_length,
+            authenticator_length,
             source: self.source.clone(),
         }))
     }
@@ -405,95 +605,86 @@ impl ArxmlParser {
     ///
     /// The structure is:
     /// CONTAINER-I-PDU → CONTAINED-PDU-TRIGGERING-REF → PDU-TRIGGERING → I-PDU-REF → I-SIGNAL-I-PDU
+    ///
+    /// The PDU ID is the HEADER-ID-SHORT-HEADER/HEADER-ID-LONG-HEADER of the
+    /// I-PDU's CONTAINED-I-PDU-PROPS, as sent in dynamic container headers.
+    /// Static containers take the byte OFFSET and UPDATE-INDICATION-BIT-POSITION
+    /// from the same props; PDUs without an OFFSET follow the previous one.
     fn parse_contained_pdus(
         &self,
         container_element: &Element,
         container_length: usize,
+        long_header: bool,
     ) -> Result<Vec<ContainedPduInfo>> {
-        use autosar_data::ElementName;
-
         let mut contained_pdus = Vec::new();
         let mut current_position = 0;
 
-        // Find CONTAINED-PDU-TRIGGERING-REFS
-        if let Some(refs_element) = self.find_sub_element(container_element, "CONTAINED-PDU-TRIGGERING-REFS")? {
-            // Get all CONTAINED-PDU-TRIGGERING-REF elements
-            for ref_element in self.find_all_sub_elements(&refs_element, "CONTAINED-PDU-TRIGGERING-REF")? {
-                if let Some(ref_text) = ref_element.character_data() {
-                    let pdu_triggering_path = ref_text.string_value().unwrap_or_default();
-
-                    // Find the PDU-TRIGGERING element by path
-                    if let Some(pdu_triggering) = self.find_element_by_path(&pdu_triggering_path)? {
-                        // Get I-PDU-REF from PDU-TRIGGERING
-                        if let Some(ipdu_ref) = self.find_sub_element(&pdu_triggering, "I-PDU-REF")? {
-                            if let Some(ipdu_ref_text) = ipdu_ref.character_data() {
-                                let ipdu_path = ipdu_ref_text.string_value().unwrap_or_default();
-                                let ipdu_name = ipdu_path.split('/').last().unwrap_or("Unknown");
-
-                                // Try to find the I-PDU to get its LENGTH
-                                let pdu_size = if let Some(ipdu_element) = self.find_element_by_path(&ipdu_path)? {
-                                    self.get_sub_element_text(&ipdu_element, "LENGTH")?
-                                        .and_then(|s| s.parse::<usize>().ok())
-                                        .unwrap_or(8) // Default to 8 bytes if not specified
-                                } else {
-                                    8 // Default size
-                                };
-
-                                // Generate a PDU ID from the name hash (since AUTOSAR doesn't have explicit PDU IDs)
-                                let pdu_id = Self::generate_pdu_id(ipdu_name);
-
-                                contained_pdus.push(ContainedPduInfo {
-                                    pdu_id,
-                                    name: ipdu_name.to_string(),
-                                    position: current_position,
-                                    size: pdu_size,
-                                });
+        for pdu_triggering in self.contained_pdu_triggerings(container_element)? {
+            // Get I-PDU-REF from PDU-TRIGGERING
+            let Some(ipdu_path) = self.get_sub_element_text(&pdu_triggering, "I-PDU-REF")? else {
+                continue;
+            };
+            let ipdu_name = ipdu_path.split('/').next_back().unwrap_or("Unknown");
+            let ipdu_element = self.model.get_element_by_path(&ipdu_path);
+
+            // Get the I-PDU's LENGTH, default to 8 bytes if not specified
+            let pdu_size = match &ipdu_element {
+                Some(ipdu) => self
+                    .get_sub_element_text(ipdu, "LENGTH")?
+                    .and_then(|s| s.parse::<usize>().ok())
+                    .unwrap_or(8),
+                None => 8,
+            };
+
+            let pdu_id = match &ipdu_element {
+                Some(ipdu) => self.contained_header_id(ipdu, long_header)?,
+                None => None,
+            };
+            let pdu_id = pdu_id.unwrap_or_else(|| {
+                log::debug!("No header ID for contained PDU {}", ipdu_name);
+                0
+            });
+
+            let props = match &ipdu_element {
+                Some(ipdu) => self.find_sub_element(ipdu, "CONTAINED-I-PDU-PROPS")?,
+                None => None,
+            };
+            let (offset, update_bit) = match &props {
+                Some(props) => (
+                    self.get_sub_element_text(props, "OFFSET")?
+                        .and_then(|s| s.parse::<usize>().ok()),
+                    self.get_sub_element_text(props, "UPDATE-INDICATION-BIT-POSITION")?
+                        .and_then(|s| s.parse::<u16>().ok()),
+                ),
+                None => (None, None),
+            };
+            let position = offset.unwrap_or(current_position);
+
+            // Check if the PDU fits into the container
+            if position + pdu_size > container_length {
+                log::warn!(
+                    "Contained PDU {} exceeds container length: {} > {}",
+                    ipdu_name,
+                    position + pdu_size,
+                    container_length
+                );
+                continue;
+            }
 
-                                current_position += pdu_size;
+            contained_pdus.push(ContainedPduInfo {
+                pdu_id,
+                name: ipdu_name.to_string(),
+                position,
+                size: pdu_size,
+                update_bit,
+            });
 
-                                // Check if we've exceeded container length
-                                if current_position > container_length {
-                                    log::warn!(
-                                        "Contained PDUs exceed container length: {} > {}",
-                                        current_position,
-                                        container_length
-                                    );
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            current_position = position + pdu_size;
         }
 
         Ok(contained_pdus)
     }
 
-    /// Generate a deterministic PDU ID from a PDU name using a simple hash
-    fn generate_pdu_id(name: &str) -> u32 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        name.hash(&mut hasher);
-        (hasher.finish() & 0xFFFF) as u32 // Use lower 16 bits for PDU ID
-    }
-
-    /// Find an element by its AUTOSAR path
-    fn find_element_by_path(&self, path: &str) -> Result<Option<Element>> {
-        // Try to find element by matching the path
-        // This is a simplified implementation - in a real parser you'd navigate the AR model properly
-        for (_depth, element) in self.model.elements_dfs() {
-            if let Ok(elem_path) = element.path() {
-                if elem_path == path {
-                    return Ok(Some(element));
-                }
-            }
-        }
-        Ok(None)
-    }
-
     fn parse_signal_mappings(&self, pdu_element: &Element) -> Result<Vec<SignalDefinition>> {
         let mut signals = Vec::new();
 
@@ -518,7 +709,7 @@ impl ArxmlParser {
         let signal_name = if let Some(i_signal_ref) = self.find_sub_element(mapping, "I-SIGNAL-REF")? {
             if let Some(ref_text) = i_signal_ref.character_data() {
                 let signal_path = ref_text.string_value().unwrap_or_default();
-                signal_path.split('/').last().unwrap_or("Unknown").to_string()
+                signal_path.split('/').next_back().unwrap_or("Unknown").to_string()
             } else {
                 log::warn!("I-SIGNAL-REF has no character data, skipping mapping");
                 return Ok(None);
@@ -543,183 +734,293 @@ impl ArxmlParser {
             ByteOrder::LittleEndian
         };
 
-        // Try to get I-SIGNAL reference to find signal properties
-        let (length, factor, offset, unit, min, max) = if let Some(i_signal_ref) =
-            self.find_sub_element(mapping, "I-SIGNAL-REF")?
-        {
-            if let Some(ref_text) = i_signal_ref.character_data() {
-                let signal_path = ref_text.string_value().unwrap_or_default();
-                let signal_short_name = signal_path.split('/').last().unwrap_or("");
-
-                if let Some(i_signal) = self.find_element_by_short_name(signal_short_name)? {
-                    let len = self
-                        .get_sub_element_text(&i_signal, "LENGTH")?
-                        .and_then(|s| s.parse::<u16>().ok())
-                        .unwrap_or(8);
-
-                    // Parse SYSTEM-SIGNAL-REF for physical value conversion
-                    let (factor, offset, unit, min, max) = self.parse_system_signal(&i_signal, len)?;
-
-                    (len, factor, offset, unit, min, max)
-                } else {
-                    let default_max = (1u64 << 8) as f64 - 1.0;
-                    (8, 1.0, 0.0, None, 0.0, default_max)
-                }
-            } else {
-                let default_max = (1u64 << 8) as f64 - 1.0;
-                (8, 1.0, 0.0, None, 0.0, default_max)
+        // Resolve the I-SIGNAL for length, base type and conversion
+        let properties = match self.resolve_reference(mapping, "I-SIGNAL-REF")? {
+            Some(i_signal) => {
+                let length = self
+                    .get_sub_element_text(&i_signal, "LENGTH")?
+                    .and_then(|s| s.parse::<u16>().ok())
+                    .unwrap_or(8);
+                self.parse_signal_properties(&i_signal, length)?
             }
-        } else {
-            let default_max = (1u64 << 8) as f64 - 1.0;
-            (8, 1.0, 0.0, None, 0.0, default_max)
+            None => SignalProperties::raw(8),
         };
 
         Ok(Some(SignalDefinition {
             name: signal_name,
             start_bit: start_position,
-            length,
+            length: properties.length,
             byte_order,
-            value_type: ValueType::Unsigned,
-            factor,
-            offset,
-            min,
-            max,
-            unit,
-            value_table: None,
+            value_type: properties.value_type,
+            factor: properties.factor,
+            offset: properties.offset,
+            min: properties.min,
+            max: properties.max,
+            unit: properties.unit,
+            value_table: properties.value_table,
+            conversion: properties.conversion,
             multiplexer_info: None,
+            attributes: std::collections::HashMap::new(),
+            comment: None,
+            extraction: None,
         }))
     }
 
-    /// Parse SYSTEM-SIGNAL for physical value conversion (factor, offset, unit, min, max)
-    fn parse_system_signal(&self, i_signal: &Element, bit_length: u16) -> Result<(f64, f64, Option<String>, f64, f64)> {
-        // Default values if SYSTEM-SIGNAL not found
-        let default_max = (1u64 << bit_length) as f64 - 1.0;
-        let mut factor = 1.0;
-        let mut offset = 0.0;
-        let mut unit = None;
-        let mut min = 0.0;
-        let mut max = default_max;
-
-        // Find SYSTEM-SIGNAL-REF
-        if let Some(sys_signal_ref) = self.find_sub_element(i_signal, "SYSTEM-SIGNAL-REF")? {
-            if let Some(ref_text) = sys_signal_ref.character_data() {
-                let sys_signal_path = ref_text.string_value().unwrap_or_default();
-                let sys_signal_name = sys_signal_path.split('/').last().unwrap_or("");
-
-                // Find the SYSTEM-SIGNAL element
-                if let Some(system_signal) = self.find_element_by_short_name(sys_signal_name)? {
-                    // Parse UNIT-REF (optional)
-                    if let Some(unit_ref) = self.find_sub_element(&system_signal, "UNIT-REF")? {
-                        if let Some(unit_text) = unit_ref.character_data() {
-                            let unit_path = unit_text.string_value().unwrap_or_default();
-                            let unit_name = unit_path.split('/').last().unwrap_or("");
-                            if !unit_name.is_empty() {
-                                unit = Some(unit_name.to_string());
-                            }
-                        }
-                    }
+    /// Parse the coding of an I-SIGNAL: base type and physical value conversion
+    ///
+    /// BASE-TYPE-REF and COMPU-METHOD-REF are taken from the I-SIGNAL's
+    /// NETWORK-REPRESENTATION-PROPS, falling back to the PHYSICAL-PROPS of the
+    /// SYSTEM-SIGNAL.
+    fn parse_signal_properties(&self, i_signal: &Element, bit_length: u16) -> Result<SignalProperties> {
+        let mut properties = SignalProperties::raw(bit_length);
+
+        let system_signal = self.resolve_reference(i_signal, "SYSTEM-SIGNAL-REF")?;
+        let network_props = self.sw_data_def_props(i_signal, "NETWORK-REPRESENTATION-PROPS")?;
+        let physical_props = match &system_signal {
+            Some(system_signal) => self.sw_data_def_props(system_signal, "PHYSICAL-PROPS")?,
+            None => None,
+        };
+        let props: Vec<&Element> = network_props.iter().chain(physical_props.iter()).collect();
+
+        // Signedness / IEEE float from the SW-BASE-TYPE encoding
+        let mut base_type = None;
+        for props in &props {
+            base_type = self.resolve_reference(props, "BASE-TYPE-REF")?;
+            if base_type.is_some() {
+                break;
+            }
+        }
+        if let Some(base_type) = base_type {
+            let encoding = self.get_sub_element_text(&base_type, "BASE-TYPE-ENCODING")?;
+            properties.value_type = match (encoding.as_deref(), bit_length) {
+                (Some("2C"), _) => ValueType::Signed,
+                (Some("IEEE754"), 32) => ValueType::Float32,
+                (Some("IEEE754"), 64) => ValueType::Float64,
+                _ => ValueType::Unsigned,
+            };
+        }
+        if properties.value_type == ValueType::Signed {
+            let half_range = (1u64 << (bit_length.clamp(1, 64) - 1)) as f64;
+            properties.min = -half_range;
+            properties.max = half_range - 1.0;
+        }
 
-                    // Parse COMPU-METHOD for factor/offset
-                    if let Some((f, o, mi, ma)) = self.parse_compu_method(&system_signal)? {
-                        factor = f;
-                        offset = o;
-                        if mi.is_finite() {
-                            min = mi;
-                        }
-                        if ma.is_finite() {
-                            max = ma;
-                        }
-                    }
+        // COMPU-METHOD: data def props first, then inline/referenced on the SYSTEM-SIGNAL
+        let mut compu_method = None;
+        for props in &props {
+            compu_method = self.resolve_reference(props, "COMPU-METHOD-REF")?;
+            if compu_method.is_some() {
+                break;
+            }
+        }
+        if compu_method.is_none() {
+            if let Some(system_signal) = &system_signal {
+                compu_method = match self.find_sub_element(system_signal, "COMPU-METHOD")? {
+                    Some(inline) => Some(inline),
+                    None => self.resolve_reference(system_signal, "COMPU-METHOD-REF")?,
+                };
+            }
+        }
+
+        // Unit: SYSTEM-SIGNAL UNIT-REF, then data def props, then COMPU-METHOD
+        let mut unit_refs: Vec<&Element> = system_signal.iter().chain(props.iter().copied()).collect();
+        unit_refs.extend(compu_method.iter());
+        for element in unit_refs {
+            if let Some(unit_path) = self.get_sub_element_text(element, "UNIT-REF")? {
+                let unit_name = unit_path.split('/').next_back().unwrap_or("");
+                if !unit_name.is_empty() {
+                    properties.unit = Some(unit_name.to_string());
+                    break;
                 }
             }
         }
 
-        Ok((factor, offset, unit, min, max))
+        if let Some(compu_method) = compu_method {
+            self.parse_compu_method(&compu_method, &mut properties)?;
+        }
+
+        Ok(properties)
     }
 
-    /// Parse COMPU-METHOD to extract factor, offset, min, max
-    /// Returns (factor, offset, min, max)
-    fn parse_compu_method(&self, system_signal: &Element) -> Result<Option<(f64, f64, f64, f64)>> {
-        // Navigate to COMPU-METHOD (can be inline or referenced)
-        let compu_method = if let Some(inline) = self.find_sub_element(system_signal, "COMPU-METHOD")? {
-            Some(inline)
-        } else if let Some(compu_ref) = self.find_sub_element(system_signal, "COMPU-METHOD-REF")? {
-            // Follow reference
-            if let Some(ref_text) = compu_ref.character_data() {
-                let compu_path = ref_text.string_value().unwrap_or_default();
-                let compu_name = compu_path.split('/').last().unwrap_or("");
-                self.find_element_by_short_name(compu_name)?
-            } else {
-                None
-            }
-        } else {
-            None
+    /// Get the first SW-DATA-DEF-PROPS-CONDITIONAL below a props element
+    /// (e.g. NETWORK-REPRESENTATION-PROPS)
+    fn sw_data_def_props(&self, element: &Element, props_name: &str) -> Result<Option<Element>> {
+        let Some(props) = self.find_sub_element(element, props_name)? else {
+            return Ok(None);
+        };
+        let Some(variants) = self.find_sub_element(&props, "SW-DATA-DEF-PROPS-VARIANTS")? else {
+            return Ok(None);
         };
+        self.find_sub_element(&variants, "SW-DATA-DEF-PROPS-CONDITIONAL")
+    }
 
-        if let Some(compu) = compu_method {
-            // Parse COMPU-INTERNAL-TO-PHYS → COMPU-SCALES → COMPU-SCALE
-            if let Some(internal_to_phys) = self.find_sub_element(&compu, "COMPU-INTERNAL-TO-PHYS")? {
-                if let Some(compu_scales) = self.find_sub_element(&internal_to_phys, "COMPU-SCALES")? {
-                    // Get first COMPU-SCALE (typically linear scaling)
-                    let scales = self.find_all_sub_elements(&compu_scales, "COMPU-SCALE")?;
-                    if let Some(scale) = scales.first() {
-                        let mut factor = 1.0;
-                        let mut offset = 0.0;
-                        let mut min = f64::NEG_INFINITY;
-                        let mut max = f64::INFINITY;
-
-                        // Parse LOWER-LIMIT and UPPER-LIMIT
-                        if let Some(lower_text) = self.get_sub_element_text(scale, "LOWER-LIMIT")? {
-                            if let Ok(val) = lower_text.parse::<f64>() {
-                                min = val;
-                            }
-                        }
-                        if let Some(upper_text) = self.get_sub_element_text(scale, "UPPER-LIMIT")? {
-                            if let Ok(val) = upper_text.parse::<f64>() {
-                                max = val;
-                            }
-                        }
+    /// Apply a COMPU-METHOD to the signal properties
+    ///
+    /// Every COMPU-SCALE is read: COMPU-CONST/VT entries become the value table,
+    /// first-order COMPU-RATIONAL-COEFFS become linear scales. A single linear
+    /// scale sets factor/offset, several are kept as a piecewise linear
+    /// conversion. Higher-order coefficients or a non-constant denominator
+    /// turn the whole method into a rational conversion.
+    fn parse_compu_method(&self, compu: &Element, properties: &mut SignalProperties) -> Result<()> {
+        let Some(internal_to_phys) = self.find_sub_element(compu, "COMPU-INTERNAL-TO-PHYS")? else {
+            return Ok(());
+        };
+        let Some(compu_scales) = self.find_sub_element(&internal_to_phys, "COMPU-SCALES")? else {
+            return Ok(());
+        };
 
-                        // Parse COMPU-RATIONAL-COEFFS (linear: y = (a0 + a1*x) / (b0 + b1*x))
-                        // Simplified for linear case: y = offset + factor * x
-                        if let Some(rational) = self.find_sub_element(scale, "COMPU-RATIONAL-COEFFS")? {
-                            if let Some(numerator) = self.find_sub_element(&rational, "COMPU-NUMERATOR")? {
-                                let v_elems = self.find_all_sub_elements(&numerator, "V")?;
-                                if v_elems.len() >= 2 {
-                                    // v[0] = offset (a0), v[1] = factor (a1)
-                                    if let Some(v0_text) = v_elems[0].character_data() {
-                                        if let Ok(val) = v0_text.string_value().unwrap_or_default().parse::<f64>() {
-                                            offset = val;
-                                        }
-                                    }
-                                    if let Some(v1_text) = v_elems[1].character_data() {
-                                        if let Ok(val) = v1_text.string_value().unwrap_or_default().parse::<f64>() {
-                                            factor = val;
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        let mut scales = Vec::new();
+        let mut rational_scales = Vec::new();
+        let mut value_table = std::collections::HashMap::new();
+
+        for scale in self.find_all_sub_elements(&compu_scales, "COMPU-SCALE")? {
+            let lower = self
+                .get_sub_element_text(&scale, "LOWER-LIMIT")?
+                .and_then(|s| s.trim().parse::<f64>().ok());
+            let upper = self
+                .get_sub_element_text(&scale, "UPPER-LIMIT")?
+                .and_then(|s| s.trim().parse::<f64>().ok());
+
+            // COMPU-RATIONAL-COEFFS: y = (n0 + n1*x + ...) / (d0 + d1*x + ...)
+            if let Some(rational) = self.find_sub_element(&scale, "COMPU-RATIONAL-COEFFS")? {
+                let numerator = self.coefficients(&rational, "COMPU-NUMERATOR")?;
+                let denominator = self.coefficients(&rational, "COMPU-DENOMINATOR")?;
+                let lower_limit = lower.unwrap_or(f64::NEG_INFINITY);
+                let upper_limit = upper.unwrap_or(f64::INFINITY);
+
+                match (numerator.as_slice(), denominator.as_slice()) {
+                    (&[] | &[_] | &[_, _], &[] | &[_]) => {
+                        let d0 = denominator.first().copied().filter(|d| *d != 0.0).unwrap_or(1.0);
+                        scales.push(ConversionScale {
+                            lower_limit,
+                            upper_limit,
+                            factor: numerator.get(1).copied().unwrap_or(0.0) / d0,
+                            offset: numerator.first().copied().unwrap_or(0.0) / d0,
+                        });
+                    }
+                    _ => rational_scales.push(RationalScale {
+                        lower_limit,
+                        upper_limit,
+                        numerator,
+                        denominator,
+                    }),
+                }
+                continue;
+            }
 
-                        // Parse COMPU-CONST (constant offset, no scaling)
-                        if let Some(compu_const) = self.find_sub_element(scale, "COMPU-CONST")? {
-                            if let Some(vt) = self.find_sub_element(&compu_const, "VT")? {
-                                if let Some(vt_text) = vt.character_data() {
-                                    if let Ok(val) = vt_text.string_value().unwrap_or_default().parse::<f64>() {
-                                        offset = val;
-                                        factor = 0.0; // Constant value
-                                    }
-                                }
-                            }
+            // COMPU-CONST: text for TEXTTABLE, numeric constant otherwise
+            if let Some(compu_const) = self.find_sub_element(&scale, "COMPU-CONST")? {
+                if let Some(vt) = self.get_sub_element_text(&compu_const, "VT")? {
+                    let (Some(lower), Some(upper)) = (lower, upper.or(lower)) else {
+                        log::warn!("COMPU-SCALE '{}' without limits, skipping", vt);
+                        continue;
+                    };
+                    if let Ok(constant) = vt.trim().parse::<f64>() {
+                        scales.push(ConversionScale {
+                            lower_limit: lower,
+                            upper_limit: upper,
+                            factor: 0.0,
+                            offset: constant,
+                        });
+                    } else if upper - lower < MAX_TEXTTABLE_RANGE {
+                        for raw in (lower.ceil() as i64)..=(upper.floor() as i64) {
+                            value_table.insert(raw, vt.clone());
                         }
+                    } else {
+                        log::warn!(
+                            "TEXTTABLE range {}..{} for '{}' too large, skipping",
+                            lower,
+                            upper,
+                            vt
+                        );
+                    }
+                }
+            }
+        }
 
-                        return Ok(Some((factor, offset, min, max)));
+        if !value_table.is_empty() {
+            properties.value_table = Some(value_table);
+        }
+
+        // Linear scales alongside rational ones become first-order rational scales
+        if !rational_scales.is_empty() {
+            rational_scales.extend(scales.into_iter().map(RationalScale::from));
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for scale in &rational_scales {
+                for limit in [scale.lower_limit, scale.upper_limit] {
+                    let value = scale.evaluate(limit);
+                    if value.is_finite() {
+                        min = min.min(value);
+                        max = max.max(value);
                     }
                 }
             }
+            if min <= max {
+                properties.min = min;
+                properties.max = max;
+            }
+            properties.conversion = Some(SignalConversion::Rational(rational_scales));
+            return Ok(());
         }
 
-        Ok(None)
+        // Physical range covered by the linear scales
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for scale in &scales {
+            for limit in [scale.lower_limit, scale.upper_limit] {
+                if limit.is_finite() {
+                    let value = scale.offset + scale.factor * limit;
+                    min = min.min(value);
+                    max = max.max(value);
+                }
+            }
+        }
+        if min <= max {
+            properties.min = min;
+            properties.max = max;
+        }
+
+        match scales.len() {
+            0 => {}
+            1 => {
+                properties.factor = scales[0].factor;
+                properties.offset = scales[0].offset;
+            }
+            _ => {
+                properties.factor = scales[0].factor;
+                properties.offset = scales[0].offset;
+                properties.conversion = Some(SignalConversion::PiecewiseLinear(scales));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the V values of a COMPU-NUMERATOR/COMPU-DENOMINATOR
+    fn coefficients(&self, rational: &Element, name: &str) -> Result<Vec<f64>> {
+        let Some(element) = self.find_sub_element(rational, name)? else {
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .find_all_sub_elements(&element, "V")?
+            .iter()
+            .filter_map(|v| v.character_data())
+            .filter_map(|v| v.string_value())
+            .filter_map(|v| v.trim().parse::<f64>().ok())
+            .collect())
+    }
+
+    /// Follow a reference sub-element (e.g. SYSTEM-SIGNAL-REF) to its target
+    ///
+    /// Falls back to a SHORT-NAME search when the path can't be resolved.
+    fn resolve_reference(&self, element: &Element, ref_name: &str) -> Result<Option<Element>> {
+        let Some(path) = self.get_sub_element_text(element, ref_name)? else {
+            return Ok(None);
+        };
+        if let Some(target) = self.model.get_element_by_path(&path) {
+            return Ok(Some(target));
+        }
+        let short_name = path.split('/').next_back().unwrap_or("");
+        self.find_element_by_short_name(short_name)
     }
 
     fn parse_can_id(&self, text: &str) -> Option<u32> {
@@ -745,7 +1046,8 @@ impl ArxmlParser {
     fn get_sub_element_text(&self, element: &Element, name: &str) -> Result<Option<String>> {
         if let Some(sub_elem) = self.find_sub_element(element, name)? {
             if let Some(char_data) = sub_elem.character_data() {
-                return Ok(Some(char_data.string_value().unwrap_or_default()));
+                // Display covers enum values (HEADER-TYPE, PACKING-BYTE-ORDER) and numbers too
+                return Ok(Some(char_data.to_string()));
             }
         }
         Ok(None)
@@ -799,9 +1101,225 @@ impl ArxmlParser {
     }
 }
 
+/// TEXTTABLE ranges wider than this aren't expanded into the value table
+const MAX_TEXTTABLE_RANGE: f64 = 4096.0;
+
+/// Coding of an I-SIGNAL collected from base type and COMPU-METHOD
+struct SignalProperties {
+    length: u16,
+    value_type: ValueType,
+    factor: f64,
+    offset: f64,
+    min: f64,
+    max: f64,
+    unit: Option<String>,
+    value_table: Option<std::collections::HashMap<i64, String>>,
+    conversion: Option<SignalConversion>,
+}
+
+impl SignalProperties {
+    /// Unsigned raw value without conversion
+    fn raw(length: u16) -> Self {
+        Self {
+            length,
+            value_type: ValueType::Unsigned,
+            factor: 1.0,
+            offset: 0.0,
+            min: 0.0,
+            max: (1u64 << length.min(63)) as f64 - 1.0,
+            unit: None,
+            value_table: None,
+            conversion: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::signals::extraction::ExtractionPlan;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Write an ARXML file with `elements` in package `/Test` and
+    /// `channel` as the content of CAN physical channel `/Test/Can/Channel`
+    fn write_arxml(elements: &str, channel: &str) -> NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".arxml").tempfile().unwrap();
+        write!(
+            file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<AUTOSAR xmlns="http://autosar.org/schema/r4.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://autosar.org/schema/r4.0 AUTOSAR_00046.xsd">
+  <AR-PACKAGES>
+    <AR-PACKAGE>
+      <SHORT-NAME>Test</SHORT-NAME>
+      <ELEMENTS>
+{elements}
+        <CAN-CLUSTER>
+          <SHORT-NAME>Can</SHORT-NAME>
+          <CAN-CLUSTER-VARIANTS>
+            <CAN-CLUSTER-CONDITIONAL>
+              <PHYSICAL-CHANNELS>
+                <CAN-PHYSICAL-CHANNEL>
+                  <SHORT-NAME>Channel</SHORT-NAME>
+{channel}
+                </CAN-PHYSICAL-CHANNEL>
+              </PHYSICAL-CHANNELS>
+            </CAN-CLUSTER-CONDITIONAL>
+          </CAN-CLUSTER-VARIANTS>
+        </CAN-CLUSTER>
+      </ELEMENTS>
+    </AR-PACKAGE>
+  </AR-PACKAGES>
+</AUTOSAR>
+"#
+        )
+        .unwrap();
+        file
+    }
+
+    /// I-SIGNAL with a base type and COMPU-METHOD in its network representation
+    fn i_signal(name: &str, length: u16, base_type: &str, compu_method: &str) -> String {
+        format!(
+            r#"        <I-SIGNAL>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <LENGTH>{length}</LENGTH>
+          <NETWORK-REPRESENTATION-PROPS>
+            <SW-DATA-DEF-PROPS-VARIANTS>
+              <SW-DATA-DEF-PROPS-CONDITIONAL>
+                <BASE-TYPE-REF DEST="SW-BASE-TYPE">/Test/{base_type}</BASE-TYPE-REF>
+                <COMPU-METHOD-REF DEST="COMPU-METHOD">/Test/{compu_method}</COMPU-METHOD-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL>
+            </SW-DATA-DEF-PROPS-VARIANTS>
+          </NETWORK-REPRESENTATION-PROPS>
+          <SYSTEM-SIGNAL-REF DEST="SYSTEM-SIGNAL">/Test/Sys{name}</SYSTEM-SIGNAL-REF>
+        </I-SIGNAL>
+        <SYSTEM-SIGNAL>
+          <SHORT-NAME>Sys{name}</SHORT-NAME>
+        </SYSTEM-SIGNAL>
+"#
+        )
+    }
+
+    fn base_type(name: &str, size: u16, encoding: &str) -> String {
+        format!(
+            r#"        <SW-BASE-TYPE>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <CATEGORY>FIXED_LENGTH</CATEGORY>
+          <BASE-TYPE-SIZE>{size}</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>{encoding}</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+"#
+        )
+    }
+
+    /// COMPU-SCALE with either a text (`Err`) or linear coefficients (`Ok((v0, v1))`)
+    fn compu_scale(lower: i64, upper: i64, content: std::result::Result<(f64, f64), &str>) -> String {
+        let content = match content {
+            Ok((v0, v1)) => format!(
+                "<COMPU-RATIONAL-COEFFS><COMPU-NUMERATOR><V>{v0}</V><V>{v1}</V></COMPU-NUMERATOR>\
+                 <COMPU-DENOMINATOR><V>1</V></COMPU-DENOMINATOR></COMPU-RATIONAL-COEFFS>"
+            ),
+            Err(text) => format!("<COMPU-CONST><VT>{text}</VT></COMPU-CONST>"),
+        };
+        format!(
+            r#"<COMPU-SCALE><LOWER-LIMIT INTERVAL-TYPE="CLOSED">{lower}</LOWER-LIMIT><UPPER-LIMIT INTERVAL-TYPE="CLOSED">{upper}</UPPER-LIMIT>{content}</COMPU-SCALE>"#
+        )
+    }
+
+    fn compu_method(name: &str, category: &str, scales: &[String]) -> String {
+        format!(
+            r#"        <COMPU-METHOD>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <CATEGORY>{category}</CATEGORY>
+          <COMPU-INTERNAL-TO-PHYS><COMPU-SCALES>{}</COMPU-SCALES></COMPU-INTERNAL-TO-PHYS>
+        </COMPU-METHOD>
+"#,
+            scales.concat()
+        )
+    }
+
+    /// I-SIGNAL-I-PDU with its signals packed back to back; `props` is
+    /// inserted before the signal mappings (e.g. CONTAINED-I-PDU-PROPS)
+    fn i_pdu(name: &str, length: usize, props: &str, signals: &[(&str, u16)]) -> String {
+        let mut position = 0;
+        let mut mappings = String::new();
+        for (signal, bits) in signals {
+            mappings.push_str(&format!(
+                r#"<I-SIGNAL-TO-I-PDU-MAPPING><SHORT-NAME>{signal}_Mapping</SHORT-NAME><I-SIGNAL-REF DEST="I-SIGNAL">/Test/{signal}</I-SIGNAL-REF><PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-LAST</PACKING-BYTE-ORDER><START-POSITION>{position}</START-POSITION></I-SIGNAL-TO-I-PDU-MAPPING>"#
+            ));
+            position += bits;
+        }
+        format!(
+            r#"        <I-SIGNAL-I-PDU>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <LENGTH>{length}</LENGTH>
+          {props}
+          <I-SIGNAL-TO-PDU-MAPPINGS>{mappings}</I-SIGNAL-TO-PDU-MAPPINGS>
+        </I-SIGNAL-I-PDU>
+"#
+        )
+    }
+
+    /// CAN-FRAME carrying a single PDU of element type `pdu_type`
+    fn can_frame(name: &str, length: usize, pdu: &str, pdu_type: &str) -> String {
+        format!(
+            r#"        <CAN-FRAME>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <FRAME-LENGTH>{length}</FRAME-LENGTH>
+          <PDU-TO-FRAME-MAPPINGS>
+            <PDU-TO-FRAME-MAPPING>
+              <SHORT-NAME>{pdu}_Mapping</SHORT-NAME>
+              <PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-LAST</PACKING-BYTE-ORDER>
+              <PDU-REF DEST="{pdu_type}">/Test/{pdu}</PDU-REF>
+              <START-POSITION>0</START-POSITION>
+            </PDU-TO-FRAME-MAPPING>
+          </PDU-TO-FRAME-MAPPINGS>
+        </CAN-FRAME>
+"#
+        )
+    }
+
+    /// PDU-TRIGGERING of a PDU of element type `pdu_type`
+    fn pdu_triggering(pdu: &str, pdu_type: &str) -> String {
+        format!(
+            r#"<PDU-TRIGGERING><SHORT-NAME>{pdu}_Triggering</SHORT-NAME><I-PDU-REF DEST="{pdu_type}">/Test/{pdu}</I-PDU-REF></PDU-TRIGGERING>"#
+        )
+    }
+
+    /// CONTAINED-I-PDU-PROPS with `fields` (e.g. HEADER-ID-SHORT-HEADER)
+    fn contained_props(pdu: &str, fields: &str) -> String {
+        format!(
+            r#"<CONTAINED-I-PDU-PROPS><CONTAINED-PDU-TRIGGERING-REF DEST="PDU-TRIGGERING">/Test/Can/Channel/{pdu}_Triggering</CONTAINED-PDU-TRIGGERING-REF>{fields}</CONTAINED-I-PDU-PROPS>"#
+        )
+    }
+
+    /// CONTAINER-I-PDU carrying `pdus`, with `settings` after the triggering refs
+    fn container_pdu(name: &str, length: usize, pdus: &[&str], settings: &str) -> String {
+        let refs: String = pdus
+            .iter()
+            .map(|pdu| {
+                format!(
+                    r#"<CONTAINED-PDU-TRIGGERING-REF DEST="PDU-TRIGGERING">/Test/Can/Channel/{pdu}_Triggering</CONTAINED-PDU-TRIGGERING-REF>"#
+                )
+            })
+            .collect();
+        format!(
+            r#"        <CONTAINER-I-PDU>
+          <SHORT-NAME>{name}</SHORT-NAME>
+          <LENGTH>{length}</LENGTH>
+          <CONTAINED-PDU-TRIGGERING-REFS>{refs}</CONTAINED-PDU-TRIGGERING-REFS>
+          {settings}
+        </CONTAINER-I-PDU>
+"#
+        )
+    }
+
+    /// CAN-FRAME-TRIGGERING sending `frame` with `can_id`
+    fn frame_triggering(frame: &str, can_id: u32) -> String {
+        format!(
+            r#"<CAN-FRAME-TRIGGERING><SHORT-NAME>{frame}_Triggering</SHORT-NAME><FRAME-REF DEST="CAN-FRAME">/Test/{frame}</FRAME-REF><CAN-ADDRESSING-MODE>STANDARD</CAN-ADDRESSING-MODE><IDENTIFIER>{can_id}</IDENTIFIER></CAN-FRAME-TRIGGERING>"#
+        )
+    }
 
     #[test]
     fn test_arxml_file_not_found() {
@@ -819,7 +1337,7 @@ mod tests {
         if test_path.exists() {
             let result = parse_arxml_file(&test_path);
             match result {
-                Ok((messages, containers)) => {
+                Ok(ArxmlDefinitions { messages, containers, .. }) => {
                     println!("✓ Parsed {} messages and {} containers", messages.len(), containers.len());
 
                     // Print some details
@@ -854,4 +1372,349 @@ mod tests {
             println!("Test file not found: {:?}", test_path);
         }
     }
+
+    #[test]
+    fn test_parse_compu_methods_and_base_types() {
+        let elements = [
+            base_type("uint8", 8, "NONE"),
+            base_type("sint8", 8, "2C"),
+            base_type("float32", 32, "IEEE754"),
+            compu_method(
+                "CM_Status",
+                "TEXTTABLE",
+                &[
+                    compu_scale(0, 0, Err("Off")),
+                    compu_scale(1, 1, Err("On")),
+                    compu_scale(2, 3, Err("Error")),
+                ],
+            ),
+            compu_method(
+                "CM_Temperature",
+                "SCALE_LINEAR_AND_TEXTTABLE",
+                &[
+                    compu_scale(0, 100, Ok((0.0, 0.5))),
+                    compu_scale(101, 200, Ok((-50.0, 1.0))),
+                    compu_scale(255, 255, Err("SNA")),
+                ],
+            ),
+            compu_method("CM_Identical", "IDENTICAL", &[]),
+            compu_method(
+                "CM_Load",
+                "RAT_FUNC",
+                &["<COMPU-SCALE><LOWER-LIMIT>0</LOWER-LIMIT><UPPER-LIMIT>255</UPPER-LIMIT>\
+                   <COMPU-RATIONAL-COEFFS><COMPU-NUMERATOR><V>0</V><V>10</V></COMPU-NUMERATOR>\
+                   <COMPU-DENOMINATOR><V>1</V><V>1</V></COMPU-DENOMINATOR></COMPU-RATIONAL-COEFFS>\
+                   </COMPU-SCALE>"
+                    .to_string()],
+            ),
+            i_signal("Status", 8, "uint8", "CM_Status"),
+            i_signal("Temperature", 8, "uint8", "CM_Temperature"),
+            i_signal("Offset", 8, "sint8", "CM_Identical"),
+            i_signal("Ratio", 32, "float32", "CM_Identical"),
+            i_signal("Load", 8, "uint8", "CM_Load"),
+            i_pdu(
+                "StatusPdu",
+                8,
+                "",
+                &[("Status", 8), ("Temperature", 8), ("Offset", 8), ("Ratio", 32), ("Load", 8)],
+            ),
+            can_frame("StatusFrame", 8, "StatusPdu", "I-SIGNAL-I-PDU"),
+        ]
+        .concat();
+        let file = write_arxml(
+            &elements,
+            &format!("<FRAME-TRIGGERINGS>{}</FRAME-TRIGGERINGS>", frame_triggering("StatusFrame", 0x100)),
+        );
+
+        let definitions = parse_arxml_file(file.path()).unwrap();
+        assert_eq!(definitions.pdu_clusters["StatusPdu"], ["Channel", "Can"]);
+        let messages = definitions.messages;
+        assert_eq!(messages.len(), 1);
+        let msg = &messages[0];
+        assert_eq!(msg.id, 0x100);
+        let signal = |name: &str| msg.signals.iter().find(|s| s.name == name).unwrap();
+
+        let status = signal("Status");
+        assert_eq!(status.value_type, ValueType::Unsigned);
+        let table = status.value_table.as_ref().unwrap();
+        assert_eq!(table[&1], "On");
+        assert_eq!(table[&3], "Error");
+        assert!(status.conversion.is_none());
+
+        let temperature = signal("Temperature");
+        assert_eq!(temperature.value_table.as_ref().unwrap()[&255], "SNA");
+        assert_eq!(temperature.linear_coefficients(80.0), Some((0.5, 0.0)));
+        assert_eq!(temperature.linear_coefficients(150.0), Some((1.0, -50.0)));
+        assert_eq!((temperature.min, temperature.max), (0.0, 150.0));
+
+        assert_eq!(signal("Offset").value_type, ValueType::Signed);
+        assert_eq!(signal("Ratio").value_type, ValueType::Float32);
+
+        // y = 10x / (1 + x)
+        let load = signal("Load");
+        assert!(matches!(load.conversion, Some(SignalConversion::Rational(_))));
+        assert_eq!(load.physical_value(4.0), 8.0);
+        assert_eq!(load.raw_value(8.0), Some(4.0));
+    }
+
+    #[test]
+    fn test_parse_big_endian_start_position() {
+        // MOST-SIGNIFICANT-BYTE-FIRST: START-POSITION is the MSB in DBC sawtooth numbering
+        let mapping = |signal: &str, start: u16| {
+            format!(
+                r#"<I-SIGNAL-TO-I-PDU-MAPPING><SHORT-NAME>{signal}_Mapping</SHORT-NAME><I-SIGNAL-REF DEST="I-SIGNAL">/Test/{signal}</I-SIGNAL-REF><PACKING-BYTE-ORDER>MOST-SIGNIFICANT-BYTE-FIRST</PACKING-BYTE-ORDER><START-POSITION>{start}</START-POSITION></I-SIGNAL-TO-I-PDU-MAPPING>"#
+            )
+        };
+        let elements = [
+            base_type("uint16", 16, "NONE"),
+            compu_method("CM_Identical", "IDENTICAL", &[]),
+            i_signal("Word", 16, "uint16", "CM_Identical"),
+            i_signal("Nibbles", 12, "uint16", "CM_Identical"),
+            format!(
+                r#"        <I-SIGNAL-I-PDU>
+          <SHORT-NAME>MotorolaPdu</SHORT-NAME>
+          <LENGTH>8</LENGTH>
+          <I-SIGNAL-TO-PDU-MAPPINGS>{}{}</I-SIGNAL-TO-PDU-MAPPINGS>
+        </I-SIGNAL-I-PDU>
+"#,
+                mapping("Word", 7),
+                mapping("Nibbles", 23)
+            ),
+            can_frame("MotorolaFrame", 8, "MotorolaPdu", "I-SIGNAL-I-PDU"),
+        ]
+        .concat();
+        let file = write_arxml(
+            &elements,
+            &format!("<FRAME-TRIGGERINGS>{}</FRAME-TRIGGERINGS>", frame_triggering("MotorolaFrame", 0x100)),
+        );
+
+        let definitions = parse_arxml_file(file.path()).unwrap();
+        let msg = &definitions.messages[0];
+        let signal = |name: &str| msg.signals.iter().find(|s| s.name == name).unwrap();
+        let data = [0x12, 0x34, 0x56, 0x78, 0, 0, 0, 0];
+
+        let word = signal("Word");
+        assert_eq!((word.start_bit, word.byte_order), (7, ByteOrder::BigEndian));
+        assert_eq!(ExtractionPlan::new(word).extract(&data), Some(0x1234));
+        // Byte 2, then the high nibble of byte 3
+        assert_eq!(ExtractionPlan::new(signal("Nibbles")).extract(&data), Some(0x567));
+    }
+
+    #[test]
+    fn test_parse_dynamic_container_header_ids() {
+        let elements = [
+            base_type("uint8", 8, "NONE"),
+            compu_method("CM_Identical", "IDENTICAL", &[]),
+            i_signal("Speed", 8, "uint8", "CM_Identical"),
+            i_signal("Gear", 8, "uint8", "CM_Identical"),
+            i_pdu(
+                "SpeedPdu",
+                1,
+                &contained_props("SpeedPdu", "<HEADER-ID-SHORT-HEADER>1193046</HEADER-ID-SHORT-HEADER>"),
+                &[("Speed", 8)],
+            ),
+            i_pdu(
+                "GearPdu",
+                1,
+                &contained_props("GearPdu", "<HEADER-ID-SHORT-HEADER>16</HEADER-ID-SHORT-HEADER>"),
+                &[("Gear", 8)],
+            ),
+            container_pdu("Container", 16, &["SpeedPdu", "GearPdu"], "<HEADER-TYPE>SHORT-HEADER</HEADER-TYPE>"),
+            can_frame("ContainerFrame", 16, "Container", "CONTAINER-I-PDU"),
+        ]
+        .concat();
+        let channel = format!(
+            "<FRAME-TRIGGERINGS>{}</FRAME-TRIGGERINGS><PDU-TRIGGERINGS>{}{}</PDU-TRIGGERINGS>",
+            frame_triggering("ContainerFrame", 0x200),
+            pdu_triggering("SpeedPdu", "I-SIGNAL-I-PDU"),
+            pdu_triggering("GearPdu", "I-SIGNAL-I-PDU")
+        );
+        let file = write_arxml(&elements, &channel);
+
+        let definitions = parse_arxml_file(file.path()).unwrap();
+        assert!(definitions.messages.is_empty());
+        assert_eq!(definitions.contained_pdus.len(), 2);
+        let container = &definitions.containers[0];
+        assert_eq!(container.id, 0x200);
+        let ContainerLayout::Dynamic { header_size, pdus } = &container.layout else {
+            panic!("expected dynamic container");
+        };
+        assert_eq!(*header_size, 4);
+        assert_eq!(pdus.iter().map(|p| p.pdu_id).collect::<Vec<_>>(), [0x123456, 0x10]);
+
+        // Contained PDUs are decoded by name from the header ID
+        let mut db = crate::signals::database::SignalDatabase::new();
+        for pdu in definitions.contained_pdus {
+            db.add_contained_pdu(pdu);
+        }
+        let frame = crate::types::CanFrame {
+            timestamp_ns: 0,
+            measurement_start_ns: 0,
+            channel: 2,
+            can_id: 0x200,
+            data: vec![0x00, 0x00, 0x10, 0x01, 0x03, 0x12, 0x34, 0x56, 0x01, 0x64, 0, 0, 0, 0, 0, 0],
+            is_extended: false,
+            is_fd: true,
+            is_error_frame: false,
+            is_remote_frame: false,
+        };
+        let events = crate::container_decoder::ContainerDecoder::decode_container(&frame, container, &db).unwrap();
+        let decoded: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                crate::types::DecodedEvent::Message { message_name, signals, .. } => {
+                    Some((message_name.as_deref()?, signals[0].raw_value))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(decoded, [("GearPdu", 3), ("SpeedPdu", 100)]);
+
+        // Contained messages keep the carrying frame's context
+        assert!(events.iter().all(|event| event.channel() == Some(2) && event.can_id() == Some(0x200)));
+        let crate::types::DecodedEvent::Message { container: Some(parent), .. } = &events[1] else {
+            panic!("expected contained message");
+        };
+        assert_eq!(parent.name, "Container");
+        assert_eq!(parent.pdu_id, 0x10);
+        assert!(parent.is_fd);
+    }
+
+    #[test]
+    fn test_parse_static_container_layout() {
+        let props = |pdu, offset, update_bit| {
+            contained_props(
+                pdu,
+                &format!(
+                    "<OFFSET>{offset}</OFFSET>\
+                     <UPDATE-INDICATION-BIT-POSITION>{update_bit}</UPDATE-INDICATION-BIT-POSITION>"
+                ),
+            )
+        };
+        let elements = [
+            base_type("uint8", 8, "NONE"),
+            compu_method("CM_Identical", "IDENTICAL", &[]),
+            i_signal("Speed", 8, "uint8", "CM_Identical"),
+            i_signal("Gear", 8, "uint8", "CM_Identical"),
+            i_pdu("SpeedPdu", 2, &props("SpeedPdu", 4, 0), &[("Speed", 8)]),
+            i_pdu("GearPdu", 1, &props("GearPdu", 1, 1), &[("Gear", 8)]),
+            container_pdu(
+                "Container",
+                8,
+                &["SpeedPdu", "GearPdu"],
+                "<CONTAINER-TRIGGER>FIRST-CONTAINED-TRIGGER</CONTAINER-TRIGGER>\
+                 <HEADER-TYPE>NO-HEADER</HEADER-TYPE><UNUSED-BIT-PATTERN>255</UNUSED-BIT-PATTERN>",
+            ),
+            can_frame("ContainerFrame", 8, "Container", "CONTAINER-I-PDU"),
+        ]
+        .concat();
+        let channel = format!(
+            "<FRAME-TRIGGERINGS>{}</FRAME-TRIGGERINGS><PDU-TRIGGERINGS>{}{}</PDU-TRIGGERINGS>",
+            frame_triggering("ContainerFrame", 0x300),
+            pdu_triggering("SpeedPdu", "I-SIGNAL-I-PDU"),
+            pdu_triggering("GearPdu", "I-SIGNAL-I-PDU")
+        );
+        let file = write_arxml(&elements, &channel);
+
+        let definitions = parse_arxml_file(file.path()).unwrap();
+        let container = &definitions.containers[0];
+        assert_eq!(container.unused_bit_pattern, 0xFF);
+        let ContainerLayout::Static { pdus } = &container.layout else {
+            panic!("expected static container");
+        };
+        let layout: Vec<_> = pdus.iter().map(|p| (p.name.as_str(), p.position, p.size, p.update_bit)).collect();
+        assert_eq!(layout, [("SpeedPdu", 4, 2, Some(0)), ("GearPdu", 1, 1, Some(1))]);
+    }
+
+    #[test]
+    fn test_parse_secured_pdu_in_nested_container() {
+        let elements = [
+            base_type("uint8", 8, "NONE"),
+            compu_method("CM_Identical", "IDENTICAL", &[]),
+            i_signal("Speed", 8, "uint8", "CM_Identical"),
+            i_pdu("SpeedPdu", 1, "", &[("Speed", 8)]),
+            r#"        <SECURE-COMMUNICATION-PROPS-SET>
+          <SHORT-NAME>SecOcProps</SHORT-NAME>
+          <FRESHNESS-PROPSS>
+            <SECURE-COMMUNICATION-FRESHNESS-PROPS>
+              <SHORT-NAME>Freshness8</SHORT-NAME>
+              <FRESHNESS-VALUE-TX-LENGTH>8</FRESHNESS-VALUE-TX-LENGTH>
+            </SECURE-COMMUNICATION-FRESHNESS-PROPS>
+          </FRESHNESS-PROPSS>
+        </SECURE-COMMUNICATION-PROPS-SET>
+        <SECURED-I-PDU>
+          <SHORT-NAME>SecuredSpeed</SHORT-NAME>
+          <LENGTH>4</LENGTH>
+          <CONTAINED-I-PDU-PROPS><HEADER-ID-SHORT-HEADER>32</HEADER-ID-SHORT-HEADER></CONTAINED-I-PDU-PROPS>
+          <FRESHNESS-PROPS-REF DEST="SECURE-COMMUNICATION-FRESHNESS-PROPS">/Test/SecOcProps/Freshness8</FRESHNESS-PROPS-REF>
+          <PAYLOAD-REF DEST="PDU-TRIGGERING">/Test/Can/Channel/SpeedPdu_Triggering</PAYLOAD-REF>
+          <SECURE-COMMUNICATION-PROPS><AUTH-INFO-TX-LENGTH>16</AUTH-INFO-TX-LENGTH></SECURE-COMMUNICATION-PROPS>
+        </SECURED-I-PDU>
+"#
+            .to_string(),
+            container_pdu(
+                "Inner",
+                12,
+                &["SecuredSpeed"],
+                &format!(
+                    "{}<HEADER-TYPE>SHORT-HEADER</HEADER-TYPE>",
+                    contained_props("Inner", "<OFFSET>2</OFFSET>")
+                ),
+            ),
+            container_pdu("Outer", 16, &["Inner"], "<HEADER-TYPE>NO-HEADER</HEADER-TYPE>"),
+            can_frame("OuterFrame", 16, "Outer", "CONTAINER-I-PDU"),
+        ]
+        .concat();
+        let channel = format!(
+            "<FRAME-TRIGGERINGS>{}</FRAME-TRIGGERINGS><PDU-TRIGGERINGS>{}{}{}</PDU-TRIGGERINGS>",
+            frame_triggering("OuterFrame", 0x400),
+            pdu_triggering("SpeedPdu", "I-SIGNAL-I-PDU"),
+            pdu_triggering("SecuredSpeed", "SECURED-I-PDU"),
+            pdu_triggering("Inner", "CONTAINER-I-PDU")
+        );
+        let file = write_arxml(&elements, &channel);
+
+        let definitions = parse_arxml_file(file.path()).unwrap();
+        assert_eq!(definitions.containers.len(), 1);
+        assert_eq!(definitions.contained_containers.len(), 1);
+        let secured = &definitions.secured_pdus[0];
+        assert_eq!(secured.payload_pdu, "SpeedPdu");
+        assert_eq!((secured.freshness_length, secured.authenticator_length), (8, 16));
+
+        let mut db = crate::signals::database::SignalDatabase::new();
+        definitions.contained_pdus.into_iter().for_each(|pdu| db.add_contained_pdu(pdu));
+        definitions.contained_containers.into_iter().for_each(|c| db.add_contained_container(c));
+        definitions.secured_pdus.into_iter().for_each(|pdu| db.add_secured_pdu(pdu));
+
+        // Outer: [2 bytes][Inner: header 0x20/4, speed 100, FV 5, MAC ABCD][padding]
+        let mut data = vec![0, 0, 0x00, 0x00, 0x20, 0x04, 0x64, 0x05, 0xAB, 0xCD];
+        data.resize(16, 0);
+        let frame = crate::types::CanFrame {
+            timestamp_ns: 0,
+            measurement_start_ns: 0,
+            channel: 1,
+            can_id: 0x400,
+            data,
+            is_extended: false,
+            is_fd: true,
+            is_error_frame: false,
+            is_remote_frame: false,
+        };
+        let events =
+            crate::container_decoder::ContainerDecoder::decode_container(&frame, &definitions.containers[0], &db)
+                .unwrap();
+        let Some(crate::types::DecodedEvent::Message { message_name, signals, container: Some(parent), .. }) =
+            events.iter().find(|e| matches!(e, crate::types::DecodedEvent::Message { .. }))
+        else {
+            panic!("expected the authentic PDU to be decoded");
+        };
+        assert_eq!(message_name.as_deref(), Some("SpeedPdu"));
+        assert_eq!(signals[0].raw_value, 100);
+        assert_eq!(parent.name, "Inner");
+        assert_eq!(parent.pdu_id, 0x20);
+        let secured = parent.secured.as_ref().unwrap();
+        assert_eq!(secured.name, "SecuredSpeed");
+        assert_eq!(secured.freshness_value, 5);
+        assert_eq!(secured.authenticator, [0xAB, 0xCD]);
+    }
 }
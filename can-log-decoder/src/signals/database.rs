@@ -3,7 +3,9 @@
 //! Combines signal definitions from multiple DBC and ARXML files into a
 //! single queryable database.
 
-use std::collections::HashMap;
+use super::extraction::ExtractionPlan;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 /// A complete CAN message definition
 #[derive(Debug, Clone)]
@@ -24,6 +26,39 @@ pub struct MessageDefinition {
     pub multiplexer_signal: Option<String>,
     /// Source file (DBC/ARXML filename)
     pub source: String,
+    /// True for SAE J1939 parameter groups, matched by PGN instead of full CAN ID
+    pub is_j1939: bool,
+    /// Message attributes (DBC `BA_`, including `BA_DEF_DEF_` defaults)
+    pub attributes: HashMap<String, AttributeValue>,
+    /// Message comment (DBC `CM_ BO_`)
+    pub comment: Option<String>,
+    /// Log channels the definition is bound to (empty = all channels)
+    pub channels: Vec<u8>,
+}
+
+impl MessageDefinition {
+    /// Get a message attribute by name (e.g. "GenMsgCycleTime")
+    pub fn attribute(&self, name: &str) -> Option<&AttributeValue> {
+        self.attributes.get(name)
+    }
+
+    /// Get a signal of this message by name
+    pub fn signal(&self, name: &str) -> Option<&SignalDefinition> {
+        self.signals.iter().find(|s| s.name == name)
+    }
+
+    /// Precompile the extraction plans of all signals
+    pub fn compile(&mut self) {
+        self.signals.iter_mut().for_each(SignalDefinition::compile);
+    }
+
+    /// Cycle time in milliseconds from `GenMsgCycleTime` (0 or absent = not cyclic)
+    pub fn cycle_time_ms(&self) -> Option<u64> {
+        self.attribute("GenMsgCycleTime")
+            .and_then(AttributeValue::as_f64)
+            .filter(|ms| *ms > 0.0)
+            .map(|ms| ms as u64)
+    }
 }
 
 /// A CAN signal definition
@@ -51,12 +86,273 @@ pub struct SignalDefinition {
     pub unit: Option<String>,
     /// Value table for enum-like values (raw_value -> description)
     pub value_table: Option<HashMap<i64, String>>,
+    /// Range-dependent conversion (None = `factor`/`offset` apply to every raw value)
+    pub conversion: Option<SignalConversion>,
     /// Multiplexer info (None if not multiplexed)
     pub multiplexer_info: Option<MultiplexerInfo>,
+    /// Signal attributes (DBC `BA_`, including `BA_DEF_DEF_` defaults)
+    pub attributes: HashMap<String, AttributeValue>,
+    /// Signal comment (DBC `CM_ SG_`)
+    pub comment: Option<String>,
+    /// Precompiled extraction (set by [`SignalDatabase`]; built per frame when `None`)
+    pub extraction: Option<ExtractionPlan>,
+}
+
+impl SignalDefinition {
+    /// Get a signal attribute by name (e.g. "GenSigStartValue")
+    pub fn attribute(&self, name: &str) -> Option<&AttributeValue> {
+        self.attributes.get(name)
+    }
+
+    /// Precompile the extraction plan (after the layout, name or unit changed)
+    pub fn compile(&mut self) {
+        self.extraction = Some(ExtractionPlan::new(self));
+    }
+
+    /// Factor and offset that apply to a raw value
+    ///
+    /// Falls back to the signal's `factor`/`offset` when no conversion scale
+    /// covers the raw value. `None` if a rational scale applies.
+    pub fn linear_coefficients(&self, raw_value: f64) -> Option<(f64, f64)> {
+        match &self.conversion {
+            Some(SignalConversion::PiecewiseLinear(scales)) => scales
+                .iter()
+                .find(|scale| scale.contains(raw_value))
+                .map(|scale| Some((scale.factor, scale.offset)))
+                .unwrap_or(Some((self.factor, self.offset))),
+            Some(SignalConversion::Rational(scales)) => {
+                if scales.iter().any(|scale| scale.contains(raw_value)) {
+                    None
+                } else {
+                    Some((self.factor, self.offset))
+                }
+            }
+            None => Some((self.factor, self.offset)),
+        }
+    }
+
+    /// Convert a raw value to its physical value
+    pub fn physical_value(&self, raw_value: f64) -> f64 {
+        if let Some(SignalConversion::Rational(scales)) = &self.conversion {
+            if let Some(scale) = scales.iter().find(|scale| scale.contains(raw_value)) {
+                return scale.evaluate(raw_value);
+            }
+        }
+        let (factor, offset) = self
+            .linear_coefficients(raw_value)
+            .unwrap_or((self.factor, self.offset));
+        offset + factor * raw_value
+    }
+
+    /// Convert a physical value back to a raw value (not rounded)
+    ///
+    /// Returns `None` if no scale can produce the physical value.
+    pub fn raw_value(&self, physical_value: f64) -> Option<f64> {
+        let linear_inverse = |factor: f64, offset: f64| {
+            (factor != 0.0).then(|| (physical_value - offset) / factor)
+        };
+
+        let from_scales = match &self.conversion {
+            Some(SignalConversion::PiecewiseLinear(scales)) => scales.iter().find_map(|scale| {
+                if scale.factor == 0.0 {
+                    // Constant scale: any raw value in range produces the constant
+                    return (scale.offset == physical_value).then_some(scale.lower_limit);
+                }
+                linear_inverse(scale.factor, scale.offset).filter(|raw| scale.contains(*raw))
+            }),
+            Some(SignalConversion::Rational(scales)) => {
+                scales.iter().find_map(|scale| scale.inverse(physical_value))
+            }
+            None => None,
+        };
+
+        from_scales.or_else(|| linear_inverse(self.factor, self.offset))
+    }
+}
+
+/// Raw-to-physical conversion that depends on the raw value
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignalConversion {
+    /// Linear scales selected by raw value range (ARXML `SCALE_LINEAR`
+    /// with several `COMPU-SCALE`s, `SCALE_LINEAR_AND_TEXTTABLE`)
+    PiecewiseLinear(Vec<ConversionScale>),
+    /// Rational function scales selected by raw value range (ARXML `RAT_FUNC`,
+    /// `SCALE_RAT_FUNC`)
+    Rational(Vec<RationalScale>),
+}
+
+/// Linear conversion for a closed range of raw values
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionScale {
+    /// Lowest raw value covered by this scale
+    pub lower_limit: f64,
+    /// Highest raw value covered by this scale
+    pub upper_limit: f64,
+    /// Scale factor
+    pub factor: f64,
+    /// Offset added after scaling
+    pub offset: f64,
+}
+
+impl ConversionScale {
+    /// Check if a raw value lies within this scale's limits
+    pub fn contains(&self, raw_value: f64) -> bool {
+        raw_value >= self.lower_limit && raw_value <= self.upper_limit
+    }
+}
+
+/// Rational conversion `(n0 + n1*x + n2*x^2 + ...) / (d0 + d1*x + ...)`
+/// for a closed range of raw values
+#[derive(Debug, Clone, PartialEq)]
+pub struct RationalScale {
+    /// Lowest raw value covered by this scale
+    pub lower_limit: f64,
+    /// Highest raw value covered by this scale
+    pub upper_limit: f64,
+    /// Numerator coefficients, lowest order first
+    pub numerator: Vec<f64>,
+    /// Denominator coefficients, lowest order first (empty = 1)
+    pub denominator: Vec<f64>,
+}
+
+impl RationalScale {
+    /// Iterations of the bisection used to invert higher-order functions
+    const INVERSE_ITERATIONS: usize = 200;
+
+    /// Check if a raw value lies within this scale's limits
+    pub fn contains(&self, raw_value: f64) -> bool {
+        raw_value >= self.lower_limit && raw_value <= self.upper_limit
+    }
+
+    /// Physical value for a raw value
+    pub fn evaluate(&self, raw_value: f64) -> f64 {
+        let denominator = if self.denominator.is_empty() {
+            1.0
+        } else {
+            Self::polynomial(&self.denominator, raw_value)
+        };
+        Self::polynomial(&self.numerator, raw_value) / denominator
+    }
+
+    /// Raw value within this scale's limits that produces `physical_value`
+    ///
+    /// First-order functions are inverted in closed form, higher orders by
+    /// bisection between the (finite) limits.
+    pub fn inverse(&self, physical_value: f64) -> Option<f64> {
+        let coefficient = |coefficients: &[f64], i: usize| coefficients.get(i).copied().unwrap_or(0.0);
+        let denominator: &[f64] = if self.denominator.is_empty() { &[1.0] } else { &self.denominator };
+
+        // Solve n(x) - y*d(x) = 0
+        let order = self.numerator.len().max(denominator.len());
+        let equation: Vec<f64> = (0..order)
+            .map(|i| coefficient(&self.numerator, i) - physical_value * coefficient(denominator, i))
+            .collect();
+        let degree = equation.iter().rposition(|c| *c != 0.0)?;
+
+        let raw = match degree {
+            0 => return None,
+            1 => -equation[0] / equation[1],
+            _ => {
+                if !self.lower_limit.is_finite() || !self.upper_limit.is_finite() {
+                    return None;
+                }
+                let f = |x: f64| Self::polynomial(&equation, x);
+                let (mut low, mut high) = (self.lower_limit, self.upper_limit);
+                if f(low) == 0.0 {
+                    return Some(low);
+                }
+                // signum() of 0.0 is 1.0, so a root at the upper limit needs its own check
+                if f(high) == 0.0 {
+                    return Some(high);
+                }
+                if f(low).signum() == f(high).signum() {
+                    return None;
+                }
+                for _ in 0..Self::INVERSE_ITERATIONS {
+                    let mid = (low + high) / 2.0;
+                    if f(mid).signum() == f(low).signum() {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                (low + high) / 2.0
+            }
+        };
+
+        self.contains(raw).then_some(raw)
+    }
+
+    fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+        coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+    }
+}
+
+impl From<ConversionScale> for RationalScale {
+    fn from(scale: ConversionScale) -> Self {
+        Self {
+            lower_limit: scale.lower_limit,
+            upper_limit: scale.upper_limit,
+            numerator: vec![scale.offset, scale.factor],
+            denominator: vec![1.0],
+        }
+    }
+}
+
+/// Value of a database attribute
+///
+/// Enum attributes are resolved to the name of the selected entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    /// INT/HEX attribute
+    Integer(i64),
+    /// FLOAT attribute
+    Float(f64),
+    /// STRING or ENUM attribute
+    String(String),
+}
+
+impl AttributeValue {
+    /// Numeric value (`None` for strings)
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AttributeValue::Integer(v) => Some(*v as f64),
+            AttributeValue::Float(v) => Some(*v),
+            AttributeValue::String(_) => None,
+        }
+    }
+
+    /// Integer value (`None` for strings and fractional floats)
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AttributeValue::Integer(v) => Some(*v),
+            AttributeValue::Float(v) if v.fract() == 0.0 => Some(*v as i64),
+            _ => None,
+        }
+    }
+
+    /// String value (`None` for numbers)
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttributeValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeValue::Integer(v) => write!(f, "{}", v),
+            AttributeValue::Float(v) => write!(f, "{}", v),
+            AttributeValue::String(s) => write!(f, "{}", s),
+        }
+    }
 }
 
 /// Byte order for signal extraction
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ByteOrder {
     /// Little-endian (Intel format)
     LittleEndian,
@@ -65,12 +361,17 @@ pub enum ByteOrder {
 }
 
 /// Value type for signal interpretation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ValueType {
     /// Signed integer
     Signed,
     /// Unsigned integer
     Unsigned,
+    /// IEEE 754 single precision float (32-bit signal)
+    Float32,
+    /// IEEE 754 double precision float (64-bit signal)
+    Float64,
 }
 
 /// Multiplexer information for multiplexed signals
@@ -80,6 +381,19 @@ pub struct MultiplexerInfo {
     pub multiplexer_signal: String,
     /// Multiplexer value(s) for which this signal is active
     pub multiplexer_values: Vec<u64>,
+    /// Inclusive multiplexer value ranges (`SG_MUL_VAL_`) for which this signal is active
+    pub multiplexer_ranges: Vec<(u64, u64)>,
+}
+
+impl MultiplexerInfo {
+    /// Check whether the signal is active for a multiplexer value
+    pub fn is_active_for(&self, value: u64) -> bool {
+        self.multiplexer_values.contains(&value)
+            || self
+                .multiplexer_ranges
+                .iter()
+                .any(|&(min, max)| (min..=max).contains(&value))
+    }
 }
 
 /// AUTOSAR Container PDU definition
@@ -93,6 +407,10 @@ pub struct ContainerDefinition {
     pub container_type: crate::types::ContainerType,
     /// Layout information (PDU positions, headers, etc.)
     pub layout: ContainerLayout,
+    /// Byte used to fill unused space (UNUSED-BIT-PATTERN)
+    pub unused_bit_pattern: u8,
+    /// Log channels the definition is bound to (empty = all channels)
+    pub channels: Vec<u8>,
     /// Source ARXML file
     pub source: String,
 }
@@ -127,24 +445,66 @@ pub struct ContainedPduInfo {
     pub position: usize,
     /// PDU size in bytes
     pub size: usize,
+    /// Bit in the container telling whether the PDU was updated
+    /// (UPDATE-INDICATION-BIT-POSITION, static containers only)
+    pub update_bit: Option<u16>,
 }
 
+/// AUTOSAR Secured I-PDU (SecOC) definition
+///
+/// Layout: `[header][authentic PDU][freshness value][authenticator]`, with the
+/// truncated freshness value and authenticator bit-packed after the payload.
+#[derive(Debug, Clone)]
+pub struct SecuredPduDefinition {
+    /// Secured I-PDU name
+    pub name: String,
+    /// Name of the authentic PDU carried as payload
+    pub payload_pdu: String,
+    /// Secured PDU header size in bytes (USE-SECURED-PDU-HEADER, 0 = no header)
+    pub header_size: usize,
+    /// Transmitted freshness value length in bits (FRESHNESS-VALUE-TX-LENGTH)
+    pub freshness_length: u16,
+    /// Transmitted authenticator length in bits (AUTH-INFO-TX-LENGTH)
+    pub authenticator_length: u16,
+    /// Source ARXML file
+    pub source: String,
+}
+
+/// Maximum container nesting depth (guards against cyclic definitions)
+pub(crate) const MAX_NESTING_DEPTH: usize = 8;
+
 /// The unified signal database
+#[derive(Clone)]
 pub struct SignalDatabase {
     /// All message definitions by CAN ID
     /// Key: CAN ID, Value: List of messages with that ID (can be multiple from different DBCs)
     messages: HashMap<u32, Vec<MessageDefinition>>,
 
     /// Container PDU definitions by container ID
-    containers: HashMap<u32, ContainerDefinition>,
+    /// Key: CAN ID, Value: List of containers with that ID (e.g. on different channels)
+    containers: HashMap<u32, Vec<ContainerDefinition>>,
 
     /// Signal name lookup for quick access
-    /// Key: Signal name, Value: List of (CAN ID, signal index) tuples
-    signal_lookup: HashMap<String, Vec<(u32, usize)>>,
+    /// Key: Signal name, Value: List of (CAN ID, message index, signal index) tuples
+    signal_lookup: HashMap<String, Vec<(u32, usize, usize)>>,
 
     /// Message name lookup for contained PDUs
     /// Key: Message name, Value: (CAN ID, message index in messages vector)
     message_lookup: HashMap<String, (u32, usize)>,
+
+    /// J1939 parameter group lookup
+    /// Key: PGN, Value: CAN IDs of the J1939 messages defining it, in load order
+    pgn_lookup: HashMap<u32, Vec<u32>>,
+
+    /// I-PDUs only sent inside containers
+    /// Key: PDU name, Value: definition (`id` is the container header ID)
+    contained_pdus: HashMap<String, MessageDefinition>,
+
+    /// Containers nested inside other containers, by name
+    contained_containers: HashMap<String, ContainerDefinition>,
+
+    /// Secured I-PDUs (SecOC), by name
+    secured_pdus: HashMap<String, SecuredPduDefinition>,
 }
 
 impl SignalDatabase {
@@ -155,20 +515,17 @@ impl SignalDatabase {
             containers: HashMap::new(),
             signal_lookup: HashMap::new(),
             message_lookup: HashMap::new(),
+            pgn_lookup: HashMap::new(),
+            contained_pdus: HashMap::new(),
+            contained_containers: HashMap::new(),
+            secured_pdus: HashMap::new(),
         }
     }
 
     /// Add a message definition to the database
-    pub fn add_message(&mut self, message: MessageDefinition) {
+    pub fn add_message(&mut self, mut message: MessageDefinition) {
         let can_id = message.id;
-
-        // Build signal lookup indices
-        for (sig_idx, signal) in message.signals.iter().enumerate() {
-            self.signal_lookup
-                .entry(signal.name.clone())
-                .or_insert_with(Vec::new)
-                .push((can_id, sig_idx));
-        }
+        message.compile();
 
         // Get the index where this message will be added
         let msg_idx = self.messages
@@ -176,19 +533,66 @@ impl SignalDatabase {
             .map(|v| v.len())
             .unwrap_or(0);
 
+        // Build signal lookup indices
+        for (sig_idx, signal) in message.signals.iter().enumerate() {
+            self.signal_lookup
+                .entry(signal.name.clone())
+                .or_default()
+                .push((can_id, msg_idx, sig_idx));
+        }
+
         // Add message name lookup (for contained PDU decoding)
         self.message_lookup.insert(message.name.clone(), (can_id, msg_idx));
 
+        if message.is_j1939 {
+            let ids = self.pgn_lookup.entry(crate::j1939::pgn_from_id(can_id)).or_default();
+            if !ids.contains(&can_id) {
+                ids.push(can_id);
+            }
+        }
+
         // Add message to database
         self.messages
             .entry(can_id)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(message);
     }
 
     /// Add a container definition to the database
     pub fn add_container(&mut self, container: ContainerDefinition) {
-        self.containers.insert(container.id, container);
+        self.containers.entry(container.id).or_default().push(container);
+    }
+
+    /// Add the definition of a PDU that is only sent inside containers
+    ///
+    /// Contained PDUs are looked up by name, not CAN ID; a later definition
+    /// with the same name replaces the earlier one.
+    pub fn add_contained_pdu(&mut self, mut pdu: MessageDefinition) {
+        pdu.compile();
+        let source = pdu.source.clone();
+        if let Some(previous) = self.contained_pdus.insert(pdu.name.clone(), pdu) {
+            warn_replaced("contained PDU", &previous.name, &previous.source, &source);
+        }
+    }
+
+    /// Add a container that is itself carried inside another container
+    ///
+    /// Looked up by name; a later definition with the same name replaces the earlier one.
+    pub fn add_contained_container(&mut self, container: ContainerDefinition) {
+        let source = container.source.clone();
+        if let Some(previous) = self.contained_containers.insert(container.name.clone(), container) {
+            warn_replaced("nested container", &previous.name, &previous.source, &source);
+        }
+    }
+
+    /// Add a Secured I-PDU definition
+    ///
+    /// Looked up by name; a later definition with the same name replaces the earlier one.
+    pub fn add_secured_pdu(&mut self, secured_pdu: SecuredPduDefinition) {
+        let source = secured_pdu.source.clone();
+        if let Some(previous) = self.secured_pdus.insert(secured_pdu.name.clone(), secured_pdu) {
+            warn_replaced("secured PDU", &previous.name, &previous.source, &source);
+        }
     }
 
     /// Get all message definitions for a given CAN ID
@@ -197,13 +601,105 @@ impl SignalDatabase {
     }
 
     /// Get a specific message definition (first one found for given CAN ID)
+    ///
+    /// Extended IDs without an exact match fall back to the J1939 PGN, so
+    /// parameter groups match regardless of source (and PDU1 destination) address.
     pub fn get_message(&self, can_id: u32) -> Option<&MessageDefinition> {
-        self.messages.get(&can_id).and_then(|msgs| msgs.first())
+        self.messages
+            .get(&can_id)
+            .and_then(|msgs| msgs.first())
+            .or_else(|| {
+                if can_id > 0x7FF && !self.pgn_lookup.is_empty() {
+                    self.get_message_by_pgn(crate::j1939::pgn_from_id(can_id))
+                } else {
+                    None
+                }
+            })
     }
 
-    /// Get container definition by ID
+    /// Get the message definition for a CAN ID received on `channel`
+    ///
+    /// Definitions bound to the channel take precedence over global ones;
+    /// definitions bound only to other channels are never returned.
+    pub fn get_message_on_channel(&self, channel: u8, can_id: u32) -> Option<&MessageDefinition> {
+        self.messages
+            .get(&can_id)
+            .and_then(|msgs| select_for_channel(msgs.iter(), channel, |m| &m.channels))
+            .or_else(|| {
+                if can_id > 0x7FF && !self.pgn_lookup.is_empty() {
+                    self.get_message_by_pgn_on_channel(channel, crate::j1939::pgn_from_id(can_id))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Get a message attribute (e.g. "GenMsgCycleTime", "VFrameFormat")
+    pub fn message_attribute(&self, can_id: u32, name: &str) -> Option<&AttributeValue> {
+        self.get_message(can_id)?.attribute(name)
+    }
+
+    /// Get a signal attribute (e.g. "GenSigStartValue")
+    pub fn signal_attribute(&self, can_id: u32, signal_name: &str, name: &str) -> Option<&AttributeValue> {
+        self.get_message(can_id)?.signal(signal_name)?.attribute(name)
+    }
+
+    /// Get the comment of a message
+    pub fn message_comment(&self, can_id: u32) -> Option<&str> {
+        self.get_message(can_id)?.comment.as_deref()
+    }
+
+    /// Get the comment of a signal
+    pub fn signal_comment(&self, can_id: u32, signal_name: &str) -> Option<&str> {
+        self.get_message(can_id)?.signal(signal_name)?.comment.as_deref()
+    }
+
+    /// Get the J1939 message definition for a parameter group number
+    pub fn get_message_by_pgn(&self, pgn: u32) -> Option<&MessageDefinition> {
+        self.pgn_messages(pgn).next()
+    }
+
+    /// Get the J1939 message definition for a parameter group number received on `channel`
+    ///
+    /// Same precedence as [`get_message_on_channel`](Self::get_message_on_channel),
+    /// across the definitions of the PGN under every source address.
+    pub fn get_message_by_pgn_on_channel(&self, channel: u8, pgn: u32) -> Option<&MessageDefinition> {
+        select_for_channel(self.pgn_messages(pgn), channel, |m| &m.channels)
+    }
+
+    /// J1939 message definitions of a parameter group number, in load order of their CAN IDs
+    fn pgn_messages(&self, pgn: u32) -> impl Iterator<Item = &MessageDefinition> + Clone {
+        self.pgn_lookup
+            .get(&pgn)
+            .into_iter()
+            .flatten()
+            .filter_map(|can_id| self.messages.get(can_id))
+            .flatten()
+            .filter(|m| m.is_j1939)
+    }
+
+    /// Get container definition by ID (first one found)
     pub fn get_container(&self, container_id: u32) -> Option<&ContainerDefinition> {
-        self.containers.get(&container_id)
+        self.containers.get(&container_id).and_then(|containers| containers.first())
+    }
+
+    /// Get the container definition for a CAN ID received on `channel`
+    ///
+    /// Same precedence as [`get_message_on_channel`](Self::get_message_on_channel).
+    pub fn get_container_on_channel(&self, channel: u8, container_id: u32) -> Option<&ContainerDefinition> {
+        self.containers
+            .get(&container_id)
+            .and_then(|containers| select_for_channel(containers.iter(), channel, |c| &c.channels))
+    }
+
+    /// Get a nested container definition by name
+    pub fn get_contained_container(&self, name: &str) -> Option<&ContainerDefinition> {
+        self.contained_containers.get(name)
+    }
+
+    /// Get a Secured I-PDU definition by name
+    pub fn get_secured_pdu(&self, name: &str) -> Option<&SecuredPduDefinition> {
+        self.secured_pdus.get(name)
     }
 
     /// Get message definition by name (for contained PDU decoding)
@@ -213,6 +709,7 @@ impl SignalDatabase {
             .and_then(|(can_id, msg_idx)| {
                 self.messages.get(can_id).and_then(|msgs| msgs.get(*msg_idx))
             })
+            .or_else(|| self.contained_pdus.get(message_name))
     }
 
     /// Find all messages containing a specific signal name
@@ -222,8 +719,10 @@ impl SignalDatabase {
             .map(|locations| {
                 locations
                     .iter()
-                    .filter_map(|(can_id, sig_idx)| {
-                        self.get_message(*can_id)
+                    .filter_map(|(can_id, msg_idx, sig_idx)| {
+                        self.messages
+                            .get(can_id)
+                            .and_then(|msgs| msgs.get(*msg_idx))
                             .and_then(|msg| msg.signals.get(*sig_idx))
                             .map(|sig| (*can_id, sig))
                     })
@@ -234,12 +733,15 @@ impl SignalDatabase {
 
     /// Get database statistics
     pub fn stats(&self) -> DatabaseStats {
-        let num_messages: usize = self.messages.values().map(|v| v.len()).sum();
+        let num_messages: usize =
+            self.messages.values().map(|v| v.len()).sum::<usize>() + self.contained_pdus.len();
         let num_signals: usize = self.messages.values()
             .flat_map(|msgs| msgs.iter())
+            .chain(self.contained_pdus.values())
             .map(|msg| msg.signals.len())
             .sum();
-        let num_containers = self.containers.len();
+        let num_containers =
+            self.containers.values().map(|v| v.len()).sum::<usize>() + self.contained_containers.len();
 
         DatabaseStats {
             num_messages,
@@ -248,12 +750,185 @@ impl SignalDatabase {
         }
     }
 
+    /// Iterate over all message definitions sent in their own frame
+    pub fn messages(&self) -> impl Iterator<Item = &MessageDefinition> {
+        self.messages.values().flatten()
+    }
+
+    /// Iterate over the definitions of PDUs only sent inside containers
+    pub fn contained_pdus(&self) -> impl Iterator<Item = &MessageDefinition> {
+        self.contained_pdus.values()
+    }
+
+    /// Iterate over all container definitions, including nested containers
+    pub fn containers(&self) -> impl Iterator<Item = &ContainerDefinition> {
+        self.containers.values().flatten().chain(self.contained_containers.values())
+    }
+
     /// Get all unique CAN IDs in the database
     pub fn get_all_can_ids(&self) -> Vec<u32> {
         let mut ids: Vec<u32> = self.messages.keys().copied().collect();
         ids.sort_unstable();
         ids
     }
+
+    /// Reduce the database to the definitions needed for a set of signals
+    ///
+    /// Messages and contained PDUs keep only the selected signals and the
+    /// multiplexers they depend on; messages without a selected signal are
+    /// dropped, as are containers and secured PDUs that no longer carry any PDU.
+    ///
+    /// Channel-bound definitions of an ID whose global definition is kept stay
+    /// in the selection (messages without any signal), so frames on their
+    /// channels are skipped instead of being decoded with the global layout.
+    pub fn select_signals<S: AsRef<str>>(&self, signal_names: &[S]) -> SignalDatabase {
+        let selected: HashSet<&str> = signal_names.iter().map(AsRef::as_ref).collect();
+        let mut found = HashSet::new();
+        let mut selection = SignalDatabase::new();
+
+        for messages in self.messages.values() {
+            let kept: Vec<_> = messages
+                .iter()
+                .map(|message| select_message(message, &selected, &mut found))
+                .collect();
+            let global_kept = kept.iter().flatten().any(|message| message.channels.is_empty());
+            for (message, kept) in messages.iter().zip(kept) {
+                match kept {
+                    Some(message) => selection.add_message(message),
+                    None if global_kept && !message.channels.is_empty() => {
+                        selection.add_message(MessageDefinition { signals: Vec::new(), ..message.clone() });
+                    }
+                    None => {}
+                }
+            }
+        }
+        for pdu in self.contained_pdus.values() {
+            if let Some(pdu) = select_message(pdu, &selected, &mut found) {
+                selection.add_contained_pdu(pdu);
+            }
+        }
+        for name in selected.iter().filter(|name| !found.contains(*name)) {
+            log::warn!("Signal filter: no message carries signal {}", name);
+        }
+
+        // Containers and SecOC wrappers that still lead to a selected PDU
+        for secured_pdu in self.secured_pdus.values() {
+            if self.carries_selection(&selection, &secured_pdu.name, 0) {
+                selection.add_secured_pdu(secured_pdu.clone());
+            }
+        }
+        for container in self.contained_containers.values() {
+            if self.container_carries_selection(&selection, container, 0) {
+                selection.add_contained_container(container.clone());
+            }
+        }
+        for containers in self.containers.values() {
+            let kept: Vec<bool> = containers
+                .iter()
+                .map(|container| self.container_carries_selection(&selection, container, 0))
+                .collect();
+            let global_kept = containers
+                .iter()
+                .zip(&kept)
+                .any(|(container, &kept)| kept && container.channels.is_empty());
+            for (container, kept) in containers.iter().zip(kept) {
+                // Bound containers shadowing a kept global one decode no unselected PDU
+                if kept || (global_kept && !container.channels.is_empty()) {
+                    selection.add_container(container.clone());
+                }
+            }
+        }
+
+        log::info!(
+            "Signal filter: {} of {} signal(s) found in {} message(s)",
+            found.len(),
+            selected.len(),
+            selection.messages().chain(selection.contained_pdus()).filter(|m| !m.signals.is_empty()).count()
+        );
+        selection
+    }
+
+    /// Whether a contained PDU resolves to a message in `selection`
+    fn carries_selection(&self, selection: &SignalDatabase, pdu_name: &str, depth: usize) -> bool {
+        if depth > MAX_NESTING_DEPTH {
+            return false;
+        }
+        if let Some(secured_pdu) = self.secured_pdus.get(pdu_name) {
+            return self.carries_selection(selection, &secured_pdu.payload_pdu, depth + 1);
+        }
+        if let Some(container) = self.contained_containers.get(pdu_name) {
+            return self.container_carries_selection(selection, container, depth + 1);
+        }
+        selection.get_message_by_name(pdu_name).is_some()
+    }
+
+    /// Whether any PDU of a container resolves to a message in `selection`
+    fn container_carries_selection(&self, selection: &SignalDatabase, container: &ContainerDefinition, depth: usize) -> bool {
+        match &container.layout {
+            ContainerLayout::Static { pdus } | ContainerLayout::Dynamic { pdus, .. } => pdus
+                .iter()
+                .any(|pdu| self.carries_selection(selection, &pdu.name, depth)),
+            ContainerLayout::Queued { pdu_id, .. } => selection.get_messages(*pdu_id).is_some(),
+        }
+    }
+}
+
+/// Copy of a message with only the selected signals and their multiplexer chains
+///
+/// `None` if the message carries none of the selected signals.
+fn select_message<'a>(
+    message: &'a MessageDefinition,
+    selected: &HashSet<&str>,
+    found: &mut HashSet<&'a str>,
+) -> Option<MessageDefinition> {
+    let mut keep: HashSet<&str> = message
+        .signals
+        .iter()
+        .map(|signal| signal.name.as_str())
+        .filter(|name| selected.contains(name))
+        .collect();
+    if keep.is_empty() {
+        return None;
+    }
+    found.extend(keep.iter().copied());
+
+    let mut pending: Vec<&str> = keep.iter().copied().collect();
+    while let Some(name) = pending.pop() {
+        let multiplexer = message
+            .signal(name)
+            .and_then(|signal| signal.multiplexer_info.as_ref())
+            .map(|mux| mux.multiplexer_signal.as_str());
+        if let Some(multiplexer) = multiplexer.filter(|mux| keep.insert(mux)) {
+            pending.push(multiplexer);
+        }
+    }
+
+    let mut selection = message.clone();
+    selection.signals.retain(|signal| keep.contains(signal.name.as_str()));
+    Some(selection)
+}
+
+/// Warn about a name-keyed definition being replaced
+fn warn_replaced(kind: &str, name: &str, previous_source: &str, source: &str) {
+    log::warn!(
+        "Duplicate {} {}: definition from {} replaces the one from {}",
+        kind,
+        name,
+        source,
+        previous_source
+    );
+}
+
+/// Pick the definition bound to `channel`, else the first global (unbound) one
+fn select_for_channel<'a, T: 'a>(
+    mut definitions: impl Iterator<Item = &'a T> + Clone,
+    channel: u8,
+    channels: impl Fn(&T) -> &Vec<u8>,
+) -> Option<&'a T> {
+    definitions
+        .clone()
+        .find(|d| channels(d).contains(&channel))
+        .or_else(|| definitions.find(|d| channels(d).is_empty()))
 }
 
 /// Database statistics
@@ -273,6 +948,62 @@ impl Default for SignalDatabase {
     }
 }
 
+#[cfg(test)]
+impl SignalDefinition {
+    /// Unsigned signal with factor 1 and offset 0 (test builder)
+    pub(crate) fn test(name: &str, start_bit: u16, length: u16, byte_order: ByteOrder) -> Self {
+        Self {
+            name: name.to_string(),
+            start_bit,
+            length,
+            byte_order,
+            value_type: ValueType::Unsigned,
+            factor: 1.0,
+            offset: 0.0,
+            min: 0.0,
+            max: 0.0,
+            unit: None,
+            value_table: None,
+            conversion: None,
+            multiplexer_info: None,
+            attributes: HashMap::new(),
+            comment: None,
+            extraction: None,
+        }
+    }
+
+    /// Make the signal active for one multiplexer value (test builder)
+    pub(crate) fn multiplexed_by(mut self, multiplexer: &str, value: u64) -> Self {
+        self.multiplexer_info = Some(MultiplexerInfo {
+            multiplexer_signal: multiplexer.to_string(),
+            multiplexer_values: vec![value],
+            multiplexer_ranges: Vec::new(),
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+impl MessageDefinition {
+    /// Global message definition from `test.dbc` (test builder)
+    pub(crate) fn test(id: u32, name: &str, size: usize, signals: Vec<SignalDefinition>) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            size,
+            sender: None,
+            signals,
+            is_multiplexed: false,
+            multiplexer_signal: None,
+            source: "test.dbc".to_string(),
+            is_j1939: false,
+            attributes: HashMap::new(),
+            comment: None,
+            channels: Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,31 +1021,12 @@ mod tests {
     fn test_add_message() {
         let mut db = SignalDatabase::new();
 
-        let signal = SignalDefinition {
-            name: "EngineSpeed".to_string(),
-            start_bit: 0,
-            length: 16,
-            byte_order: ByteOrder::LittleEndian,
-            value_type: ValueType::Unsigned,
-            factor: 1.0,
-            offset: 0.0,
-            min: 0.0,
-            max: 8000.0,
-            unit: Some("rpm".to_string()),
-            value_table: None,
-            multiplexer_info: None,
-        };
+        let mut signal = SignalDefinition::test("EngineSpeed", 0, 16, ByteOrder::LittleEndian);
+        signal.max = 8000.0;
+        signal.unit = Some("rpm".to_string());
 
-        let message = MessageDefinition {
-            id: 0x123,
-            name: "EngineData".to_string(),
-            size: 8,
-            sender: Some("ECU1".to_string()),
-            signals: vec![signal],
-            is_multiplexed: false,
-            multiplexer_signal: None,
-            source: "test.dbc".to_string(),
-        };
+        let mut message = MessageDefinition::test(0x123, "EngineData", 8, vec![signal]);
+        message.sender = Some("ECU1".to_string());
 
         db.add_message(message);
 
@@ -326,10 +1038,189 @@ mod tests {
         let msg = db.get_message(0x123).unwrap();
         assert_eq!(msg.name, "EngineData");
         assert_eq!(msg.signals[0].name, "EngineSpeed");
+        // Extraction plans are compiled when the message is added
+        let plan = msg.signals[0].extraction.as_ref().unwrap();
+        assert_eq!(&*plan.name, "EngineSpeed");
 
         // Test signal lookup
         let found = db.find_signal("EngineSpeed");
         assert_eq!(found.len(), 1);
         assert_eq!(found[0].0, 0x123);
     }
+
+    #[test]
+    fn test_j1939_pgn_lookup() {
+        let j1939 = |id: u32, name: &str| MessageDefinition {
+            is_j1939: true,
+            ..MessageDefinition::test(id, name, 8, Vec::new())
+        };
+        let mut db = SignalDatabase::new();
+        db.add_message(j1939(0x0CF00400, "EEC1")); // EEC1 from SA 0x00
+        db.add_message(j1939(0x18EF0000, "PropA")); // Proprietary A (PDU1) to DA 0x00
+
+        // Different source address and priority, same PGN
+        assert_eq!(db.get_message(0x18F00417).unwrap().name, "EEC1");
+        // PDU1: destination address is not part of the PGN
+        assert_eq!(db.get_message(0x18EF2A17).unwrap().name, "PropA");
+        assert_eq!(db.get_message_by_pgn(0xF004).unwrap().name, "EEC1");
+        assert!(db.get_message(0x18F00517).is_none());
+        assert!(db.get_message(0x004).is_none());
+    }
+
+    #[test]
+    fn test_j1939_pgn_lookup_on_channel() {
+        let j1939 = |id: u32, name: &str, channels: Vec<u8>| MessageDefinition {
+            is_j1939: true,
+            channels,
+            ..MessageDefinition::test(id, name, 8, Vec::new())
+        };
+        // EEC1 bound to channel 2 from SA 0x00, global EEC1 from SA 0x03, in both load orders
+        for bound_first in [true, false] {
+            let mut db = SignalDatabase::new();
+            let bound = j1939(0x0CF00400, "BoundEEC1", vec![2]);
+            let global = j1939(0x0CF00403, "EEC1", Vec::new());
+            let definitions = if bound_first { [bound, global] } else { [global, bound] };
+            for definition in definitions {
+                db.add_message(definition);
+            }
+
+            assert_eq!(db.get_message_on_channel(2, 0x18F00417).unwrap().name, "BoundEEC1");
+            assert_eq!(db.get_message_on_channel(1, 0x18F00417).unwrap().name, "EEC1");
+            assert_eq!(db.get_message_by_pgn_on_channel(2, 0xF004).unwrap().name, "BoundEEC1");
+            assert_eq!(db.get_message_by_pgn_on_channel(1, 0xF004).unwrap().name, "EEC1");
+        }
+    }
+
+    #[test]
+    fn test_channel_binding() {
+        let message = |name: &str, channels: Vec<u8>| MessageDefinition {
+            channels,
+            ..MessageDefinition::test(0x100, name, 8, Vec::new())
+        };
+        let with_speed = |mut message: MessageDefinition, start_bit: u16| {
+            message.signals.push(SignalDefinition::test("Speed", start_bit, 8, ByteOrder::LittleEndian));
+            message
+        };
+        let mut db = SignalDatabase::new();
+        db.add_message(message("Private", vec![2]));
+        db.add_message(message("Global", Vec::new()));
+        db.add_message(with_speed(message("Chassis", vec![3, 4]), 16));
+
+        assert_eq!(db.get_message_on_channel(2, 0x100).unwrap().name, "Private");
+        assert_eq!(db.get_message_on_channel(4, 0x100).unwrap().name, "Chassis");
+        assert_eq!(db.get_message_on_channel(1, 0x100).unwrap().name, "Global");
+        assert!(db.get_message_on_channel(1, 0x101).is_none());
+        // Signals resolve to the definition that carries them, not the first one of the ID
+        let found = db.find_signal("Speed");
+        assert_eq!((found.len(), found[0].1.start_bit), (1, 16));
+
+        // Without a global definition, other channels' definitions never match
+        let mut db = SignalDatabase::new();
+        db.add_message(message("Private", vec![2]));
+        assert!(db.get_message_on_channel(1, 0x100).is_none());
+        assert_eq!(db.get_message(0x100).unwrap().name, "Private");
+    }
+
+    #[test]
+    fn test_select_signals() {
+        let signal = |name: &str, multiplexer: Option<&str>| {
+            let signal = SignalDefinition::test(name, 0, 8, ByteOrder::LittleEndian);
+            match multiplexer {
+                Some(mux) => signal.multiplexed_by(mux, 0),
+                None => signal,
+            }
+        };
+        let message = |id: u32, name: &str, signals| MessageDefinition::test(id, name, 8, signals);
+        let container = |id: u32, pdu: &str| ContainerDefinition {
+            id,
+            name: format!("Container{}", id),
+            container_type: crate::types::ContainerType::Static,
+            layout: ContainerLayout::Static {
+                pdus: vec![ContainedPduInfo {
+                    pdu_id: 0,
+                    name: pdu.to_string(),
+                    position: 0,
+                    size: 8,
+                    update_bit: None,
+                }],
+            },
+            unused_bit_pattern: 0,
+            channels: Vec::new(),
+            source: "test.arxml".to_string(),
+        };
+
+        let mut db = SignalDatabase::new();
+        // Extended multiplexing: Value depends on Page, which depends on Mode
+        db.add_message(message(
+            0x100,
+            "Multiplexed",
+            vec![signal("Mode", None), signal("Page", Some("Mode")), signal("Value", Some("Page")), signal("Other", None)],
+        ));
+        db.add_message(message(0x200, "Unrelated", vec![signal("Speed", None)]));
+        db.add_contained_pdu(message(0x300, "InnerPdu", vec![signal("Inner", None), signal("Spare", None)]));
+        db.add_container(container(0x300, "InnerPdu"));
+        db.add_container(container(0x400, "OtherPdu"));
+
+        let selection = db.select_signals(&["Value", "Inner", "Missing"]);
+        let names = |message: &MessageDefinition| message.signals.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(selection.get_message(0x100).unwrap()), ["Mode", "Page", "Value"]);
+        assert!(selection.get_message(0x200).is_none());
+        assert_eq!(names(selection.get_message_by_name("InnerPdu").unwrap()), ["Inner"]);
+        assert!(selection.get_container(0x300).is_some());
+        assert!(selection.get_container(0x400).is_none());
+
+        // A secured PDU whose payload refers back to itself ends at the nesting limit
+        db.add_secured_pdu(SecuredPduDefinition {
+            name: "Looped".to_string(),
+            payload_pdu: "Looped".to_string(),
+            header_size: 0,
+            freshness_length: 0,
+            authenticator_length: 0,
+            source: "test.arxml".to_string(),
+        });
+        assert!(db.select_signals(&["Inner"]).get_secured_pdu("Looped").is_none());
+    }
+
+    #[test]
+    fn test_rational_scale_inverse() {
+        // y = (1 + x^2) / 2
+        let quadratic = RationalScale {
+            lower_limit: 0.0,
+            upper_limit: 100.0,
+            numerator: vec![1.0, 0.0, 1.0],
+            denominator: vec![2.0],
+        };
+        assert_eq!(quadratic.evaluate(9.0), 41.0);
+        assert!((quadratic.inverse(41.0).unwrap() - 9.0).abs() < 1e-9);
+        assert_eq!(quadratic.inverse(10_000.0), None);
+
+        // Decreasing y = 100 - x^2 reaching its physical value exactly at the upper limit
+        let decreasing = RationalScale {
+            lower_limit: 0.0,
+            upper_limit: 10.0,
+            numerator: vec![100.0, 0.0, -1.0],
+            denominator: Vec::new(),
+        };
+        assert_eq!(decreasing.inverse(0.0), Some(10.0));
+        assert!((decreasing.inverse(36.0).unwrap() - 8.0).abs() < 1e-9);
+
+        // y = (10x) / (1 + x)
+        let hyperbolic = RationalScale {
+            lower_limit: 0.0,
+            upper_limit: 255.0,
+            numerator: vec![0.0, 10.0],
+            denominator: vec![1.0, 1.0],
+        };
+        assert_eq!(hyperbolic.inverse(5.0), Some(1.0));
+        assert_eq!(hyperbolic.inverse(-5.0), None);
+
+        let linear = RationalScale::from(ConversionScale {
+            lower_limit: 0.0,
+            upper_limit: 10.0,
+            factor: 0.5,
+            offset: -1.0,
+        });
+        assert_eq!(linear.evaluate(4.0), 1.0);
+        assert_eq!(linear.inverse(1.0), Some(4.0));
+    }
 }
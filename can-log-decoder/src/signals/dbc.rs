@@ -3,7 +3,7 @@
 //! Parses Vector DBC files and converts them into our internal signal database format.
 
 use crate::signals::database::{
-    ByteOrder, MessageDefinition, MultiplexerInfo, SignalDefinition, ValueType,
+    AttributeValue, ByteOrder, MessageDefinition, MultiplexerInfo, SignalDefinition, ValueType,
 };
 use crate::types::{DecoderError, Result};
 use std::collections::HashMap;
@@ -44,9 +44,10 @@ pub fn parse_dbc_file(path: &Path) -> Result<Vec<MessageDefinition>> {
 
     // Convert to our internal format
     let mut messages = Vec::new();
+    let attributes = DbcAttributes::from_dbc(&dbc);
 
     for dbc_msg in dbc.messages() {
-        let message = convert_message(dbc_msg, &source_filename)?;
+        let message = convert_message(dbc_msg, &dbc, &attributes, &source_filename)?;
         messages.push(message);
     }
 
@@ -59,37 +60,219 @@ pub fn parse_dbc_file(path: &Path) -> Result<Vec<MessageDefinition>> {
     Ok(messages)
 }
 
+/// Bit 31 of a DBC message ID marks a 29-bit (extended) identifier
+const DBC_EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+
+/// Declared type of a DBC attribute (`BA_DEF_`)
+enum AttributeKind {
+    Integer,
+    Float,
+    String,
+    Enum(Vec<String>),
+}
+
+impl AttributeKind {
+    /// Parse a definition such as `"GenMsgCycleTime" INT 0 65535` into name and type
+    fn parse(text: &str) -> Option<(String, Self)> {
+        let (name, rest) = text.trim_start().strip_prefix('"')?.split_once('"')?;
+        let rest = rest.trim_start();
+        let kind = if let Some(values) = rest.strip_prefix("ENUM") {
+            AttributeKind::Enum(
+                values
+                    .split(',')
+                    .map(|v| v.trim().trim_matches('"').to_string())
+                    .collect(),
+            )
+        } else if rest.starts_with("INT") || rest.starts_with("HEX") {
+            AttributeKind::Integer
+        } else if rest.starts_with("FLOAT") {
+            AttributeKind::Float
+        } else {
+            AttributeKind::String
+        };
+        Some((name.to_string(), kind))
+    }
+
+    /// Convert a parsed value according to the declared type (enum index → name)
+    fn convert(kind: Option<&Self>, value: &can_dbc::AttributeValue) -> AttributeValue {
+        let number = match value {
+            can_dbc::AttributeValue::AttributeValueCharString(s) => return AttributeValue::String(s.clone()),
+            can_dbc::AttributeValue::AttributeValueF64(v) => *v,
+            can_dbc::AttributeValue::AttributeValueU64(v) => *v as f64,
+            can_dbc::AttributeValue::AttributeValueI64(v) => *v as f64,
+        };
+        match kind {
+            Some(AttributeKind::Integer) => AttributeValue::Integer(number as i64),
+            Some(AttributeKind::Enum(names)) => names
+                .get(number as usize)
+                .map(|name| AttributeValue::String(name.clone()))
+                .unwrap_or(AttributeValue::Integer(number as i64)),
+            Some(AttributeKind::Float) | Some(AttributeKind::String) | None => AttributeValue::Float(number),
+        }
+    }
+}
+
+/// Object attribute values in the order they appear: (attribute name, value)
+type AttributeList<'a> = Vec<(&'a str, &'a can_dbc::AttributeValue)>;
+
+/// DBC attribute definitions, defaults and values indexed by message/signal
+struct DbcAttributes<'a> {
+    message_kinds: HashMap<String, AttributeKind>,
+    signal_kinds: HashMap<String, AttributeKind>,
+    defaults: HashMap<&'a str, &'a can_dbc::AttributeValue>,
+    message_values: HashMap<u32, AttributeList<'a>>,
+    signal_values: HashMap<(u32, &'a str), AttributeList<'a>>,
+    /// Global `ProtocolType` is "J1939"
+    protocol_j1939: bool,
+}
+
+impl<'a> DbcAttributes<'a> {
+    fn from_dbc(dbc: &'a can_dbc::DBC) -> Self {
+        let mut message_kinds = HashMap::new();
+        let mut signal_kinds = HashMap::new();
+        for definition in dbc.attribute_definitions() {
+            match definition {
+                can_dbc::AttributeDefinition::Message(text) => {
+                    message_kinds.extend(AttributeKind::parse(text));
+                }
+                can_dbc::AttributeDefinition::Signal(text) => {
+                    signal_kinds.extend(AttributeKind::parse(text));
+                }
+                _ => {}
+            }
+        }
+
+        let defaults: HashMap<&str, &can_dbc::AttributeValue> = dbc
+            .attribute_defaults()
+            .iter()
+            .map(|default| (default.attribute_name().as_str(), default.attribute_value()))
+            .collect();
+
+        let mut message_values: HashMap<u32, AttributeList> = HashMap::new();
+        let mut signal_values: HashMap<(u32, &str), AttributeList> = HashMap::new();
+        let mut protocol = defaults.get("ProtocolType").copied();
+        for attr in dbc.attribute_values() {
+            let name = attr.attribute_name().as_str();
+            match attr.attribute_value() {
+                can_dbc::AttributeValuedForObjectType::MessageDefinitionAttributeValue(id, Some(value)) => {
+                    message_values.entry(id.0).or_default().push((name, value));
+                }
+                can_dbc::AttributeValuedForObjectType::SignalAttributeValue(id, signal, value) => {
+                    signal_values.entry((id.0, signal.as_str())).or_default().push((name, value));
+                }
+                can_dbc::AttributeValuedForObjectType::RawAttributeValue(value) if name == "ProtocolType" => {
+                    protocol = Some(value);
+                }
+                _ => {}
+            }
+        }
+        let protocol_j1939 = matches!(
+            protocol,
+            Some(can_dbc::AttributeValue::AttributeValueCharString(value)) if value.eq_ignore_ascii_case("J1939")
+        );
+
+        Self {
+            message_kinds,
+            signal_kinds,
+            defaults,
+            message_values,
+            signal_values,
+            protocol_j1939,
+        }
+    }
+
+    fn message_attributes(&self, message_id: can_dbc::MessageId) -> HashMap<String, AttributeValue> {
+        self.collect(&self.message_kinds, self.message_values.get(&message_id.0))
+    }
+
+    fn signal_attributes(&self, message_id: can_dbc::MessageId, signal_name: &str) -> HashMap<String, AttributeValue> {
+        self.collect(&self.signal_kinds, self.signal_values.get(&(message_id.0, signal_name)))
+    }
+
+    /// Defaults of all attributes defined for the object type, overridden by explicit values
+    fn collect(
+        &self,
+        kinds: &HashMap<String, AttributeKind>,
+        values: Option<&AttributeList<'a>>,
+    ) -> HashMap<String, AttributeValue> {
+        let mut attributes: HashMap<String, AttributeValue> = kinds
+            .iter()
+            .filter_map(|(name, kind)| {
+                let default = self.defaults.get(name.as_str())?;
+                Some((name.clone(), AttributeKind::convert(Some(kind), default)))
+            })
+            .collect();
+        for (name, value) in values.into_iter().flatten() {
+            attributes.insert(name.to_string(), AttributeKind::convert(kinds.get(*name), value));
+        }
+        attributes
+    }
+
+    /// A message is J1939 if its `VFrameFormat` says so, or if it is an
+    /// extended frame in a database whose `ProtocolType` is J1939
+    fn is_j1939(&self, message_id: can_dbc::MessageId, attributes: &HashMap<String, AttributeValue>) -> bool {
+        let explicit_format = self
+            .message_values
+            .get(&message_id.0)
+            .is_some_and(|values| values.iter().any(|(name, _)| *name == "VFrameFormat"));
+        let format = attributes.get("VFrameFormat").and_then(AttributeValue::as_str);
+
+        if explicit_format || !self.protocol_j1939 {
+            format == Some("J1939PG")
+        } else {
+            message_id.0 & DBC_EXTENDED_ID_FLAG != 0
+        }
+    }
+}
+
 /// Convert a can-dbc message to our MessageDefinition
 fn convert_message(
     dbc_msg: &can_dbc::Message,
+    dbc: &can_dbc::DBC,
+    attributes: &DbcAttributes,
     source: &str,
 ) -> Result<MessageDefinition> {
     let mut signals = Vec::new();
-    let mut is_multiplexed = false;
-    let mut multiplexer_signal_name: Option<String> = None;
-
-    // First pass: identify multiplexer signal
-    for dbc_sig in dbc_msg.signals() {
-        if let can_dbc::MultiplexIndicator::Multiplexor = dbc_sig.multiplexer_indicator() {
-            is_multiplexed = true;
-            multiplexer_signal_name = Some(dbc_sig.name().to_string());
-            break;
-        } else if matches!(
-            dbc_sig.multiplexer_indicator(),
-            can_dbc::MultiplexIndicator::MultiplexedSignal(_)
-        ) {
-            is_multiplexed = true;
-        }
-    }
+    let is_multiplexed = dbc_msg
+        .signals()
+        .iter()
+        .any(|s| !matches!(s.multiplexer_indicator(), can_dbc::MultiplexIndicator::Plain));
+
+    // First pass: identify the top-level multiplexer signal (`M`, not `m3M`)
+    let multiplexer_signal_name = dbc_msg
+        .signals()
+        .iter()
+        .find(|s| matches!(s.multiplexer_indicator(), can_dbc::MultiplexIndicator::Multiplexor))
+        .map(|s| s.name().to_string());
 
     // Second pass: convert all signals
     for dbc_sig in dbc_msg.signals() {
-        let signal = convert_signal(dbc_sig, multiplexer_signal_name.as_deref())?;
+        // Extended multiplexing names the switch (and value ranges) per signal
+        let extended_multiplex = dbc.extended_multiplex().iter().find(|ext| {
+            ext.message_id() == dbc_msg.message_id() && ext.signal_name() == dbc_sig.name()
+        });
+        let mut signal = convert_signal(dbc_sig, multiplexer_signal_name.as_deref(), extended_multiplex)?;
+        signal.value_table = signal_value_table(dbc, *dbc_msg.message_id(), dbc_sig.name());
+        signal.attributes = attributes.signal_attributes(*dbc_msg.message_id(), dbc_sig.name());
+        signal.comment = dbc.signal_comment(*dbc_msg.message_id(), dbc_sig.name()).map(str::to_string);
+
+        // SIG_VALTYPE_ overrides the integer type for IEEE float signals
+        match dbc.extended_value_type_for_signal(*dbc_msg.message_id(), dbc_sig.name()) {
+            Some(can_dbc::SignalExtendedValueType::IEEEfloat32Bit) => {
+                signal.value_type = ValueType::Float32;
+            }
+            Some(can_dbc::SignalExtendedValueType::IEEEdouble64bit) => {
+                signal.value_type = ValueType::Float64;
+            }
+            _ => {}
+        }
         signals.push(signal);
     }
 
+    let message_attributes = attributes.message_attributes(*dbc_msg.message_id());
+
     Ok(MessageDefinition {
-        id: dbc_msg.message_id().0,  // Extract raw ID from MessageId tuple struct
+        id: dbc_msg.message_id().0 & !DBC_EXTENDED_ID_FLAG,  // Strip the extended-ID flag
         name: dbc_msg.message_name().to_string(),
         size: *dbc_msg.message_size() as usize,
         sender: match dbc_msg.transmitter() {
@@ -100,6 +283,10 @@ fn convert_message(
         is_multiplexed,
         multiplexer_signal: multiplexer_signal_name,
         source: source.to_string(),
+        is_j1939: attributes.is_j1939(*dbc_msg.message_id(), &message_attributes),
+        attributes: message_attributes,
+        comment: dbc.message_comment(*dbc_msg.message_id()).map(str::to_string),
+        channels: Vec::new(),
     })
 }
 
@@ -107,6 +294,7 @@ fn convert_message(
 fn convert_signal(
     dbc_sig: &can_dbc::Signal,
     multiplexer_signal_name: Option<&str>,
+    extended_multiplex: Option<&can_dbc::ExtendedMultiplex>,
 ) -> Result<SignalDefinition> {
     // Determine byte order
     let byte_order = match *dbc_sig.byte_order() {
@@ -120,12 +308,29 @@ fn convert_signal(
         can_dbc::ValueType::Unsigned => ValueType::Unsigned,
     };
 
-    // Extract value table if present
-    let value_table = None;  // TODO: can-dbc v5.0 API for value descriptions needs investigation
-
     // Handle multiplexer information
-    let multiplexer_info = match *dbc_sig.multiplexer_indicator() {
-        can_dbc::MultiplexIndicator::MultiplexedSignal(switch_value) => {
+    let multiplexer_info = match (*dbc_sig.multiplexer_indicator(), extended_multiplex) {
+        (
+            can_dbc::MultiplexIndicator::MultiplexedSignal(_)
+            | can_dbc::MultiplexIndicator::MultiplexorAndMultiplexedSignal(_),
+            Some(ext),
+        ) => {
+            let (single, ranges): (Vec<_>, Vec<_>) = ext
+                .mappings()
+                .iter()
+                .map(|m| (*m.min_value(), *m.max_value()))
+                .partition(|(min, max)| min == max);
+            Some(MultiplexerInfo {
+                multiplexer_signal: ext.multiplexor_signal_name().to_string(),
+                multiplexer_values: single.into_iter().map(|(value, _)| value).collect(),
+                multiplexer_ranges: ranges,
+            })
+        }
+        (
+            can_dbc::MultiplexIndicator::MultiplexedSignal(switch_value)
+            | can_dbc::MultiplexIndicator::MultiplexorAndMultiplexedSignal(switch_value),
+            None,
+        ) => {
             Some(MultiplexerInfo {
                 multiplexer_signal: multiplexer_signal_name
                     .ok_or_else(|| {
@@ -135,7 +340,8 @@ fn convert_signal(
                         ))
                     })?
                     .to_string(),
-                multiplexer_values: vec![switch_value as u64],  // switch_value is already u64
+                multiplexer_values: vec![switch_value],
+                multiplexer_ranges: Vec::new(),
             })
         }
         _ => None,
@@ -156,11 +362,53 @@ fn convert_signal(
         } else {
             Some(dbc_sig.unit().to_string())
         },
-        value_table,
+        value_table: None,  // Filled from VAL_/VAL_TABLE_ by convert_message
+        conversion: None,
         multiplexer_info,
+        attributes: HashMap::new(),  // Filled from BA_ by convert_message
+        comment: None,
+        extraction: None,
     })
 }
 
+/// Look up the value table of a signal
+///
+/// A `VAL_` entry for the signal takes precedence; otherwise the `VAL_TABLE_`
+/// referenced through the signal's type (`SIG_TYPE_REF_` → `SGTYPE_`) is used.
+fn signal_value_table(
+    dbc: &can_dbc::DBC,
+    message_id: can_dbc::MessageId,
+    signal_name: &str,
+) -> Option<HashMap<i64, String>> {
+    let descriptions = dbc
+        .value_descriptions_for_signal(message_id, signal_name)
+        .or_else(|| {
+            let type_ref = dbc
+                .signal_type_refs()
+                .iter()
+                .find(|r| *r.message_id() == message_id && r.signal_name() == signal_name)?;
+            let signal_type = dbc
+                .signal_types()
+                .iter()
+                .find(|t| t.signal_type_name() == type_ref.signal_type_name())?;
+            dbc.value_tables()
+                .iter()
+                .find(|t| t.value_table_name() == signal_type.value_table())
+                .map(|t| t.value_descriptions().as_slice())
+        })?;
+
+    if descriptions.is_empty() {
+        return None;
+    }
+
+    Some(
+        descriptions
+            .iter()
+            .map(|desc| (*desc.a() as i64, desc.b().clone()))
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,4 +526,219 @@ BO_ 512 MultiplexedMsg: 8 ECU1
             "Mode"
         );
     }
+
+    #[test]
+    fn test_parse_j1939_dbc() {
+        let dbc_content = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: Engine
+
+BO_ 2364540158 EEC1: 8 Engine
+ SG_ EngineSpeed : 24|16@1+ (0.125,0) [0|8031.875] "rpm" Vector__XXX
+
+BO_ 2566844926 CCVS1: 8 Vector__XXX
+ SG_ WheelBasedVehicleSpeed : 8|16@1+ (0.00390625,0) [0|250.996] "km/h" Vector__XXX
+
+BO_ 291 Diagnostic: 8 Engine
+ SG_ Counter : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+
+BA_DEF_ BO_  "VFrameFormat" ENUM  "StandardCAN","ExtendedCAN","reserved","J1939PG";
+BA_DEF_DEF_  "VFrameFormat" "J1939PG";
+BA_ "VFrameFormat" BO_ 2566844926 1;
+BA_ "VFrameFormat" BO_ 291 0;
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(dbc_content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let messages = parse_dbc_file(temp_file.path()).unwrap();
+        assert_eq!(messages.len(), 3);
+
+        // Extended-ID flag stripped; default VFrameFormat applies
+        assert_eq!(messages[0].id, 0x0CF004FE);
+        assert!(messages[0].is_j1939);
+        // Explicit ExtendedCAN overrides the J1939PG default
+        assert_eq!(messages[1].id, 0x18FEF1FE);
+        assert!(!messages[1].is_j1939);
+        assert!(!messages[2].is_j1939);
+    }
+
+    #[test]
+    fn test_parse_value_tables_and_value_types() {
+        let dbc_content = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU1
+
+VAL_TABLE_ GearTable 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+
+BO_ 256 Transmission: 8 ECU1
+ SG_ Gear : 0|4@1+ (1,0) [0|15] "" Vector__XXX
+ SG_ Torque : 8|16@1- (1,0) [-1000|1000] "Nm" Vector__XXX
+ SG_ Temp : 24|8@1+ (1,-40) [-40|215] "C" Vector__XXX
+
+VAL_ 256 Gear 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+VAL_ 256 Torque -1 "Invalid" 0 "Zero" ;
+
+SIG_VALTYPE_ 256 Temp : 1;
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(dbc_content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let messages = parse_dbc_file(temp_file.path()).unwrap();
+        let msg = &messages[0];
+
+        let gear = msg.signals.iter().find(|s| s.name == "Gear").unwrap();
+        let table = gear.value_table.as_ref().unwrap();
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[&3], "Drive");
+
+        let torque = msg.signals.iter().find(|s| s.name == "Torque").unwrap();
+        assert_eq!(torque.value_table.as_ref().unwrap()[&-1], "Invalid");
+
+        let temp = msg.signals.iter().find(|s| s.name == "Temp").unwrap();
+        assert!(temp.value_table.is_none());
+        assert_eq!(temp.value_type, ValueType::Float32);
+        assert_eq!(gear.value_type, ValueType::Unsigned);
+
+        // Decoded signals carry the description of the raw value
+        let decoded =
+            crate::message_decoder::MessageDecoder::decode_signal(&[0x03, 0xFF, 0xFF, 0], gear).unwrap();
+        assert_eq!(decoded.value_description.as_deref(), Some("Drive"));
+        let decoded =
+            crate::message_decoder::MessageDecoder::decode_signal(&[0x03, 0xFF, 0xFF, 0], torque).unwrap();
+        assert_eq!(decoded.value_description.as_deref(), Some("Invalid"));
+    }
+
+    #[test]
+    fn test_parse_extended_multiplexing() {
+        let dbc_content = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU1
+
+BO_ 768 ExtMux: 8 ECU1
+ SG_ Mux1 M : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Mux2 m1M : 8|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ SigA m0 : 16|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ SigB m2 : 16|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ SigC m3 : 24|8@1+ (1,0) [0|255] "" Vector__XXX
+
+SG_MUL_VAL_ 768 Mux2 Mux1 1-1;
+SG_MUL_VAL_ 768 SigA Mux1 0-0;
+SG_MUL_VAL_ 768 SigB Mux2 2-2;
+SG_MUL_VAL_ 768 SigC Mux1 3-5, 7-7;
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(dbc_content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let messages = parse_dbc_file(temp_file.path()).unwrap();
+        let msg = &messages[0];
+        assert!(msg.is_multiplexed);
+        assert_eq!(msg.multiplexer_signal.as_deref(), Some("Mux1"));
+
+        let mux2 = msg.signals.iter().find(|s| s.name == "Mux2").unwrap();
+        assert_eq!(mux2.multiplexer_info.as_ref().unwrap().multiplexer_signal, "Mux1");
+        let sig_c = msg.signals.iter().find(|s| s.name == "SigC").unwrap().multiplexer_info.as_ref().unwrap();
+        assert_eq!(sig_c.multiplexer_ranges, vec![(3, 5)]);
+        assert_eq!(sig_c.multiplexer_values, vec![7]);
+        assert!(sig_c.is_active_for(4) && sig_c.is_active_for(7) && !sig_c.is_active_for(6));
+
+        let active = |data: &[u8]| -> Vec<String> {
+            match crate::message_decoder::MessageDecoder::decode_pdu_data(data, msg, chrono::Utc::now(), 0, msg.id) {
+                Some(crate::types::DecodedEvent::Message { signals, .. }) => {
+                    signals.into_iter().map(|s| s.name.to_string()).collect()
+                }
+                _ => Vec::new(),
+            }
+        };
+        assert_eq!(active(&[0, 2, 0, 0]), vec!["Mux1", "SigA"]);
+        // Nested chain: Mux1 = 1 enables Mux2, Mux2 = 2 enables SigB
+        assert_eq!(active(&[1, 2, 0, 0]), vec!["Mux1", "Mux2", "SigB"]);
+        // Mux2 = 2 is ignored while Mux2 itself is inactive
+        assert_eq!(active(&[4, 2, 0, 0]), vec!["Mux1", "SigC"]);
+        assert_eq!(active(&[6, 2, 0, 0]), vec!["Mux1"]);
+    }
+
+    #[test]
+    fn test_parse_attributes_and_comments() {
+        let dbc_content = r#"
+VERSION ""
+
+NS_ :
+
+BS_:
+
+BU_: ECU1
+
+BO_ 256 Status: 8 ECU1
+ SG_ Counter : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+ SG_ Mode : 8|8@1+ (1,0) [0|255] "" Vector__XXX
+
+BO_ 257 Event: 8 ECU1
+ SG_ Code : 0|8@1+ (1,0) [0|255] "" Vector__XXX
+
+CM_ BO_ 256 "Cyclic status message";
+CM_ SG_ 256 Counter "Alive counter";
+BA_DEF_ BO_  "GenMsgCycleTime" INT 0 65535;
+BA_DEF_ BO_  "GenMsgSendType" ENUM  "Cyclic","Event";
+BA_DEF_ SG_  "GenSigStartValue" FLOAT 0 1000;
+BA_DEF_DEF_  "GenMsgCycleTime" 0;
+BA_DEF_DEF_  "GenMsgSendType" "Cyclic";
+BA_DEF_DEF_  "GenSigStartValue" 0;
+BA_ "GenMsgCycleTime" BO_ 256 100;
+BA_ "GenMsgSendType" BO_ 257 1;
+BA_ "GenSigStartValue" SG_ 256 Mode 2.5;
+"#;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(dbc_content.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut db = crate::signals::SignalDatabase::new();
+        for message in parse_dbc_file(temp_file.path()).unwrap() {
+            db.add_message(message);
+        }
+
+        let status = db.get_message(256).unwrap();
+        assert_eq!(status.cycle_time_ms(), Some(100));
+        assert_eq!(status.attribute("GenMsgSendType").and_then(|v| v.as_str()), Some("Cyclic"));
+        assert_eq!(db.message_comment(256), Some("Cyclic status message"));
+        assert_eq!(db.signal_comment(256, "Counter"), Some("Alive counter"));
+        assert_eq!(db.signal_comment(256, "Mode"), None);
+        assert_eq!(
+            db.signal_attribute(256, "Mode", "GenSigStartValue"),
+            Some(&AttributeValue::Float(2.5))
+        );
+        assert_eq!(
+            db.signal_attribute(256, "Counter", "GenSigStartValue"),
+            Some(&AttributeValue::Float(0.0))
+        );
+
+        // Defaults apply; enum indices resolve to names
+        assert_eq!(db.get_message(257).unwrap().cycle_time_ms(), None);
+        assert_eq!(
+            db.message_attribute(257, "GenMsgSendType"),
+            Some(&AttributeValue::String("Event".to_string()))
+        );
+        assert!(db.message_attribute(257, "Unknown").is_none());
+    }
 }
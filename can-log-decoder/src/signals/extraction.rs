@@ -0,0 +1,198 @@
+//! Precompiled signal extraction
+//!
+//! Turns a signal's start bit, length and byte order into a byte window, a
+//! shift and a mask once, so that decoding a frame loads the window and
+//! applies two bit operations instead of walking the signal bit by bit.
+//! Plans are compiled when messages are added to the [`SignalDatabase`](super::SignalDatabase).
+//!
+//! Motorola (big-endian) signals use DBC sawtooth bit numbering: the start bit
+//! is the MSB, and the signal continues towards bit 0 of its byte, then at
+//! bit 7 of the next byte.
+
+use super::database::{ByteOrder, SignalDefinition, ValueType};
+use std::sync::Arc;
+
+/// Number of frame bytes a signal occupies, counted from byte 0
+pub(crate) fn required_bytes(byte_order: ByteOrder, start_bit: usize, length: usize) -> usize {
+    match byte_order {
+        ByteOrder::LittleEndian => (start_bit + length).div_ceil(8),
+        ByteOrder::BigEndian => {
+            // Bits available in the MSB byte, then whole bytes following it
+            let first_byte_bits = start_bit % 8 + 1;
+            let extra_bytes = length.saturating_sub(first_byte_bits).div_ceil(8);
+            start_bit / 8 + 1 + extra_bytes
+        }
+    }
+}
+
+/// Frame bit positions (byte * 8 + bit) of a signal, least significant bit first
+pub(crate) fn signal_bits(byte_order: ByteOrder, start_bit: usize, length: usize) -> Vec<usize> {
+    match byte_order {
+        ByteOrder::LittleEndian => (start_bit..start_bit + length).collect(),
+        ByteOrder::BigEndian => {
+            // Walk down from the MSB, jumping to bit 7 of the next byte after bit 0
+            let next = |&bit: &usize| Some(if bit.is_multiple_of(8) { bit + 15 } else { bit - 1 });
+            let mut bits: Vec<usize> = std::iter::successors(Some(start_bit), next).take(length).collect();
+            bits.reverse();
+            bits
+        }
+    }
+}
+
+/// Precompiled extraction of one signal
+///
+/// Also holds the signal name and unit shared with every decoded value, so
+/// decoding does not allocate per signal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractionPlan {
+    /// Signal name
+    pub name: Arc<str>,
+    /// Engineering unit
+    pub unit: Option<Arc<str>>,
+    pub(crate) window: BitWindow,
+}
+
+impl ExtractionPlan {
+    /// Compile the extraction plan of a signal
+    pub fn new(signal: &SignalDefinition) -> Self {
+        Self {
+            name: signal.name.as_str().into(),
+            unit: signal.unit.as_deref().map(Into::into),
+            window: BitWindow::new(signal),
+        }
+    }
+
+    /// Extract the raw value of the signal (`None` if the data is too short)
+    pub fn extract(&self, data: &[u8]) -> Option<i64> {
+        self.window.extract(data)
+    }
+}
+
+/// Bytes holding a signal and how to cut the signal out of them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BitWindow {
+    byte_order: ByteOrder,
+    first_byte: usize,
+    byte_count: usize,
+    shift: u32,
+    mask: u64,
+    /// Length in bits if the value is sign-extended
+    signed_length: Option<u32>,
+}
+
+impl BitWindow {
+    pub(crate) fn new(signal: &SignalDefinition) -> Self {
+        let start_bit = signal.start_bit as usize;
+        let length = (signal.length as usize).min(64);
+        let first_byte = start_bit / 8;
+        let byte_count = required_bytes(signal.byte_order, start_bit, length) - first_byte;
+
+        let shift = match signal.byte_order {
+            // Little-endian: the start bit is the LSB, counted from the first byte
+            ByteOrder::LittleEndian => start_bit % 8,
+            // Big-endian: the start bit is the MSB, the LSB sits `shift` bits above
+            // the end of the window (windows are loaded first byte most significant)
+            ByteOrder::BigEndian => byte_count * 8 + start_bit % 8 + 1 - 8 - length,
+        };
+
+        Self {
+            byte_order: signal.byte_order,
+            first_byte,
+            byte_count,
+            shift: shift as u32,
+            mask: if length >= 64 { u64::MAX } else { (1u64 << length) - 1 },
+            signed_length: (signal.value_type == ValueType::Signed && length > 0).then_some(length as u32),
+        }
+    }
+
+    /// Number of frame bytes the signal occupies, counted from byte 0
+    pub(crate) fn required_bytes(&self) -> usize {
+        self.first_byte + self.byte_count
+    }
+
+    /// Extract the raw value, sign-extended for signed signals (float bits are kept as-is)
+    pub(crate) fn extract(&self, data: &[u8]) -> Option<i64> {
+        let bytes = data.get(self.first_byte..self.required_bytes())?;
+        // At most 9 bytes (64-bit signal not aligned to a byte)
+        let window = match self.byte_order {
+            ByteOrder::LittleEndian => bytes.iter().rev().fold(0u128, |acc, &b| (acc << 8) | b as u128),
+            ByteOrder::BigEndian => bytes.iter().fold(0u128, |acc, &b| (acc << 8) | b as u128),
+        };
+        let raw = (window >> self.shift) as u64 & self.mask;
+
+        Some(match self.signed_length {
+            Some(length) if length < 64 => {
+                // Move the sign bit to bit 63, then shift back arithmetically
+                ((raw << (64 - length)) as i64) >> (64 - length)
+            }
+            _ => raw as i64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(start_bit: u16, length: u16, byte_order: ByteOrder, value_type: ValueType) -> SignalDefinition {
+        let mut signal = SignalDefinition::test("Value", start_bit, length, byte_order);
+        signal.value_type = value_type;
+        signal.unit = Some("V".to_string());
+        signal
+    }
+
+    fn extract(data: &[u8], start_bit: u16, length: u16, byte_order: ByteOrder, value_type: ValueType) -> Option<i64> {
+        ExtractionPlan::new(&signal(start_bit, length, byte_order, value_type)).extract(data)
+    }
+
+    #[test]
+    fn test_required_bytes_and_bits() {
+        // 12 bits, MSB at bit 7 of byte 0, LSB at bit 4 of byte 1
+        assert_eq!(required_bytes(ByteOrder::BigEndian, 7, 12), 2);
+        assert_eq!(required_bytes(ByteOrder::BigEndian, 3, 4), 1);
+        assert_eq!(required_bytes(ByteOrder::LittleEndian, 4, 12), 2);
+
+        assert_eq!(signal_bits(ByteOrder::BigEndian, 7, 12), [12, 13, 14, 15, 0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(signal_bits(ByteOrder::BigEndian, 3, 4), [0, 1, 2, 3]);
+        assert_eq!(signal_bits(ByteOrder::LittleEndian, 4, 3), [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_extract_little_endian() {
+        let data = [0xAB, 0xCD, 0xEF, 0x12];
+        assert_eq!(extract(&data, 0, 8, ByteOrder::LittleEndian, ValueType::Unsigned), Some(0xAB));
+        assert_eq!(extract(&data, 0, 16, ByteOrder::LittleEndian, ValueType::Unsigned), Some(0xCDAB));
+        assert_eq!(extract(&data, 4, 12, ByteOrder::LittleEndian, ValueType::Unsigned), Some(0xCDA));
+        assert_eq!(extract(&data, 20, 12, ByteOrder::LittleEndian, ValueType::Unsigned), Some(0x12E));
+        assert_eq!(extract(&data, 24, 9, ByteOrder::LittleEndian, ValueType::Unsigned), None);
+
+        // 64-bit signal spanning 9 bytes
+        let data = [0xF0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F];
+        assert_eq!(extract(&data, 4, 64, ByteOrder::LittleEndian, ValueType::Unsigned), Some(-1));
+    }
+
+    #[test]
+    fn test_extract_big_endian() {
+        let data = [0xAB, 0xCD, 0xEF, 0x12];
+        assert_eq!(extract(&data, 7, 8, ByteOrder::BigEndian, ValueType::Unsigned), Some(0xAB));
+        // MSB at bit 7 of byte 0, LSB at bit 4 of byte 1
+        assert_eq!(extract(&data, 7, 12, ByteOrder::BigEndian, ValueType::Unsigned), Some(0xABC));
+        // MSB at bit 3 of byte 0, within one byte
+        assert_eq!(extract(&data, 3, 4, ByteOrder::BigEndian, ValueType::Unsigned), Some(0xB));
+        assert_eq!(extract(&data, 11, 12, ByteOrder::BigEndian, ValueType::Unsigned), Some(0xDEF));
+        assert_eq!(extract(&data, 23, 24, ByteOrder::BigEndian, ValueType::Unsigned), None);
+    }
+
+    #[test]
+    fn test_sign_extension() {
+        assert_eq!(extract(&[0x7F], 0, 8, ByteOrder::LittleEndian, ValueType::Signed), Some(127));
+        assert_eq!(extract(&[0xFF], 0, 8, ByteOrder::LittleEndian, ValueType::Signed), Some(-1));
+        assert_eq!(extract(&[0x00, 0x80], 0, 16, ByteOrder::LittleEndian, ValueType::Signed), Some(-32768));
+        assert_eq!(extract(&[0x80, 0x00], 7, 16, ByteOrder::BigEndian, ValueType::Signed), Some(-32768));
+        // Float bits are never sign-extended
+        assert_eq!(extract(&[0, 0, 0, 0x80], 0, 32, ByteOrder::LittleEndian, ValueType::Float32), Some(0x8000_0000));
+
+        let plan = ExtractionPlan::new(&signal(0, 8, ByteOrder::LittleEndian, ValueType::Unsigned));
+        assert_eq!((&*plan.name, plan.unit.as_deref()), ("Value", Some("V")));
+    }
+}
@@ -6,10 +6,14 @@
 pub mod dbc;
 pub mod arxml;
 pub mod database;
+pub mod extraction;
+pub mod validation;
 
 // Re-export key types for convenience
 pub use database::{
-    ByteOrder, ContainerDefinition, ContainerLayout, ContainedPduInfo,
-    MessageDefinition, MultiplexerInfo, SignalDatabase, SignalDefinition,
-    ValueType, DatabaseStats,
+    AttributeValue, ByteOrder, ContainerDefinition, ContainerLayout, ContainedPduInfo,
+    ConversionScale, MessageDefinition, MultiplexerInfo, RationalScale, SecuredPduDefinition,
+    SignalConversion, SignalDatabase, SignalDefinition, ValueType, DatabaseStats,
 };
+pub use extraction::ExtractionPlan;
+pub use validation::{SharedSignalName, ValidationIssue, ValidationReport};
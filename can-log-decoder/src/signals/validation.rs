@@ -0,0 +1,384 @@
+//! Signal database consistency checks
+//!
+//! Loading several DBC/ARXML files can stack conflicting definitions under one
+//! CAN ID or name. [`SignalDatabase::validate`] reports such conflicts and
+//! definitions that cannot decode correctly.
+
+use crate::signals::database::{
+    ByteOrder, ContainerDefinition, ContainerLayout, MessageDefinition, MultiplexerInfo,
+    SignalDatabase, SignalDefinition, ValueType,
+};
+use crate::signals::extraction::signal_bits;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A problem found in the signal database
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// Several definitions with different layouts share a CAN ID (and channel)
+    DuplicateId {
+        can_id: u32,
+        /// "Name (source)" of each definition
+        messages: Vec<String>,
+    },
+    /// A message name is defined under several CAN IDs; lookup by name keeps the last
+    DuplicateName { name: String, can_ids: Vec<u32> },
+    /// Two signals that can be active together occupy the same bits
+    OverlappingSignals {
+        message: String,
+        signals: (String, String),
+    },
+    /// A signal extends past the message size
+    SignalOutOfBounds {
+        message: String,
+        signal: String,
+        /// Message size in bytes
+        message_size: usize,
+    },
+    /// A multiplexed signal refers to a multiplexer the message does not define
+    MissingMultiplexer {
+        message: String,
+        signal: String,
+        multiplexer: String,
+    },
+    /// A container refers to a contained PDU without a definition
+    UndefinedContainedPdu { container: String, pdu: String },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateId { can_id, messages } => {
+                write!(f, "CAN ID 0x{:X} has conflicting definitions: {}", can_id, messages.join(", "))
+            }
+            Self::DuplicateName { name, can_ids } => {
+                let ids: Vec<_> = can_ids.iter().map(|id| format!("0x{:X}", id)).collect();
+                write!(f, "Message {} is defined under several CAN IDs: {}", name, ids.join(", "))
+            }
+            Self::OverlappingSignals { message, signals } => {
+                write!(f, "Signals {} and {} overlap in message {}", signals.0, signals.1, message)
+            }
+            Self::SignalOutOfBounds { message, signal, message_size } => write!(
+                f,
+                "Signal {} extends past the {} byte(s) of message {}",
+                signal, message_size, message
+            ),
+            Self::MissingMultiplexer { message, signal, multiplexer } => write!(
+                f,
+                "Signal {} in message {} refers to missing multiplexer {}",
+                signal, message, multiplexer
+            ),
+            Self::UndefinedContainedPdu { container, pdu } => {
+                write!(f, "Container {} carries undefined PDU {}", container, pdu)
+            }
+        }
+    }
+}
+
+/// A signal name used by several messages
+///
+/// Lookups by signal name bind to the first message defining it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedSignalName {
+    /// Signal name
+    pub name: String,
+    /// Messages defining a signal with this name
+    pub messages: Vec<String>,
+}
+
+/// Result of [`SignalDatabase::validate`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Conflicts and inconsistent definitions
+    pub issues: Vec<ValidationIssue>,
+    /// Signal names that appear in several messages (informational)
+    pub shared_signal_names: Vec<SharedSignalName>,
+}
+
+impl ValidationReport {
+    /// True if no issues were found (shared signal names are not issues)
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl SignalDatabase {
+    /// Check the loaded definitions for conflicts and inconsistencies
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        // Duplicate CAN IDs and names
+        let mut by_id: BTreeMap<u32, Vec<&MessageDefinition>> = BTreeMap::new();
+        let mut ids_by_name: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+        for message in self.messages() {
+            by_id.entry(message.id).or_default().push(message);
+            let ids = ids_by_name.entry(&message.name).or_default();
+            if !ids.contains(&message.id) {
+                ids.push(message.id);
+            }
+        }
+        for (&can_id, messages) in &by_id {
+            let conflicting = messages.iter().enumerate().any(|(i, a)| {
+                messages[i + 1..].iter().any(|b| share_channel(a, b) && !same_layout(a, b))
+            });
+            if conflicting {
+                issues.push(ValidationIssue::DuplicateId {
+                    can_id,
+                    messages: messages.iter().map(|m| format!("{} ({})", m.name, m.source)).collect(),
+                });
+            }
+        }
+        for (name, mut can_ids) in ids_by_name {
+            if can_ids.len() > 1 {
+                can_ids.sort_unstable();
+                issues.push(ValidationIssue::DuplicateName { name: name.to_string(), can_ids });
+            }
+        }
+
+        // Signal layout of every message, including contained PDUs
+        let mut messages: Vec<_> = self.messages().chain(self.contained_pdus()).collect();
+        messages.sort_by(|a, b| (a.id, &a.name).cmp(&(b.id, &b.name)));
+        for message in &messages {
+            Self::validate_signals(message, &mut issues);
+        }
+
+        // Contained PDUs must resolve to a message, secured PDU or container
+        let mut containers: Vec<_> = self.containers().collect();
+        containers.sort_by(|a, b| a.name.cmp(&b.name));
+        for container in containers {
+            self.validate_container(container, &mut issues);
+        }
+
+        // Signal names used by several messages
+        let mut signal_messages: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for message in &messages {
+            for signal in &message.signals {
+                let names = signal_messages.entry(&signal.name).or_default();
+                if !names.contains(&message.name) {
+                    names.push(message.name.clone());
+                }
+            }
+        }
+        let shared_signal_names = signal_messages
+            .into_iter()
+            .filter(|(_, messages)| messages.len() > 1)
+            .map(|(name, messages)| SharedSignalName { name: name.to_string(), messages })
+            .collect();
+
+        ValidationReport { issues, shared_signal_names }
+    }
+
+    fn validate_signals(message: &MessageDefinition, issues: &mut Vec<ValidationIssue>) {
+        let bits: Vec<Vec<usize>> = message
+            .signals
+            .iter()
+            .map(|s| signal_bits(s.byte_order, s.start_bit as usize, s.length as usize))
+            .collect();
+        let message_bits = message.size * 8;
+
+        for (i, signal) in message.signals.iter().enumerate() {
+            if bits[i].iter().any(|&bit| bit >= message_bits) {
+                issues.push(ValidationIssue::SignalOutOfBounds {
+                    message: message.name.clone(),
+                    signal: signal.name.clone(),
+                    message_size: message.size,
+                });
+            }
+
+            if let Some(mux) = &signal.multiplexer_info {
+                if message.signal(&mux.multiplexer_signal).is_none() {
+                    issues.push(ValidationIssue::MissingMultiplexer {
+                        message: message.name.clone(),
+                        signal: signal.name.clone(),
+                        multiplexer: mux.multiplexer_signal.clone(),
+                    });
+                }
+            }
+
+            for (j, other) in message.signals.iter().enumerate().skip(i + 1) {
+                if can_be_active_together(signal, other)
+                    && bits[i].iter().any(|bit| bits[j].contains(bit))
+                {
+                    issues.push(ValidationIssue::OverlappingSignals {
+                        message: message.name.clone(),
+                        signals: (signal.name.clone(), other.name.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    fn validate_container(&self, container: &ContainerDefinition, issues: &mut Vec<ValidationIssue>) {
+        let pdus = match &container.layout {
+            ContainerLayout::Static { pdus } | ContainerLayout::Dynamic { pdus, .. } => pdus,
+            ContainerLayout::Queued { pdu_id, .. } => {
+                if self.get_message(*pdu_id).is_none() {
+                    issues.push(ValidationIssue::UndefinedContainedPdu {
+                        container: container.name.clone(),
+                        pdu: format!("PDU_{}", pdu_id),
+                    });
+                }
+                return;
+            }
+        };
+        for pdu in pdus {
+            let name = self
+                .get_secured_pdu(&pdu.name)
+                .map_or(pdu.name.as_str(), |secured| secured.payload_pdu.as_str());
+            if self.get_message_by_name(name).is_none() && self.get_contained_container(name).is_none() {
+                issues.push(ValidationIssue::UndefinedContainedPdu {
+                    container: container.name.clone(),
+                    pdu: name.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// True if two definitions can apply to frames on the same channel
+fn share_channel(a: &MessageDefinition, b: &MessageDefinition) -> bool {
+    a.channels.is_empty() || b.channels.is_empty() || a.channels.iter().any(|c| b.channels.contains(c))
+}
+
+/// True if two message definitions decode a frame identically
+fn same_layout(a: &MessageDefinition, b: &MessageDefinition) -> bool {
+    a.size == b.size && signal_layout(a) == signal_layout(b)
+}
+
+type SignalLayout = (u16, u16, ByteOrder, ValueType, u64, u64);
+
+fn signal_layout(message: &MessageDefinition) -> HashMap<&str, SignalLayout> {
+    message
+        .signals
+        .iter()
+        .map(|s| {
+            let layout = (s.start_bit, s.length, s.byte_order, s.value_type, s.factor.to_bits(), s.offset.to_bits());
+            (s.name.as_str(), layout)
+        })
+        .collect()
+}
+
+/// False if the signals belong to different values of the same multiplexer
+fn can_be_active_together(a: &SignalDefinition, b: &SignalDefinition) -> bool {
+    match (&a.multiplexer_info, &b.multiplexer_info) {
+        (Some(a), Some(b)) if a.multiplexer_signal == b.multiplexer_signal => share_mux_value(a, b),
+        // Different multiplexers (extended multiplexing) are not compared
+        (Some(_), Some(_)) => false,
+        _ => true,
+    }
+}
+
+fn share_mux_value(a: &MultiplexerInfo, b: &MultiplexerInfo) -> bool {
+    a.multiplexer_values.iter().any(|&value| b.is_active_for(value))
+        || b.multiplexer_values.iter().any(|&value| a.is_active_for(value))
+        || a.multiplexer_ranges.iter().any(|&(a_min, a_max)| {
+            b.multiplexer_ranges.iter().any(|&(b_min, b_max)| a_min <= b_max && b_min <= a_max)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_database() {
+        let mut db = SignalDatabase::new();
+        db.add_message(MessageDefinition::test(
+            0x100,
+            "Mux",
+            2,
+            vec![
+                SignalDefinition::test("Mode", 0, 8, ByteOrder::LittleEndian),
+                SignalDefinition::test("A", 8, 8, ByteOrder::LittleEndian).multiplexed_by("Mode", 0),
+                SignalDefinition::test("B", 8, 8, ByteOrder::LittleEndian).multiplexed_by("Mode", 1),
+            ],
+        ));
+        // Same layout loaded twice is not a conflict
+        let speed = SignalDefinition::test("Speed", 7, 16, ByteOrder::BigEndian);
+        db.add_message(MessageDefinition::test(0x200, "Speed", 2, vec![speed.clone()]));
+        db.add_message(MessageDefinition::test(0x200, "Speed", 2, vec![speed]));
+
+        let report = db.validate();
+        assert!(report.is_ok(), "{:?}", report.issues);
+        assert!(report.shared_signal_names.is_empty());
+    }
+
+    #[test]
+    fn test_report_issues() {
+        let mut db = SignalDatabase::new();
+        db.add_message(MessageDefinition::test(
+            0x100,
+            "Engine",
+            2,
+            vec![
+                SignalDefinition::test("Speed", 0, 12, ByteOrder::LittleEndian),
+                // Motorola MSB at bit 7 of byte 1 covers byte 1 and bit 7 of byte 2
+                SignalDefinition::test("Temp", 15, 9, ByteOrder::BigEndian),
+                SignalDefinition::test("Gear", 12, 4, ByteOrder::LittleEndian).multiplexed_by("Selector", 1),
+            ],
+        ));
+        let speed = SignalDefinition::test("Speed", 0, 16, ByteOrder::LittleEndian);
+        db.add_message(MessageDefinition::test(0x100, "Private", 8, vec![speed]));
+        db.add_message(MessageDefinition::test(0x101, "Private", 8, Vec::new()));
+
+        let report = db.validate();
+        let issues: Vec<String> = report.issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            issues,
+            [
+                "CAN ID 0x100 has conflicting definitions: Engine (test.dbc), Private (test.dbc)",
+                "Message Private is defined under several CAN IDs: 0x100, 0x101",
+                "Signals Speed and Temp overlap in message Engine",
+                "Signal Temp extends past the 2 byte(s) of message Engine",
+                "Signals Temp and Gear overlap in message Engine",
+                "Signal Gear in message Engine refers to missing multiplexer Selector",
+            ]
+        );
+        assert_eq!(
+            report.shared_signal_names,
+            [SharedSignalName {
+                name: "Speed".to_string(),
+                messages: vec!["Engine".to_string(), "Private".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_undefined_contained_pdu() {
+        use crate::signals::database::ContainedPduInfo;
+        use crate::types::ContainerType;
+
+        let mut db = SignalDatabase::new();
+        db.add_contained_pdu(MessageDefinition::test(0x10, "Known", 1, Vec::new()));
+        db.add_container(ContainerDefinition {
+            id: 0x300,
+            name: "Container".to_string(),
+            container_type: ContainerType::Dynamic,
+            layout: ContainerLayout::Dynamic {
+                header_size: 4,
+                pdus: ["Known", "Unknown"]
+                    .iter()
+                    .map(|name| ContainedPduInfo {
+                        pdu_id: 0,
+                        name: name.to_string(),
+                        position: 0,
+                        size: 1,
+                        update_bit: None,
+                    })
+                    .collect(),
+            },
+            unused_bit_pattern: 0,
+            channels: Vec::new(),
+            source: "test.arxml".to_string(),
+        });
+
+        let report = db.validate();
+        assert_eq!(
+            report.issues,
+            [ValidationIssue::UndefinedContainedPdu {
+                container: "Container".to_string(),
+                pdu: "Unknown".to_string(),
+            }]
+        );
+    }
+}
@@ -7,6 +7,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::Arc;
 
 /// Timestamp type used throughout the decoder
 pub type Timestamp = DateTime<Utc>;
@@ -21,7 +22,12 @@ pub type Result<T> = std::result::Result<T, DecoderError>;
 #[derive(Debug, Clone, PartialEq)]
 pub struct CanFrame {
     /// Timestamp in nanoseconds since epoch
+    ///
+    /// Relative to the measurement start if the log does not record a start
+    /// time (`measurement_start_ns` is then 0).
     pub timestamp_ns: u64,
+    /// Measurement start in nanoseconds since epoch (0 if unknown)
+    pub measurement_start_ns: u64,
     /// CAN channel number (e.g., 0, 1, 2...)
     pub channel: u8,
     /// CAN message ID (11-bit or 29-bit)
@@ -38,12 +44,27 @@ pub struct CanFrame {
     pub is_remote_frame: bool,
 }
 
+/// Convert nanoseconds since epoch to a Timestamp
+pub(crate) fn timestamp_from_ns(ns: u64) -> Timestamp {
+    let secs = (ns / 1_000_000_000) as i64;
+    let nsecs = (ns % 1_000_000_000) as u32;
+    DateTime::from_timestamp(secs, nsecs).unwrap_or_else(Utc::now)
+}
+
 impl CanFrame {
     /// Convert timestamp from nanoseconds to DateTime<Utc>
     pub fn timestamp(&self) -> Timestamp {
-        let secs = (self.timestamp_ns / 1_000_000_000) as i64;
-        let nsecs = (self.timestamp_ns % 1_000_000_000) as u32;
-        DateTime::from_timestamp(secs, nsecs).unwrap_or_else(|| Utc::now())
+        timestamp_from_ns(self.timestamp_ns)
+    }
+
+    /// Nanoseconds since the measurement start
+    pub fn relative_timestamp_ns(&self) -> u64 {
+        self.timestamp_ns.saturating_sub(self.measurement_start_ns)
+    }
+
+    /// Time since the measurement start
+    pub fn relative_time(&self) -> std::time::Duration {
+        std::time::Duration::from_nanos(self.relative_timestamp_ns())
     }
 
     /// Get the data length code (DLC) - number of data bytes
@@ -64,18 +85,27 @@ pub enum DecoderError {
     #[error("Failed to parse ARXML file: {0}")]
     ArxmlParseError(String),
 
+    #[error("Failed to parse UDS description file: {0}")]
+    UdsDescriptionParseError(String),
+
     #[error("Signal not found: {0}")]
     SignalNotFound(String),
 
     #[error("Message not found: CAN ID 0x{0:X}")]
     MessageNotFound(u32),
 
+    #[error("Message not found: {0}")]
+    MessageNameNotFound(String),
+
     #[error("Invalid signal definition: {0}")]
     InvalidSignalDefinition(String),
 
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
+    #[error("Failed to encode message: {0}")]
+    EncodeError(String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -98,12 +128,16 @@ pub enum DecodedEvent {
         message_name: Option<String>,
         /// Sender ECU name from DBC/ARXML (if available)
         sender: Option<String>,
+        /// J1939 source address (low byte of the CAN ID) for J1939 messages
+        source_address: Option<u8>,
         /// All decoded signals in this message
         signals: Vec<DecodedSignal>,
         /// True if this message contains multiplexed signals
         is_multiplexed: bool,
         /// Active multiplexer value (if message is multiplexed)
         multiplexer_value: Option<u64>,
+        /// Container PDU this message was unpacked from (None for plain frames)
+        container: Option<ParentContainer>,
     },
 
     /// A reconstructed CAN-TP (ISO-TP) message with complete payload
@@ -112,22 +146,89 @@ pub enum DecodedEvent {
         timestamp: Timestamp,
         /// CAN channel number
         channel: u8,
-        /// Source address (request ID)
+        /// Source address (N_SA)
+        ///
+        /// The CAN ID the payload was sent on for normal addressing, the 8-bit
+        /// address for normal fixed, extended and 29-bit mixed addressing.
         source_addr: u32,
-        /// Target address (response ID)
+        /// Target address (N_TA): the paired CAN ID or 8-bit address of the receiver
         target_addr: u32,
+        /// Address extension (N_AE), mixed addressing only
+        address_extension: Option<u8>,
         /// Complete reassembled payload bytes
         payload: Vec<u8>,
         /// Total length of the payload
         payload_length: usize,
     },
 
+    /// A CAN-TP (ISO-TP) transfer that was aborted or violated the protocol
+    CanTpError {
+        /// Timestamp of the frame where the error was detected
+        timestamp: Timestamp,
+        /// CAN channel number
+        channel: u8,
+        /// Source address of the sender (same meaning as in `CanTpMessage`)
+        source_addr: u32,
+        /// Target address of the receiver (same meaning as in `CanTpMessage`)
+        target_addr: u32,
+        /// What went wrong
+        error: CanTpErrorKind,
+    },
+
+    /// A UDS (ISO 14229) service interpreted from a CAN-TP payload
+    UdsMessage {
+        /// Timestamp of the underlying CAN-TP message
+        timestamp: Timestamp,
+        /// CAN channel number
+        channel: u8,
+        /// Source address (same meaning as in `CanTpMessage`)
+        source_addr: u32,
+        /// Target address (same meaning as in `CanTpMessage`)
+        target_addr: u32,
+        /// Interpreted request or response
+        uds: UdsMessage,
+    },
+
+    /// A reassembled J1939 transport protocol (BAM, CMDT or ETP) message
+    J1939TpMessage {
+        /// Timestamp of the connection management frame (RTS/BAM)
+        timestamp: Timestamp,
+        /// CAN channel number
+        channel: u8,
+        /// Parameter group number of the transported message
+        pgn: u32,
+        /// Source address of the originator
+        source_address: u8,
+        /// Destination address (0xFF for BAM broadcasts)
+        destination_address: u8,
+        /// Complete reassembled payload bytes
+        payload: Vec<u8>,
+    },
+
+    /// A J1939 address claim (PGN 60928) binding a source address to a NAME
+    J1939AddressClaim {
+        /// Absolute timestamp from the log file
+        timestamp: Timestamp,
+        /// CAN channel number
+        channel: u8,
+        /// Claimed source address (0xFE for "cannot claim address")
+        source_address: u8,
+        /// NAME of the claiming controller application
+        name: J1939Name,
+        /// NAME that previously held this address on the channel (if different)
+        previous_name: Option<J1939Name>,
+    },
+
     /// An AUTOSAR container PDU with raw contained PDUs (before signal decoding)
     ContainerPdu {
         /// Absolute timestamp from the log file
         timestamp: Timestamp,
-        /// Container PDU CAN ID
+        /// CAN channel of the carrying frame
+        channel: u8,
+        /// CAN ID of the carrying frame
         container_id: u32,
+        /// True if the container was sent in a CAN-FD frame
+        is_fd: bool,
         /// Container name from ARXML
         container_name: String,
         /// Type of container (Static/Dynamic/Queued)
@@ -151,6 +252,223 @@ pub enum DecodedEvent {
     },
 }
 
+/// Protocol errors detected while reassembling CAN-TP (ISO-TP) messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanTpErrorKind {
+    /// Consecutive frame arrived with an unexpected sequence number
+    SequenceError {
+        /// Sequence number that was expected
+        expected: u8,
+        /// Sequence number that was received
+        received: u8,
+    },
+    /// No frame arrived within the configured timeout
+    Timeout {
+        /// Payload bytes received before the timeout
+        received: usize,
+        /// Payload bytes announced in the First Frame
+        expected: usize,
+    },
+    /// Consecutive frame without a preceding First Frame
+    UnexpectedConsecutiveFrame {
+        /// Sequence number of the orphaned frame
+        sequence_number: u8,
+    },
+    /// A new Single/First Frame started before the previous transfer completed
+    InterruptedTransfer {
+        /// Payload bytes received before the interruption
+        received: usize,
+        /// Payload bytes announced in the First Frame
+        expected: usize,
+    },
+    /// Receiver answered with Flow Control OVFLW (buffer overflow)
+    FlowControlOverflow,
+    /// Receiver sent more Flow Control WAIT frames than allowed
+    WaitLimitExceeded {
+        /// Number of WAIT frames received
+        wait_frames: usize,
+    },
+    /// Frame could not be interpreted as a valid ISO-TP frame
+    InvalidFrame(String),
+}
+
+impl fmt::Display for CanTpErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanTpErrorKind::SequenceError { expected, received } => {
+                write!(f, "Sequence error: expected SN {}, received SN {}", expected, received)
+            }
+            CanTpErrorKind::Timeout { received, expected } => {
+                write!(f, "Timeout after {}/{} bytes", received, expected)
+            }
+            CanTpErrorKind::UnexpectedConsecutiveFrame { sequence_number } => {
+                write!(f, "Unexpected consecutive frame (SN {})", sequence_number)
+            }
+            CanTpErrorKind::InterruptedTransfer { received, expected } => {
+                write!(f, "Transfer interrupted after {}/{} bytes", received, expected)
+            }
+            CanTpErrorKind::FlowControlOverflow => write!(f, "Flow control overflow"),
+            CanTpErrorKind::WaitLimitExceeded { wait_frames } => {
+                write!(f, "Too many flow control WAIT frames ({})", wait_frames)
+            }
+            CanTpErrorKind::InvalidFrame(msg) => write!(f, "Invalid frame: {}", msg),
+        }
+    }
+}
+
+/// A UDS request or response
+#[derive(Debug, Clone, PartialEq)]
+pub struct UdsMessage {
+    /// Service ID of the request (also for responses, e.g. 0x22 for a 0x62 response)
+    pub service_id: u8,
+    /// Service name (e.g. "ReadDataByIdentifier")
+    pub service_name: &'static str,
+    /// Request, positive or negative response
+    pub kind: UdsMessageKind,
+    /// Sub-function without the suppress-positive-response bit
+    pub sub_function: Option<u8>,
+    /// Suppress-positive-response bit of a request's sub-function
+    pub suppress_positive_response: bool,
+    /// Data identifiers (ReadDataByIdentifier, WriteDataByIdentifier, ...)
+    pub data_identifiers: Vec<u16>,
+    /// Names of `data_identifiers` (same order) from the UDS description database
+    pub did_names: Vec<Option<String>>,
+    /// Routine identifier (RoutineControl)
+    pub routine_id: Option<u16>,
+    /// DTCs (ClearDiagnosticInformation group, ReadDTCInformation records)
+    pub dtcs: Vec<UdsDtc>,
+    /// Remaining parameter bytes after sub-function and identifiers
+    pub data: Vec<u8>,
+    /// DID values decoded with the layouts from the UDS description database
+    pub signals: Vec<DecodedSignal>,
+    /// Time from the start of the request to the start of this response
+    pub response_time_ms: Option<f64>,
+}
+
+/// Direction and outcome of a UDS message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UdsMessageKind {
+    /// Request from the tester
+    Request,
+    /// Positive response (SID + 0x40)
+    PositiveResponse,
+    /// Negative response (0x7F)
+    NegativeResponse {
+        /// Negative response code
+        nrc: u8,
+        /// NRC name (e.g. "requestOutOfRange")
+        nrc_name: &'static str,
+    },
+}
+
+/// A diagnostic trouble code from a UDS message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UdsDtc {
+    /// 3-byte DTC number
+    pub code: u32,
+    /// DTC status byte (if present)
+    pub status: Option<u8>,
+    /// Fault text from the UDS description database
+    pub description: Option<String>,
+}
+
+/// Names of the DTC status bits (ISO 14229-1 DTCStatusMask), bit 0 first
+pub const DTC_STATUS_BITS: [&str; 8] = [
+    "testFailed",
+    "testFailedThisOperationCycle",
+    "pendingDTC",
+    "confirmedDTC",
+    "testNotCompletedSinceLastClear",
+    "testFailedSinceLastClear",
+    "testNotCompletedThisOperationCycle",
+    "warningIndicatorRequested",
+];
+
+impl UdsDtc {
+    /// Create a DTC without description
+    pub fn new(code: u32, status: Option<u8>) -> Self {
+        Self {
+            code,
+            status,
+            description: None,
+        }
+    }
+
+    /// Names of the status bits that are set
+    pub fn active_status_bits(&self) -> Vec<&'static str> {
+        let status = self.status.unwrap_or(0);
+        DTC_STATUS_BITS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| status & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// A 64-bit J1939 NAME (SAE J1939-81) identifying a controller application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct J1939Name(pub u64);
+
+impl J1939Name {
+    /// Build a NAME from the 8 little-endian data bytes of an address claim
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let bytes: [u8; 8] = data.get(..8)?.try_into().ok()?;
+        Some(Self(u64::from_le_bytes(bytes)))
+    }
+
+    /// Identity number (21 bits)
+    pub fn identity_number(&self) -> u32 {
+        (self.0 & 0x1F_FFFF) as u32
+    }
+
+    /// Manufacturer code (11 bits)
+    pub fn manufacturer_code(&self) -> u16 {
+        ((self.0 >> 21) & 0x7FF) as u16
+    }
+
+    /// ECU instance (3 bits)
+    pub fn ecu_instance(&self) -> u8 {
+        ((self.0 >> 32) & 0x07) as u8
+    }
+
+    /// Function instance (5 bits)
+    pub fn function_instance(&self) -> u8 {
+        ((self.0 >> 35) & 0x1F) as u8
+    }
+
+    /// Function (8 bits)
+    pub fn function(&self) -> u8 {
+        (self.0 >> 40) as u8
+    }
+
+    /// Vehicle system (7 bits)
+    pub fn vehicle_system(&self) -> u8 {
+        ((self.0 >> 49) & 0x7F) as u8
+    }
+
+    /// Vehicle system instance (4 bits)
+    pub fn vehicle_system_instance(&self) -> u8 {
+        ((self.0 >> 56) & 0x0F) as u8
+    }
+
+    /// Industry group (3 bits)
+    pub fn industry_group(&self) -> u8 {
+        ((self.0 >> 60) & 0x07) as u8
+    }
+
+    /// True if the controller can claim an arbitrary address
+    pub fn arbitrary_address_capable(&self) -> bool {
+        self.0 >> 63 != 0
+    }
+}
+
+impl fmt::Display for J1939Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016X}", self.0)
+    }
+}
+
 /// AUTOSAR container PDU types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContainerType {
@@ -181,6 +499,34 @@ pub struct ContainedPdu {
     pub name: String,
     /// Raw PDU data bytes
     pub data: Vec<u8>,
+    /// False if the PDU's update bit is cleared (data is stale and not decoded)
+    pub updated: bool,
+    /// Freshness value and authenticator if this is a Secured I-PDU
+    pub secured: Option<SecuredPduInfo>,
+}
+
+/// Freshness value and authenticator stripped from a Secured I-PDU (SecOC)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecuredPduInfo {
+    /// Secured I-PDU name
+    pub name: String,
+    /// Truncated freshness value as transmitted
+    pub freshness_value: u64,
+    /// Truncated authenticator (MAC) as transmitted, left-aligned bytes
+    pub authenticator: Vec<u8>,
+}
+
+/// Container PDU a decoded message was unpacked from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentContainer {
+    /// Container name from ARXML
+    pub name: String,
+    /// Contained PDU identifier (header ID in dynamic containers)
+    pub pdu_id: u32,
+    /// True if the container was sent in a CAN-FD frame
+    pub is_fd: bool,
+    /// SecOC data if the message was the payload of a Secured I-PDU
+    pub secured: Option<SecuredPduInfo>,
 }
 
 /// A message contained within an AUTOSAR container PDU (after signal decoding)
@@ -201,12 +547,12 @@ pub struct ContainedMessage {
 /// A decoded signal with its current value
 #[derive(Debug, Clone, PartialEq)]
 pub struct DecodedSignal {
-    /// Signal name from DBC/ARXML
-    pub name: String,
+    /// Signal name from DBC/ARXML (shared with the signal database)
+    pub name: Arc<str>,
     /// Current decoded value (no history tracked by decoder)
     pub value: SignalValue,
     /// Engineering unit (e.g., "km/h", "°C", "V")
-    pub unit: Option<String>,
+    pub unit: Option<Arc<str>>,
     /// Value description from value tables (e.g., "0=Off, 1=On")
     pub value_description: Option<String>,
     /// Raw value before scaling (useful for debugging)
@@ -269,6 +615,10 @@ impl DecodedEvent {
         match self {
             DecodedEvent::Message { timestamp, .. } => *timestamp,
             DecodedEvent::CanTpMessage { timestamp, .. } => *timestamp,
+            DecodedEvent::CanTpError { timestamp, .. } => *timestamp,
+            DecodedEvent::UdsMessage { timestamp, .. } => *timestamp,
+            DecodedEvent::J1939TpMessage { timestamp, .. } => *timestamp,
+            DecodedEvent::J1939AddressClaim { timestamp, .. } => *timestamp,
             DecodedEvent::ContainerPdu { timestamp, .. } => *timestamp,
             DecodedEvent::RawFrame { timestamp, .. } => *timestamp,
         }
@@ -279,7 +629,11 @@ impl DecodedEvent {
         match self {
             DecodedEvent::Message { channel, .. } => Some(*channel),
             DecodedEvent::CanTpMessage { channel, .. } => Some(*channel),
-            DecodedEvent::ContainerPdu { .. } => None, // Channel not stored in container
+            DecodedEvent::CanTpError { channel, .. } => Some(*channel),
+            DecodedEvent::UdsMessage { channel, .. } => Some(*channel),
+            DecodedEvent::J1939TpMessage { channel, .. } => Some(*channel),
+            DecodedEvent::J1939AddressClaim { channel, .. } => Some(*channel),
+            DecodedEvent::ContainerPdu { channel, .. } => Some(*channel),
             DecodedEvent::RawFrame { channel, .. } => Some(*channel),
         }
     }
@@ -291,6 +645,10 @@ impl DecodedEvent {
             DecodedEvent::ContainerPdu { container_id, .. } => Some(*container_id),
             DecodedEvent::RawFrame { can_id, .. } => Some(*can_id),
             DecodedEvent::CanTpMessage { .. } => None,
+            DecodedEvent::CanTpError { .. } => None,
+            DecodedEvent::UdsMessage { .. } => None,
+            DecodedEvent::J1939TpMessage { .. } => None,
+            DecodedEvent::J1939AddressClaim { .. } => None,
         }
     }
 }
@@ -300,6 +658,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::approx_constant)] // Arbitrary decimals, not π
     fn test_signal_value_conversions() {
         let int_val = SignalValue::Integer(42);
         assert_eq!(int_val.as_f64(), 42.0);
@@ -316,6 +675,28 @@ mod tests {
     }
 
     #[test]
+    fn test_j1939_name_fields() {
+        let name = J1939Name::from_bytes(&[0x15, 0x76, 0xD1, 0x20, 0x00, 0x82, 0x01, 0x80]).unwrap();
+        assert_eq!(name.identity_number(), 0x117615);
+        assert_eq!(name.manufacturer_code(), 0x106);
+        assert_eq!(name.ecu_instance(), 0);
+        assert_eq!(name.function(), 0x82);
+        assert_eq!(name.vehicle_system(), 0);
+        assert_eq!(name.industry_group(), 0);
+        assert!(name.arbitrary_address_capable());
+        assert_eq!(name.to_string(), "8001820020D17615");
+        assert!(J1939Name::from_bytes(&[0; 7]).is_none());
+    }
+
+    #[test]
+    fn test_dtc_status_bits() {
+        let dtc = UdsDtc::new(0x123456, Some(0x09));
+        assert_eq!(dtc.active_status_bits(), vec!["testFailed", "confirmedDTC"]);
+        assert!(UdsDtc::new(0x123456, None).active_status_bits().is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)] // Arbitrary decimals, not π
     fn test_signal_value_display() {
         assert_eq!(format!("{}", SignalValue::Integer(42)), "42");
         assert_eq!(format!("{}", SignalValue::Float(3.14159)), "3.142");
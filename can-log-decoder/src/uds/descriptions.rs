@@ -0,0 +1,418 @@
+//! DID and DTC descriptions for UDS decoding
+//!
+//! An optional description file gives data identifiers a name and a data layout,
+//! and DTCs a readable fault text. TOML and JSON are accepted:
+//!
+//! ```toml
+//! [[did]]
+//! id = 0xF40D
+//! name = "VehicleSpeed"
+//! size = 1
+//!
+//! [[did.signals]]
+//! name = "VehicleSpeed"
+//! start_bit = 7
+//! length = 8
+//! unit = "km/h"
+//!
+//! [[dtc]]
+//! code = 0x9A0123
+//! text = "Battery voltage too low"
+//! ```
+//!
+//! Signal fields follow `SignalDefinition` (DBC bit numbering, start bit = MSB for
+//! big-endian signals). `byte_order` defaults to `big_endian` and `value_type` to
+//! `unsigned`, as is usual for UDS data records.
+
+use super::take_u16;
+use crate::message_decoder::MessageDecoder;
+use crate::signals::database::{ByteOrder, SignalDefinition, ValueType};
+use crate::types::{DecoderError, Result, UdsMessage, UdsMessageKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Top-level layout of a description file
+#[derive(Debug, Default, Deserialize)]
+struct DescriptionFile {
+    #[serde(default)]
+    did: Vec<DidDescription>,
+    #[serde(default)]
+    dtc: Vec<DtcDescription>,
+}
+
+/// Description of a data identifier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidDescription {
+    /// Data identifier (e.g. 0xF190)
+    pub id: u16,
+    /// DID name
+    pub name: String,
+    /// Data record length in bytes (derived from the signals if omitted)
+    #[serde(default)]
+    pub size: Option<usize>,
+    /// Signals in the data record
+    #[serde(default)]
+    pub signals: Vec<DidSignalDescription>,
+}
+
+/// A signal within a DID data record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DidSignalDescription {
+    /// Signal name
+    pub name: String,
+    /// Start bit within the data record
+    pub start_bit: u16,
+    /// Length in bits
+    pub length: u16,
+    /// Byte order (default: big-endian)
+    #[serde(default = "default_byte_order")]
+    pub byte_order: ByteOrder,
+    /// Value type (default: unsigned)
+    #[serde(default = "default_value_type")]
+    pub value_type: ValueType,
+    /// Scale factor (default: 1.0)
+    #[serde(default = "default_factor")]
+    pub factor: f64,
+    /// Offset (default: 0.0)
+    #[serde(default)]
+    pub offset: f64,
+    /// Engineering unit
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Value descriptions, raw value → text (string keys so TOML tables work)
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+}
+
+/// Description of a diagnostic trouble code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DtcDescription {
+    /// 3-byte DTC number
+    pub code: u32,
+    /// Fault text
+    pub text: String,
+}
+
+fn default_byte_order() -> ByteOrder {
+    ByteOrder::BigEndian
+}
+
+fn default_value_type() -> ValueType {
+    ValueType::Unsigned
+}
+
+fn default_factor() -> f64 {
+    1.0
+}
+
+/// A DID ready for decoding
+#[derive(Debug, Clone)]
+struct DidLayout {
+    name: String,
+    size: Option<usize>,
+    signals: Vec<SignalDefinition>,
+}
+
+/// DID and DTC descriptions used to annotate decoded UDS messages
+#[derive(Debug, Clone, Default)]
+pub struct UdsDescriptions {
+    dids: HashMap<u16, DidLayout>,
+    dtcs: HashMap<u32, String>,
+}
+
+impl UdsDescriptions {
+    /// Create an empty description set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load descriptions from a `.toml` or `.json` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|s| s.to_str()).map(|s| s.to_lowercase());
+
+        let result = match extension.as_deref() {
+            Some("toml") => Self::from_toml_str(&content),
+            Some("json") => Self::from_json_str(&content),
+            _ => {
+                return Err(DecoderError::UdsDescriptionParseError(format!(
+                    "Unsupported file format: {:?}",
+                    extension
+                )))
+            }
+        };
+        result.map_err(|e| DecoderError::UdsDescriptionParseError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parse descriptions from a TOML string
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        let file: DescriptionFile =
+            toml::from_str(content).map_err(|e| DecoderError::UdsDescriptionParseError(e.to_string()))?;
+        Self::from_description_file(file)
+    }
+
+    /// Parse descriptions from a JSON string
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        let file: DescriptionFile =
+            serde_json::from_str(content).map_err(|e| DecoderError::UdsDescriptionParseError(e.to_string()))?;
+        Self::from_description_file(file)
+    }
+
+    fn from_description_file(file: DescriptionFile) -> Result<Self> {
+        let mut descriptions = Self::new();
+        for did in file.did {
+            descriptions.add_did(did)?;
+        }
+        for dtc in file.dtc {
+            descriptions.add_dtc(dtc.code, dtc.text);
+        }
+        Ok(descriptions)
+    }
+
+    /// Add (or replace) a DID description
+    pub fn add_did(&mut self, did: DidDescription) -> Result<()> {
+        let signals = did
+            .signals
+            .into_iter()
+            .map(Self::convert_signal)
+            .collect::<Result<Vec<_>>>()?;
+
+        let size = did.size.or_else(|| {
+            signals
+                .iter()
+                .map(|s| crate::signals::extraction::required_bytes(s.byte_order, s.start_bit as usize, s.length as usize))
+                .max()
+        });
+
+        self.dids.insert(
+            did.id,
+            DidLayout {
+                name: did.name,
+                size,
+                signals,
+            },
+        );
+        Ok(())
+    }
+
+    /// Add (or replace) a DTC fault text
+    pub fn add_dtc(&mut self, code: u32, text: impl Into<String>) {
+        self.dtcs.insert(code, text.into());
+    }
+
+    /// Merge another description set into this one (later definitions win)
+    pub fn merge(&mut self, other: UdsDescriptions) {
+        self.dids.extend(other.dids);
+        self.dtcs.extend(other.dtcs);
+    }
+
+    /// True if no DIDs or DTCs are described
+    pub fn is_empty(&self) -> bool {
+        self.dids.is_empty() && self.dtcs.is_empty()
+    }
+
+    /// Name of a data identifier
+    pub fn did_name(&self, id: u16) -> Option<&str> {
+        self.dids.get(&id).map(|did| did.name.as_str())
+    }
+
+    /// Fault text of a DTC
+    pub fn dtc_text(&self, code: u32) -> Option<&str> {
+        self.dtcs.get(&code).map(String::as_str)
+    }
+
+    /// Add DID names, decoded DID values and DTC texts to a UDS message
+    pub fn annotate(&self, uds: &mut UdsMessage) {
+        if self.is_empty() {
+            return;
+        }
+
+        // Data records follow the DID in read responses and write requests
+        if matches!(
+            (uds.service_id, uds.kind),
+            (0x22, UdsMessageKind::PositiveResponse) | (0x2E, UdsMessageKind::Request)
+        ) {
+            self.decode_did_records(uds);
+        }
+
+        uds.did_names = uds
+            .data_identifiers
+            .iter()
+            .map(|id| self.did_name(*id).map(str::to_string))
+            .collect();
+
+        for dtc in &mut uds.dtcs {
+            dtc.description = self.dtc_text(dtc.code).map(str::to_string);
+        }
+    }
+
+    /// Decode the data records in `uds.data`
+    ///
+    /// A ReadDataByIdentifier response can hold several `DID record` blocks; they
+    /// can only be split while every DID has a known size.
+    fn decode_did_records(&self, uds: &mut UdsMessage) {
+        let Some(&first) = uds.data_identifiers.first() else {
+            return;
+        };
+
+        let mut did = first;
+        let mut rest = uds.data.as_slice();
+        let mut more_dids = Vec::new();
+        let mut signals = Vec::new();
+
+        while let Some(layout) = self.dids.get(&did) {
+            let record = match layout.size {
+                Some(size) if size <= rest.len() => &rest[..size],
+                Some(_) => break,
+                None => rest,
+            };
+            signals.extend(layout.signals.iter().filter_map(|s| MessageDecoder::decode_signal(record, s)));
+
+            if layout.size.is_none() || uds.service_id != 0x22 {
+                break;
+            }
+            let Some((next, tail)) = take_u16(&rest[record.len()..]) else {
+                break;
+            };
+            more_dids.push(next);
+            did = next;
+            rest = tail;
+        }
+
+        uds.data_identifiers.extend(more_dids);
+        uds.signals.extend(signals);
+    }
+
+    fn convert_signal(signal: DidSignalDescription) -> Result<SignalDefinition> {
+        let value_table = signal
+            .values
+            .into_iter()
+            .map(|(raw, text)| {
+                let raw = parse_raw_value(&raw).ok_or_else(|| {
+                    DecoderError::UdsDescriptionParseError(format!(
+                        "Invalid raw value '{}' in value table of signal '{}'",
+                        raw, signal.name
+                    ))
+                })?;
+                Ok((raw, text))
+            })
+            .collect::<Result<HashMap<i64, String>>>()?;
+
+        Ok(SignalDefinition {
+            name: signal.name,
+            start_bit: signal.start_bit,
+            length: signal.length,
+            byte_order: signal.byte_order,
+            value_type: signal.value_type,
+            factor: signal.factor,
+            offset: signal.offset,
+            min: 0.0,
+            max: 0.0,
+            unit: signal.unit,
+            value_table: (!value_table.is_empty()).then_some(value_table),
+            conversion: None,
+            multiplexer_info: None,
+            attributes: HashMap::new(),
+            comment: None,
+            extraction: None,
+        })
+    }
+}
+
+/// Parse a decimal or `0x` hexadecimal value table key
+fn parse_raw_value(text: &str) -> Option<i64> {
+    let text = text.trim();
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SignalValue;
+    use crate::uds::parse_uds_payload;
+
+    const DESCRIPTIONS: &str = r#"
+        [[did]]
+        id = 0xF40D
+        name = "VehicleSpeed"
+
+        [[did.signals]]
+        name = "VehicleSpeed"
+        start_bit = 7
+        length = 8
+        unit = "km/h"
+
+        [[did]]
+        id = 0x0101
+        name = "DoorState"
+        size = 2
+
+        [[did.signals]]
+        name = "DriverDoor"
+        start_bit = 0
+        length = 2
+        byte_order = "little_endian"
+        values = { 0 = "Closed", 1 = "Open" }
+
+        [[did.signals]]
+        name = "BatteryVoltage"
+        start_bit = 15
+        length = 8
+        factor = 0.1
+
+        [[dtc]]
+        code = 0x9A0123
+        text = "Battery voltage too low"
+    "#;
+
+    #[test]
+    fn test_decode_multiple_did_records() {
+        let descriptions = UdsDescriptions::from_toml_str(DESCRIPTIONS).unwrap();
+        let mut uds = parse_uds_payload(&[0x62, 0xF4, 0x0D, 0x32, 0x01, 0x01, 0x01, 0x7D]).unwrap();
+        descriptions.annotate(&mut uds);
+
+        assert_eq!(uds.data_identifiers, vec![0xF40D, 0x0101]);
+        assert_eq!(
+            uds.did_names,
+            vec![Some("VehicleSpeed".to_string()), Some("DoorState".to_string())]
+        );
+        let names: Vec<_> = uds.signals.iter().map(|s| &*s.name).collect();
+        assert_eq!(names, vec!["VehicleSpeed", "DriverDoor", "BatteryVoltage"]);
+        assert_eq!(uds.signals[0].value, SignalValue::Integer(50));
+        assert_eq!(uds.signals[1].value_description.as_deref(), Some("Open"));
+        assert!((uds.signals[2].value.as_f64() - 12.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dtc_text_and_json() {
+        let descriptions =
+            UdsDescriptions::from_json_str(r#"{"dtc": [{"code": 10092835, "text": "Battery voltage too low"}]}"#)
+                .unwrap();
+        let mut uds = parse_uds_payload(&[0x59, 0x02, 0xFF, 0x9A, 0x01, 0x23, 0x09]).unwrap();
+        descriptions.annotate(&mut uds);
+
+        assert_eq!(uds.dtcs[0].description.as_deref(), Some("Battery voltage too low"));
+        assert_eq!(uds.dtcs[0].active_status_bits(), vec!["testFailed", "confirmedDTC"]);
+    }
+
+    #[test]
+    fn test_invalid_value_table_key() {
+        let result = UdsDescriptions::from_toml_str(
+            r#"
+            [[did]]
+            id = 1
+            name = "X"
+            [[did.signals]]
+            name = "X"
+            start_bit = 7
+            length = 8
+            values = { on = "On" }
+            "#,
+        );
+        assert!(matches!(result, Err(DecoderError::UdsDescriptionParseError(_))));
+    }
+}
@@ -0,0 +1,457 @@
+//! UDS (ISO 14229) interpretation of CAN-TP payloads
+//!
+//! Turns reassembled `CanTpMessage` payloads into structured [`UdsMessage`]s:
+//!
+//! - **Request**: `SID [sub-function] [parameters]`
+//! - **Positive response**: `SID+0x40 [sub-function] [parameters]`
+//! - **Negative response**: `7F SID NRC`
+//!
+//! Requests are paired with the response travelling the opposite way on the
+//! same CAN-TP pair (source/target swapped) to measure the response time.
+//! NRC 0x78 (response pending) keeps the request open until the final answer.
+//!
+//! OBD-II services (0x01..0x0A) are not UDS and are ignored here.
+//!
+//! DID names/layouts and DTC texts can be added from a description file, see
+//! [`descriptions`].
+
+pub mod descriptions;
+
+use crate::types::{DecodedEvent, Timestamp, UdsDtc, UdsMessage, UdsMessageKind};
+use descriptions::UdsDescriptions;
+use std::collections::HashMap;
+
+/// Negative response SID
+const SID_NEGATIVE_RESPONSE: u8 = 0x7F;
+/// Offset between request and positive response SIDs
+pub(crate) const POSITIVE_RESPONSE_OFFSET: u8 = 0x40;
+/// Suppress-positive-response bit of the sub-function byte
+const SUPPRESS_POSITIVE_RESPONSE: u8 = 0x80;
+/// NRC: requestCorrectlyReceived-ResponsePending
+const NRC_RESPONSE_PENDING: u8 = 0x78;
+
+/// An outstanding request waiting for its response
+#[derive(Debug)]
+struct PendingRequest {
+    service_id: u8,
+    timestamp: Timestamp,
+}
+
+/// Stateful UDS interpreter that pairs requests with responses
+#[derive(Debug, Default)]
+pub struct UdsInterpreter<'a> {
+    /// Open requests, keyed by (channel, tester address, ECU address)
+    pending: HashMap<(u8, u32, u32), PendingRequest>,
+    /// Optional DID/DTC descriptions
+    descriptions: Option<&'a UdsDescriptions>,
+}
+
+impl<'a> UdsInterpreter<'a> {
+    /// Create a new interpreter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an interpreter that annotates messages with DID/DTC descriptions
+    pub fn with_descriptions(descriptions: &'a UdsDescriptions) -> Self {
+        Self {
+            pending: HashMap::new(),
+            descriptions: Some(descriptions),
+        }
+    }
+
+    /// Interpret a `CanTpMessage` event
+    ///
+    /// Returns `None` for other events and for payloads that are not UDS.
+    pub fn process_event(&mut self, event: &DecodedEvent) -> Option<DecodedEvent> {
+        let DecodedEvent::CanTpMessage {
+            timestamp,
+            channel,
+            source_addr,
+            target_addr,
+            payload,
+            ..
+        } = event
+        else {
+            return None;
+        };
+
+        let mut uds = parse_uds_payload(payload)?;
+        if let Some(descriptions) = self.descriptions {
+            descriptions.annotate(&mut uds);
+        }
+
+        match uds.kind {
+            UdsMessageKind::Request => {
+                self.pending.insert(
+                    (*channel, *source_addr, *target_addr),
+                    PendingRequest {
+                        service_id: uds.service_id,
+                        timestamp: *timestamp,
+                    },
+                );
+            }
+            UdsMessageKind::PositiveResponse | UdsMessageKind::NegativeResponse { .. } => {
+                // The response travels the opposite way of its request
+                let key = (*channel, *target_addr, *source_addr);
+                if let Some(request) = self.pending.get(&key).filter(|r| r.service_id == uds.service_id) {
+                    let elapsed = *timestamp - request.timestamp;
+                    uds.response_time_ms = elapsed.num_microseconds().map(|us| us as f64 / 1000.0);
+
+                    let pending = matches!(
+                        uds.kind,
+                        UdsMessageKind::NegativeResponse {
+                            nrc: NRC_RESPONSE_PENDING,
+                            ..
+                        }
+                    );
+                    if !pending {
+                        self.pending.remove(&key);
+                    }
+                }
+            }
+        }
+
+        Some(DecodedEvent::UdsMessage {
+            timestamp: *timestamp,
+            channel: *channel,
+            source_addr: *source_addr,
+            target_addr: *target_addr,
+            uds,
+        })
+    }
+}
+
+/// Parse a single UDS payload without request/response pairing
+pub fn parse_uds_payload(payload: &[u8]) -> Option<UdsMessage> {
+    let (&sid, rest) = payload.split_first()?;
+
+    if sid == SID_NEGATIVE_RESPONSE {
+        let (&service_id, &nrc) = (rest.first()?, rest.get(1)?);
+        let mut uds = UdsMessage::new(service_id, UdsMessageKind::NegativeResponse {
+            nrc,
+            nrc_name: nrc_name(nrc),
+        });
+        uds.data = rest[2..].to_vec();
+        return Some(uds);
+    }
+
+    let (service_id, kind) = if sid & POSITIVE_RESPONSE_OFFSET != 0 {
+        (sid - POSITIVE_RESPONSE_OFFSET, UdsMessageKind::PositiveResponse)
+    } else {
+        (sid, UdsMessageKind::Request)
+    };
+    if !is_uds_service(service_id) {
+        return None;
+    }
+
+    let mut uds = UdsMessage::new(service_id, kind);
+    let mut params = rest;
+
+    if has_sub_function(service_id) {
+        if let Some((&sub_function, tail)) = params.split_first() {
+            uds.sub_function = Some(sub_function & !SUPPRESS_POSITIVE_RESPONSE);
+            uds.suppress_positive_response =
+                kind == UdsMessageKind::Request && sub_function & SUPPRESS_POSITIVE_RESPONSE != 0;
+            params = tail;
+        }
+    }
+
+    match service_id {
+        // ReadDataByIdentifier: requests list DIDs, responses start with the first DID
+        0x22 if kind == UdsMessageKind::Request => {
+            uds.data_identifiers = params.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+            params = &params[params.len() - params.len() % 2..];
+        }
+        0x22 | 0x24 | 0x2E | 0x2F | 0x2C => {
+            if let Some((did, tail)) = take_u16(params) {
+                uds.data_identifiers.push(did);
+                params = tail;
+            }
+        }
+        0x31 => {
+            if let Some((rid, tail)) = take_u16(params) {
+                uds.routine_id = Some(rid);
+                params = tail;
+            }
+        }
+        0x14 if kind == UdsMessageKind::Request => {
+            if let Some((group, tail)) = take_u24(params) {
+                uds.dtcs.push(UdsDtc::new(group, None));
+                params = tail;
+            }
+        }
+        0x19 => params = parse_read_dtc_information(&mut uds, params),
+        _ => {}
+    }
+
+    uds.data = params.to_vec();
+    Some(uds)
+}
+
+/// Extract DTCs from ReadDTCInformation parameters, returning the unparsed rest
+fn parse_read_dtc_information<'a>(uds: &mut UdsMessage, params: &'a [u8]) -> &'a [u8] {
+    let Some(sub_function) = uds.sub_function else {
+        return params;
+    };
+
+    match (uds.kind, sub_function) {
+        // reportDTCSnapshotRecordByDTCNumber, reportDTCExtDataRecordByDTCNumber, ...
+        (UdsMessageKind::Request, 0x04 | 0x06 | 0x09 | 0x10) => match take_u24(params) {
+            Some((code, tail)) => {
+                uds.dtcs.push(UdsDtc::new(code, None));
+                tail
+            }
+            None => params,
+        },
+        (UdsMessageKind::PositiveResponse, 0x04 | 0x06 | 0x09 | 0x10) => match take_dtc_record(params) {
+            Some((dtc, tail)) => {
+                uds.dtcs.push(dtc);
+                tail
+            }
+            None => params,
+        },
+        // Status availability mask followed by DTC + status records
+        (UdsMessageKind::PositiveResponse, 0x02 | 0x0A | 0x0B..=0x0F | 0x13 | 0x15) if !params.is_empty() => {
+            let mut rest = &params[1..];
+            while let Some((dtc, tail)) = take_dtc_record(rest) {
+                uds.dtcs.push(dtc);
+                rest = tail;
+            }
+            rest
+        }
+        _ => params,
+    }
+}
+
+fn take_u16(data: &[u8]) -> Option<(u16, &[u8])> {
+    let value = u16::from_be_bytes([*data.first()?, *data.get(1)?]);
+    Some((value, &data[2..]))
+}
+
+fn take_u24(data: &[u8]) -> Option<(u32, &[u8])> {
+    let value = u32::from_be_bytes([0, *data.first()?, *data.get(1)?, *data.get(2)?]);
+    Some((value, &data[3..]))
+}
+
+/// DTC (3 bytes) followed by its status byte
+fn take_dtc_record(data: &[u8]) -> Option<(UdsDtc, &[u8])> {
+    let (code, rest) = take_u24(data)?;
+    let status = *rest.first()?;
+    Some((UdsDtc::new(code, Some(status)), &rest[1..]))
+}
+
+impl UdsMessage {
+    fn new(service_id: u8, kind: UdsMessageKind) -> Self {
+        Self {
+            service_id,
+            service_name: service_name(service_id),
+            kind,
+            sub_function: None,
+            suppress_positive_response: false,
+            data_identifiers: Vec::new(),
+            did_names: Vec::new(),
+            routine_id: None,
+            dtcs: Vec::new(),
+            data: Vec::new(),
+            signals: Vec::new(),
+            response_time_ms: None,
+        }
+    }
+}
+
+/// True for request SIDs defined by ISO 14229 (and the supplier-specific range)
+fn is_uds_service(service_id: u8) -> bool {
+    matches!(service_id, 0x10..=0x3E | 0x83..=0x88 | 0xBA..=0xBE)
+}
+
+/// Services whose first parameter byte is a sub-function
+fn has_sub_function(service_id: u8) -> bool {
+    matches!(
+        service_id,
+        0x10 | 0x11 | 0x19 | 0x27 | 0x28 | 0x29 | 0x2C | 0x31 | 0x3E | 0x83 | 0x85 | 0x86 | 0x87
+    )
+}
+
+/// Name of a UDS service
+pub fn service_name(service_id: u8) -> &'static str {
+    match service_id {
+        0x10 => "DiagnosticSessionControl",
+        0x11 => "ECUReset",
+        0x14 => "ClearDiagnosticInformation",
+        0x19 => "ReadDTCInformation",
+        0x22 => "ReadDataByIdentifier",
+        0x23 => "ReadMemoryByAddress",
+        0x24 => "ReadScalingDataByIdentifier",
+        0x27 => "SecurityAccess",
+        0x28 => "CommunicationControl",
+        0x29 => "Authentication",
+        0x2A => "ReadDataByPeriodicIdentifier",
+        0x2C => "DynamicallyDefineDataIdentifier",
+        0x2E => "WriteDataByIdentifier",
+        0x2F => "InputOutputControlByIdentifier",
+        0x31 => "RoutineControl",
+        0x34 => "RequestDownload",
+        0x35 => "RequestUpload",
+        0x36 => "TransferData",
+        0x37 => "RequestTransferExit",
+        0x38 => "RequestFileTransfer",
+        0x3D => "WriteMemoryByAddress",
+        0x3E => "TesterPresent",
+        0x83 => "AccessTimingParameter",
+        0x84 => "SecuredDataTransmission",
+        0x85 => "ControlDTCSetting",
+        0x86 => "ResponseOnEvent",
+        0x87 => "LinkControl",
+        0xBA..=0xBE => "SystemSupplierSpecific",
+        _ => "Unknown",
+    }
+}
+
+/// Name of a negative response code
+pub fn nrc_name(nrc: u8) -> &'static str {
+    match nrc {
+        0x10 => "generalReject",
+        0x11 => "serviceNotSupported",
+        0x12 => "subFunctionNotSupported",
+        0x13 => "incorrectMessageLengthOrInvalidFormat",
+        0x14 => "responseTooLong",
+        0x21 => "busyRepeatRequest",
+        0x22 => "conditionsNotCorrect",
+        0x24 => "requestSequenceError",
+        0x25 => "noResponseFromSubnetComponent",
+        0x26 => "failurePreventsExecutionOfRequestedAction",
+        0x31 => "requestOutOfRange",
+        0x33 => "securityAccessDenied",
+        0x34 => "authenticationRequired",
+        0x35 => "invalidKey",
+        0x36 => "exceededNumberOfAttempts",
+        0x37 => "requiredTimeDelayNotExpired",
+        0x70 => "uploadDownloadNotAccepted",
+        0x71 => "transferDataSuspended",
+        0x72 => "generalProgrammingFailure",
+        0x73 => "wrongBlockSequenceCounter",
+        0x78 => "requestCorrectlyReceivedResponsePending",
+        0x7E => "subFunctionNotSupportedInActiveSession",
+        0x7F => "serviceNotSupportedInActiveSession",
+        0x81 => "rpmTooHigh",
+        0x82 => "rpmTooLow",
+        0x83 => "engineIsRunning",
+        0x84 => "engineIsNotRunning",
+        0x85 => "engineRunTimeTooLow",
+        0x86 => "temperatureTooHigh",
+        0x87 => "temperatureTooLow",
+        0x88 => "vehicleSpeedTooHigh",
+        0x89 => "vehicleSpeedTooLow",
+        0x8A => "throttlePedalTooHigh",
+        0x8B => "throttlePedalTooLow",
+        0x8C => "transmissionRangeNotInNeutral",
+        0x8D => "transmissionRangeNotInGear",
+        0x8F => "brakeSwitchesNotClosed",
+        0x90 => "shifterLeverNotInPark",
+        0x91 => "torqueConverterClutchLocked",
+        0x92 => "voltageTooHigh",
+        0x93 => "voltageTooLow",
+        0xF0..=0xFE => "vehicleManufacturerSpecific",
+        _ => "ISOSAEReserved",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn cantp(timestamp_ms: i64, source_addr: u32, target_addr: u32, payload: &[u8]) -> DecodedEvent {
+        DecodedEvent::CanTpMessage {
+            timestamp: DateTime::from_timestamp_millis(timestamp_ms).unwrap(),
+            channel: 0,
+            source_addr,
+            target_addr,
+            address_extension: None,
+            payload: payload.to_vec(),
+            payload_length: payload.len(),
+        }
+    }
+
+    fn uds_of(event: Option<DecodedEvent>) -> UdsMessage {
+        match event {
+            Some(DecodedEvent::UdsMessage { uds, .. }) => uds,
+            other => panic!("Expected UdsMessage, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_requests_and_responses() {
+        let uds = parse_uds_payload(&[0x10, 0x83]).unwrap();
+        assert_eq!(uds.service_name, "DiagnosticSessionControl");
+        assert_eq!(uds.kind, UdsMessageKind::Request);
+        assert_eq!(uds.sub_function, Some(0x03));
+        assert!(uds.suppress_positive_response);
+
+        let uds = parse_uds_payload(&[0x22, 0xF1, 0x90, 0xF1, 0x87]).unwrap();
+        assert_eq!(uds.data_identifiers, vec![0xF190, 0xF187]);
+
+        let uds = parse_uds_payload(&[0x62, 0xF1, 0x90, b'W', b'0', b'L']).unwrap();
+        assert_eq!(uds.kind, UdsMessageKind::PositiveResponse);
+        assert_eq!(uds.service_id, 0x22);
+        assert_eq!(uds.data_identifiers, vec![0xF190]);
+        assert_eq!(uds.data, b"W0L");
+
+        let uds = parse_uds_payload(&[0x71, 0x01, 0xFF, 0x00, 0x02]).unwrap();
+        assert_eq!(uds.sub_function, Some(0x01));
+        assert_eq!(uds.routine_id, Some(0xFF00));
+        assert_eq!(uds.data, vec![0x02]);
+
+        let uds = parse_uds_payload(&[0x7F, 0x27, 0x35]).unwrap();
+        assert_eq!(uds.service_name, "SecurityAccess");
+        assert_eq!(
+            uds.kind,
+            UdsMessageKind::NegativeResponse {
+                nrc: 0x35,
+                nrc_name: "invalidKey"
+            }
+        );
+
+        // OBD-II is not UDS
+        assert!(parse_uds_payload(&[0x01, 0x0C]).is_none());
+        assert!(parse_uds_payload(&[]).is_none());
+    }
+
+    #[test]
+    fn test_parse_dtc_list() {
+        let uds = parse_uds_payload(&[0x59, 0x02, 0xFF, 0x12, 0x34, 0x56, 0x2F, 0xC1, 0x00, 0x01, 0x08]).unwrap();
+        assert_eq!(uds.sub_function, Some(0x02));
+        assert_eq!(
+            uds.dtcs,
+            vec![
+                UdsDtc::new(0x123456, Some(0x2F)),
+                UdsDtc::new(0xC10001, Some(0x08)),
+            ]
+        );
+        assert!(uds.data.is_empty());
+
+        let uds = parse_uds_payload(&[0x14, 0xFF, 0xFF, 0xFF]).unwrap();
+        assert_eq!(uds.dtcs, vec![UdsDtc::new(0xFFFFFF, None)]);
+    }
+
+    #[test]
+    fn test_request_response_pairing() {
+        let mut interpreter = UdsInterpreter::new();
+
+        let request = uds_of(interpreter.process_event(&cantp(1000, 0x7E0, 0x7E8, &[0x22, 0xF1, 0x90])));
+        assert_eq!(request.response_time_ms, None);
+
+        // Response pending, then the final positive response
+        let pending = uds_of(interpreter.process_event(&cantp(1020, 0x7E8, 0x7E0, &[0x7F, 0x22, 0x78])));
+        assert_eq!(pending.response_time_ms, Some(20.0));
+        let response = uds_of(interpreter.process_event(&cantp(1150, 0x7E8, 0x7E0, &[0x62, 0xF1, 0x90, 0x01])));
+        assert_eq!(response.response_time_ms, Some(150.0));
+
+        // Request is closed, a repeated response is unpaired
+        let repeated = uds_of(interpreter.process_event(&cantp(1200, 0x7E8, 0x7E0, &[0x62, 0xF1, 0x90, 0x01])));
+        assert_eq!(repeated.response_time_ms, None);
+
+        assert!(interpreter.process_event(&cantp(1300, 0x7E0, 0x7E8, &[0x01, 0x0C])).is_none());
+    }
+}